@@ -0,0 +1,268 @@
+# German. Messages missing here are shown in English
+
+language-name = Deutsch
+number-group-separator = .
+
+## Shared
+
+ui-close = Schließen
+ui-main-menu = Zurück zum Menü
+ui-back = Zurück
+mode-free-play = Freies Spiel
+mode-campaign = Kampagne
+mode-endless = Endlos
+mode-daily = Tägliche Herausforderung
+
+## Main menu
+
+menu-new-game = Freies Spiel
+menu-new-campaign = Neue Kampagne
+menu-continue-campaign = Kampagne fortsetzen
+menu-endless = Endlos
+menu-daily = Tägliche Herausforderung
+menu-daily-done = Tägliche Herausforderung: { NUMBER($score) } Punkte
+menu-records = Rekorde
+menu-achievements = Erfolge
+menu-skills = Fähigkeiten
+menu-options = Optionen
+menu-exit = Beenden
+
+## Top bar of the shop
+
+hud-barter = Feilschen
+hud-purchase = Einkaufen
+hud-upgrades = Ausbau
+hud-bank = Bank
+hud-ledger = Kassenbuch
+hud-options = Optionen
+hud-pause = Pause
+hud-gold = Gold: { NUMBER($amount) }
+hud-reputation = Ruf: { $amount }
+hud-gold-gained = +{ NUMBER($amount) }
+hud-gold-lost = -{ NUMBER($amount) }
+
+## Barter screen
+
+haggle-plea = Flehen
+haggle-persuade = Überreden
+haggle-bully = Einschüchtern
+haggle-charm = Bezirzen
+barter-approve = Annehmen
+barter-deny = Ablehnen
+barter-patience = Geduld
+barter-budget = Budget: { NUMBER($budget) } Gold
+barter-appraise = Einschätzen
+barter-appraisal-resistance = Widerstand gegen { $haggle }: { $low } bis { $high }
+barter-appraisal-budget = Budget: { NUMBER($low) } bis { NUMBER($high) } Gold
+barter-result-success = { $haggle } erfolgreich!
+barter-result-failure = { $haggle } gescheitert!
+barter-new-price = Neuer Preis: { NUMBER($price) } Gold
+
+## Items
+
+item-trinket = Schmuckstück
+item-potion = Trank
+item-tome = Foliant
+item-weapon = Waffe
+item-relic = Relikt
+
+## Supplier screen
+
+purchase-title = Händler
+purchase-buy = Kaufen
+purchase-capacity = Platz für Vorrat: { $stock } von { $capacity }
+purchase-row = { $item } - Vorrat: { $stock } - Preis: { NUMBER($cost) } Gold ({ $trend }%)
+
+## Upgrades screen
+
+upgrades-title = Ausbau des Ladens
+upgrades-buy = Ausbauen
+upgrades-row = { $upgrade } { $level }/{ $max } - { NUMBER($cost) } Gold: { $description }
+upgrades-row-maxed = { $upgrade } (voll ausgebaut): { $description }
+upgrade-counter = Größere Theke
+upgrade-counter-description = Platz für einen weiteren Kunden in der Schlange
+upgrade-display-cases = Vitrinen
+upgrade-display-cases-description = Kunden schätzen jede Ware höher und lassen sich leichter einschätzen
+upgrade-decor = Kerzen und Dekor
+upgrade-decor-description = Der Laden gewinnt jede Nacht an Ruf
+upgrade-back-room = Hinterzimmer
+upgrade-back-room-description = Platz für mehr Vorrat
+
+## Bank screen
+
+bank-borrow = { NUMBER($principal) } leihen ({ $interest }% über { $nights ->
+        [one] { $nights } Nacht
+       *[other] { $nights } Nächte
+    })
+bank-deposit = { NUMBER($amount) } einzahlen
+bank-withdraw = { NUMBER($amount) } abheben
+bank-pay-overdue = Überfällige Raten zahlen
+bank-summary =
+    Guthaben: { NUMBER($deposit) } Gold ({ $interest }% Zinsen pro Nacht)
+    Kredite: { NUMBER($owed) } offen, { NUMBER($overdue) } überfällig
+bank-no-history = Noch keine Buchungen
+bank-history-entry = Nacht { $night }: { $kind } { NUMBER($amount) } Gold
+bank-transaction-loan-taken = Kredit aufgenommen
+bank-transaction-loan-payment = Kreditrate
+bank-transaction-missed-payment = Verpasste Rate
+bank-transaction-deposit = Einzahlung
+bank-transaction-withdrawal = Abhebung
+bank-transaction-interest = Zinsen
+bank-transaction-collection = Inkasso
+bank-debt-collector = Schuldeneintreiber
+
+## Ledger screen
+
+ledger-previous-night = < Nacht
+ledger-next-night = Nacht >
+ledger-all-time = Gesamt
+ledger-export = Exportieren
+ledger-night = Nacht { $night }
+ledger-stats =
+    { $title }
+    Umsatz: { NUMBER($revenue) } Gold
+    Kosten: { NUMBER($costs) } Gold
+    Marge: { NUMBER($margin) } Gold
+    Verkäufe: { $sales } von { $barters ->
+        [one] { $barters } Verhandlung
+       *[other] { $barters } Verhandlungen
+    }
+    Gewonnenes Feilschen: { $won } von { $haggles } ({ $rate }%)
+ledger-chart-revenue = Umsatz
+ledger-chart-margin = Marge
+ledger-chart-haggle-success = Feilscherfolg %
+ledger-counterparty-supplier = Lieferant
+ledger-counterparty-bank = Bank
+ledger-counterparty-debt-collector = Schuldeneintreiber
+ledger-counterparty-landlord = Vermieter
+ledger-counterparty-carpenter = Schreiner
+
+## Pause screen
+
+pause-title = Pausiert
+pause-resume = Weiterspielen
+pause-quit = Ins Hauptmenü
+
+## Dialogue screen
+
+dialogue-leave = Auf Wiedersehen
+
+## Campaign
+
+campaign-continue = Weiter
+campaign-objectives = Ziele
+campaign-nights = { $nights ->
+        [one] Eine Nacht für dieses Kapitel
+       *[other] { $nights } Nächte für dieses Kapitel
+    }
+campaign-nights-left = { $nights ->
+        [one] Letzte Nacht
+       *[other] Noch { $nights } Nächte
+    }
+objective-earn-gold = Verdiene { NUMBER($amount) } Gold mit Verkäufen ({ NUMBER($progress) }/{ NUMBER($amount) })
+objective-reach-reputation = Erreiche { $amount } Ruf ({ $progress }/{ $amount })
+objective-sell-item = Verkaufe { $item }: { $progress }/{ $count }
+
+## Endless
+
+endless-rent = Miete im Morgengrauen: { NUMBER($amount) } Gold
+endless-bankrupt = Bankrott!
+endless-result =
+    Überstandene Nächte: { $nights }
+    Verdientes Gold: { NUMBER($gold) }
+    Punkte: { NUMBER($score) }
+daily-challenge-of = Tägliche Herausforderung vom { $date }
+
+## Difficulty
+
+difficulty-title = Schwierigkeit
+difficulty-preset = Schwierigkeit: { $preset }
+difficulty-easy = Leicht
+difficulty-normal = Normal
+difficulty-hard = Schwer
+difficulty-custom = Eigene
+difficulty-resistance = Widerstand der Kunden: { $value }
+difficulty-budget = Budget der Kunden: { NUMBER($value) } Gold
+difficulty-spawn-interval = Zeit zwischen Kunden: { $value }s
+difficulty-max-customers = Kunden im Laden: { $value }
+difficulty-price-step = Feilschschritt: { $value } Gold
+difficulty-expenses = Ausgaben: { $value }%
+difficulty-start = Starten
+
+## Records screen
+
+records-title = Rekorde
+records-mode = Modus: { $mode }
+records-all-modes = Alle
+records-sort = Sortiert nach: { $stat }
+records-sort-newest = Neueste
+records-sort-nights = Nächte
+records-sort-gold = Gold
+records-sort-reputation = Ruf
+records-sort-haggles = Gewonnenes Feilschen
+records-empty = Noch keine Durchläufe
+records-entry = { $mode }: { $nights } Nächte, { NUMBER($gold) } Gold, { $reputation } Ruf, { $haggles } mal erfolgreich gefeilscht (Seed { $seed })
+
+## Achievements
+
+achievements-title = Erfolge
+achievements-progress = { $unlocked } von { $total } freigeschaltet
+achievements-unlocked = Erfolg freigeschaltet!
+achievements-hidden-name = ???
+achievements-hidden-description = Spiel weiter, um es herauszufinden
+
+## Skills
+
+skills-title = Fähigkeiten
+skills-level = Stufe { $level }: { NUMBER($experience) } von { NUMBER($next) } Erfahrung
+skills-points = { $points ->
+    [one] { $points } Fähigkeitspunkt zu vergeben
+   *[other] { $points } Fähigkeitspunkte zu vergeben
+}
+skills-learn = Lernen ({ $cost })
+skills-learned = Gelernt
+skills-cost = Kostet { $cost } Punkte
+skills-requires = Benötigt { $skills }
+skill-bully-odds = Einschüchternde Präsenz
+skill-bully-odds-description = Einschüchtern gelingt öfter
+skill-bully-swing = Harte Hand
+skill-bully-swing-description = Erfolgreiches Einschüchtern hebt den Preis weiter an
+skill-persuade-odds = Silberzunge
+skill-persuade-odds-description = Überreden gelingt öfter
+skill-persuade-swing = Schlussplädoyer
+skill-persuade-swing-description = Erfolgreiches Überreden hebt den Preis weiter an
+skill-plea-odds = Rührselige Geschichte
+skill-plea-odds-description = Flehen gelingt öfter
+skill-plea-swing = Tränendrüse
+skill-plea-swing-description = Erfolgreiches Flehen hebt den Preis weiter an
+skill-charm = Charme
+skill-charm-description = Schaltet Bezirzen frei, das auf die schwächste Stelle des Kunden zielt
+skill-patience = Gute Gesellschaft
+skill-patience-description = Kunden lassen mehr Feilschen über sich ergehen
+skill-keen-eye = Scharfes Auge
+skill-keen-eye-description = Zeigt beim Feilschen das Budget des Kunden
+skill-appraiser = Gutachter
+skill-appraiser-description = Kunden einzuschätzen liefert genauere Werte
+
+## Options screen
+
+options-title = Optionen
+options-volume = { $setting }: { $percent }%
+volume-master = Gesamtlautstärke
+volume-music = Musik
+volume-sfx = Effekte
+volume-ui = Oberfläche
+volume-ambience = Atmosphäre
+options-theme = Design: { $theme }
+options-colors = Farben: { $palette }
+palette-standard = Standard
+palette-deuteranopia = Deuteranopie
+palette-protanopia = Protanopie
+palette-tritanopia = Tritanopie
+palette-high-contrast = Hoher Kontrast
+options-reduced-motion = Weniger Bewegung: { $enabled ->
+        [yes] An
+       *[no] Aus
+    }
+options-interface-size = Oberflächengröße: { $percent }%
+options-language = Sprache: { $language }
@@ -0,0 +1,269 @@
+# English, the language every other translation falls back to.
+# Numbers go through NUMBER() so their digits are grouped with the separator below
+
+language-name = English
+number-group-separator = ,
+
+## Shared
+
+ui-close = Close
+ui-main-menu = Back to Menu
+ui-back = Back
+mode-free-play = Free Play
+mode-campaign = Campaign
+mode-endless = Endless
+mode-daily = Daily Challenge
+
+## Main menu
+
+menu-new-game = Free Play
+menu-new-campaign = New Campaign
+menu-continue-campaign = Continue Campaign
+menu-endless = Endless
+menu-daily = Daily Challenge
+menu-daily-done = Daily Challenge: { NUMBER($score) } points
+menu-records = Records
+menu-achievements = Achievements
+menu-skills = Skills
+menu-options = Options
+menu-exit = Exit
+
+## Top bar of the shop
+
+hud-barter = Barter
+hud-purchase = Purchase
+hud-upgrades = Upgrades
+hud-bank = Bank
+hud-ledger = Ledger
+hud-options = Options
+hud-pause = Pause
+hud-gold = Gold: { NUMBER($amount) }
+hud-reputation = Reputation: { $amount }
+hud-gold-gained = +{ NUMBER($amount) }
+hud-gold-lost = -{ NUMBER($amount) }
+
+## Barter screen
+
+haggle-plea = Plea
+haggle-persuade = Persuade
+haggle-bully = Bully
+haggle-charm = Charm
+barter-approve = Approve
+barter-deny = Deny
+barter-patience = Patience
+barter-budget = Budget: { NUMBER($budget) } gold
+barter-appraise = Appraise
+barter-appraisal-resistance = { $haggle } resistance: { $low } to { $high }
+barter-appraisal-budget = Budget: { NUMBER($low) } to { NUMBER($high) } gold
+barter-result-success = { $haggle } Success!
+barter-result-failure = { $haggle } Failure!
+barter-new-price = New Price: { NUMBER($price) } gold
+
+## Items
+
+item-trinket = Trinket
+item-potion = Potion
+item-tome = Tome
+item-weapon = Weapon
+item-relic = Relic
+
+## Supplier screen
+
+purchase-title = Supplier
+purchase-buy = Buy
+purchase-capacity = Room for stock: { $stock } of { $capacity }
+purchase-row = { $item } - Stock: { $stock } - Cost: { NUMBER($cost) } gold ({ $trend }%)
+
+## Upgrades screen
+
+upgrades-title = Shop Upgrades
+upgrades-buy = Upgrade
+upgrades-row = { $upgrade } { $level }/{ $max } - { NUMBER($cost) } gold: { $description }
+upgrades-row-maxed = { $upgrade } (fully upgraded): { $description }
+upgrade-counter = Bigger Counter
+upgrade-counter-description = Room for one more customer in line
+upgrade-display-cases = Display Cases
+upgrade-display-cases-description = Customers value every item more and are easier to appraise
+upgrade-decor = Candles and Décor
+upgrade-decor-description = The shop gains reputation every night
+upgrade-back-room = Back Room
+upgrade-back-room-description = Room for more stock
+
+## Bank screen
+
+bank-borrow = Borrow { NUMBER($principal) } ({ $interest }% over { $nights ->
+        [one] { $nights } night
+       *[other] { $nights } nights
+    })
+bank-deposit = Deposit { NUMBER($amount) }
+bank-withdraw = Withdraw { NUMBER($amount) }
+bank-pay-overdue = Pay overdue gold
+bank-summary =
+    Deposit: { NUMBER($deposit) } gold ({ $interest }% nightly interest)
+    Loans: { NUMBER($owed) } owed, { NUMBER($overdue) } overdue
+bank-no-history = No transactions yet
+bank-history-entry = Night { $night }: { $kind } { NUMBER($amount) } gold
+bank-transaction-loan-taken = Loan taken
+bank-transaction-loan-payment = Loan payment
+bank-transaction-missed-payment = Missed payment
+bank-transaction-deposit = Deposit
+bank-transaction-withdrawal = Withdrawal
+bank-transaction-interest = Interest
+bank-transaction-collection = Collection
+bank-debt-collector = Debt Collector
+
+## Ledger screen
+
+ledger-previous-night = < Night
+ledger-next-night = Night >
+ledger-all-time = All Time
+ledger-export = Export
+ledger-night = Night { $night }
+ledger-stats =
+    { $title }
+    Revenue: { NUMBER($revenue) } gold
+    Costs: { NUMBER($costs) } gold
+    Margin: { NUMBER($margin) } gold
+    Sales: { $sales } of { $barters ->
+        [one] { $barters } barter
+       *[other] { $barters } barters
+    }
+    Haggles won: { $won } of { $haggles } ({ $rate }%)
+ledger-chart-revenue = Revenue
+ledger-chart-margin = Margin
+ledger-chart-haggle-success = Haggle Success %
+ledger-counterparty-supplier = Supplier
+ledger-counterparty-bank = Bank
+ledger-counterparty-debt-collector = Debt Collector
+ledger-counterparty-landlord = Landlord
+ledger-counterparty-carpenter = Carpenter
+
+## Pause screen
+
+pause-title = Paused
+pause-resume = Resume
+pause-quit = Quit to Menu
+
+## Dialogue screen
+
+dialogue-leave = Goodbye
+
+## Campaign
+
+campaign-continue = Continue
+campaign-objectives = Objectives
+campaign-nights = { $nights ->
+        [one] One night to finish the chapter
+       *[other] { $nights } nights to finish the chapter
+    }
+campaign-nights-left = { $nights ->
+        [one] Last night
+       *[other] { $nights } nights left
+    }
+objective-earn-gold = Earn { NUMBER($amount) } gold from sales ({ NUMBER($progress) }/{ NUMBER($amount) })
+objective-reach-reputation = Reach { $amount } reputation ({ $progress }/{ $amount })
+objective-sell-item = Sell { $item }: { $progress }/{ $count }
+
+## Endless
+
+endless-rent = Rent at dawn: { NUMBER($amount) } gold
+endless-bankrupt = Bankrupt!
+endless-result =
+    Nights survived: { $nights }
+    Gold earned: { NUMBER($gold) }
+    Score: { NUMBER($score) }
+daily-challenge-of = Daily challenge of { $date }
+
+## Difficulty
+
+difficulty-title = Difficulty
+difficulty-preset = Difficulty: { $preset }
+difficulty-easy = Easy
+difficulty-normal = Normal
+difficulty-hard = Hard
+difficulty-custom = Custom
+difficulty-resistance = Customer Resistance: { $value }
+difficulty-budget = Customer Budget: { NUMBER($value) } gold
+difficulty-spawn-interval = Time Between Customers: { $value }s
+difficulty-max-customers = Customers in the Shop: { $value }
+difficulty-price-step = Haggle Step: { $value } gold
+difficulty-expenses = Expenses: { $value }%
+difficulty-start = Start
+
+## Records screen
+
+records-title = Records
+records-mode = Mode: { $mode }
+records-all-modes = All
+records-sort = Sorted by: { $stat }
+records-sort-newest = Newest
+records-sort-nights = Nights
+records-sort-gold = Gold
+records-sort-reputation = Reputation
+records-sort-haggles = Haggles Won
+records-empty = No runs yet
+records-entry = { $mode }: { $nights } nights, { NUMBER($gold) } gold, { $reputation } reputation, { $haggles } haggles won (seed { $seed })
+
+## Achievements
+
+achievements-title = Achievements
+achievements-progress = { $unlocked } of { $total } unlocked
+achievements-unlocked = Achievement unlocked!
+achievements-hidden-name = ???
+achievements-hidden-description = Keep playing to find out
+
+## Skills
+
+skills-title = Skills
+skills-level = Level { $level }: { NUMBER($experience) } of { NUMBER($next) } experience
+skills-points = { $points ->
+    [one] { $points } skill point to spend
+   *[other] { $points } skill points to spend
+}
+skills-learn = Learn ({ $cost })
+skills-learned = Learned
+skills-cost = Costs { $cost } points
+skills-requires = Needs { $skills }
+skill-bully-odds = Intimidating Presence
+skill-bully-odds-description = Bullying works more often
+skill-bully-swing = Heavy Hand
+skill-bully-swing-description = Successful bullying raises the price further
+skill-persuade-odds = Silver Tongue
+skill-persuade-odds-description = Persuading works more often
+skill-persuade-swing = Closing Argument
+skill-persuade-swing-description = Successful persuading raises the price further
+skill-plea-odds = Sob Story
+skill-plea-odds-description = Pleas work more often
+skill-plea-swing = Tearjerker
+skill-plea-swing-description = Successful pleas raise the price further
+skill-charm = Charm
+skill-charm-description = Unlocks the charm haggle, which plays on what the customer resists least
+skill-patience = Good Company
+skill-patience-description = Customers put up with more haggling
+skill-keen-eye = Keen Eye
+skill-keen-eye-description = Shows the budget of the customer while bartering
+skill-appraiser = Appraiser
+skill-appraiser-description = Appraising a customer gives closer estimates
+
+## Options screen
+
+options-title = Options
+options-volume = { $setting }: { $percent }%
+volume-master = Master Volume
+volume-music = Music Volume
+volume-sfx = Effects Volume
+volume-ui = Interface Volume
+volume-ambience = Ambience Volume
+options-theme = Theme: { $theme }
+options-colors = Colors: { $palette }
+palette-standard = Standard
+palette-deuteranopia = Deuteranopia
+palette-protanopia = Protanopia
+palette-tritanopia = Tritanopia
+palette-high-contrast = High Contrast
+options-reduced-motion = Reduced Motion: { $enabled ->
+        [yes] On
+       *[no] Off
+    }
+options-interface-size = Interface Size: { $percent }%
+options-language = Language: { $language }
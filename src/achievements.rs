@@ -0,0 +1,231 @@
+//! Achievements are read from the file in `assets/achievements`. Each one has an
+//! [`AchievementCondition`] that is checked against what happened in the current run, which the
+//! [`AchievementTracker`] keeps count of from the events of the barters and nights.
+//! Unlocked achievements are saved to [`ACHIEVEMENTS_FILE`] and stay unlocked in every run after
+
+use crate::barter::customers::{CustomerArchetype, CustomerHandler};
+use crate::barter::{
+    Barter, BarterResolutionTypes, BarterResolved, HaggleResult, HaggleResultEvent, HaggleType,
+};
+use crate::loading::AchievementAssets;
+use crate::localization::LocalizedText;
+use crate::night::NightEnded;
+use crate::persistence::{load_ron, save_on_change, RonAsset, RonAssetLoader};
+use crate::player::Gold;
+use crate::GameState;
+use bevy::prelude::{
+    AddAsset, App, Assets, EventReader, EventWriter, Plugin, Query, Res, ResMut, Resource,
+};
+use bevy::reflect::TypeUuid;
+use bevy::utils::{HashMap, HashSet};
+use iyes_loopless::prelude::ConditionSet;
+use serde::{Deserialize, Serialize};
+
+pub struct AchievementsPlugin;
+
+impl Plugin for AchievementsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_asset::<AchievementList>()
+            .init_asset_loader::<RonAssetLoader<AchievementList>>()
+            .insert_resource(load_ron::<Achievements>(ACHIEVEMENTS_FILE).unwrap_or_default())
+            .init_resource::<AchievementTracker>()
+            .add_event::<AchievementUnlocked>()
+            .add_system(save_on_change::<Achievements>(ACHIEVEMENTS_FILE))
+            .add_system_set(
+                ConditionSet::new()
+                    .run_in_state(GameState::Playing)
+                    .with_system(track_haggles)
+                    .with_system(track_barters)
+                    .with_system(track_nights)
+                    .with_system(unlock_achievements)
+                    .into(),
+            );
+    }
+}
+
+pub const ACHIEVEMENTS_FILE: &str = "achievements.ron";
+
+/// What has to happen in a single run to unlock an achievement
+#[derive(Clone, PartialEq, Debug, Deserialize)]
+pub enum AchievementCondition {
+    /// Win this many haggles of the kind without failing any haggle in between
+    HaggleStreak {
+        haggle: HaggleType,
+        count: u32,
+    },
+    SellToArchetype(CustomerArchetype),
+    /// Have this much gold on hand when a night ends
+    GoldAtDawn(u32),
+    /// Make it through this many nights in a row without turning a customer away
+    NightsWithoutDenying(u32),
+    Sales(u32),
+}
+
+impl AchievementCondition {
+    pub fn is_met(&self, tracker: &AchievementTracker) -> bool {
+        return match self {
+            AchievementCondition::HaggleStreak { haggle, count } => {
+                tracker.best_streaks.get(haggle).copied().unwrap_or(0) >= *count
+            }
+            AchievementCondition::SellToArchetype(archetype) => tracker.sold_to.contains(archetype),
+            AchievementCondition::GoldAtDawn(gold) => tracker.best_dawn_gold >= *gold,
+            AchievementCondition::NightsWithoutDenying(nights) => {
+                tracker.nights_without_denying >= *nights
+            }
+            AchievementCondition::Sales(sales) => tracker.sales >= *sales,
+        };
+    }
+}
+
+#[derive(Clone, PartialEq, Debug, Deserialize)]
+pub struct Achievement {
+    /// Unique name of the achievement, used to remember that it was unlocked
+    pub id: String,
+    pub name: LocalizedText,
+    pub description: LocalizedText,
+    pub condition: AchievementCondition,
+    /// The gallery keeps the name and description a secret until it's unlocked
+    #[serde(default)]
+    pub hidden: bool,
+}
+
+#[derive(Clone, PartialEq, Debug, Deserialize, TypeUuid)]
+#[uuid = "d5755797-4423-4d3f-bba7-509adb5173f7"]
+pub struct AchievementList {
+    pub achievements: Vec<Achievement>,
+}
+
+impl AchievementList {
+    pub fn get(&self, id: &str) -> Option<&Achievement> {
+        self.achievements
+            .iter()
+            .find(|achievement| achievement.id == id)
+    }
+}
+
+impl RonAsset for AchievementList {
+    const EXTENSIONS: &'static [&'static str] = &["achievements.ron"];
+}
+
+/// The ids of every unlocked achievement in the order they were unlocked, saved in
+/// [`ACHIEVEMENTS_FILE`]
+#[derive(Resource, Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Achievements {
+    pub unlocked: Vec<String>,
+}
+
+impl Achievements {
+    pub fn is_unlocked(&self, id: &str) -> bool {
+        self.unlocked.iter().any(|unlocked| unlocked == id)
+    }
+}
+
+/// What happened in the current run so far, reset with the run
+#[derive(Resource, Clone, PartialEq, Debug, Default)]
+pub struct AchievementTracker {
+    /// Haggles of every kind won since the last failed haggle
+    pub streaks: HashMap<HaggleType, u32>,
+    pub best_streaks: HashMap<HaggleType, u32>,
+    pub sold_to: HashSet<CustomerArchetype>,
+    pub sales: u32,
+    pub best_dawn_gold: u32,
+    pub denied: bool,
+    pub nights_without_denying: u32,
+}
+
+/// Sent once when an achievement is unlocked for the first time
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct AchievementUnlocked {
+    pub id: String,
+}
+
+fn track_haggles(
+    mut events: EventReader<HaggleResultEvent>,
+    mut tracker: ResMut<AchievementTracker>,
+) {
+    for event in events.iter() {
+        if let HaggleResult::Failure = event.result {
+            // any failed haggle breaks every streak
+            tracker.streaks.clear();
+            continue;
+        }
+        let streak = tracker.streaks.get(&event.attempt_type).unwrap_or(&0) + 1;
+        tracker.streaks.insert(event.attempt_type.clone(), streak);
+        let best = tracker
+            .best_streaks
+            .entry(event.attempt_type.clone())
+            .or_insert(0);
+        *best = (*best).max(streak);
+    }
+}
+
+fn track_barters(
+    mut events: EventReader<BarterResolved>,
+    mut tracker: ResMut<AchievementTracker>,
+    barter: Option<Res<Barter>>,
+    customer_handler: Res<CustomerHandler>,
+    archetypes: Query<&CustomerArchetype>,
+) {
+    for event in events.iter() {
+        match event.resolution_type {
+            BarterResolutionTypes::Approve { .. } => {
+                tracker.sales += 1;
+                // the customer only leaves the line once the barter screen is closed
+                let archetype = customer_handler
+                    .get_next_customer()
+                    .and_then(|customer| archetypes.get(customer).ok());
+                if let Some(archetype) = archetype {
+                    tracker.sold_to.insert(*archetype);
+                }
+            }
+            BarterResolutionTypes::Deny => {
+                // a customer that ran out of patience walked away on their own
+                let walked_away = matches!(&barter, Some(barter) if barter.is_out_of_patience());
+                if !walked_away {
+                    tracker.denied = true;
+                    tracker.nights_without_denying = 0;
+                }
+            }
+        }
+    }
+}
+
+fn track_nights(
+    mut events: EventReader<NightEnded>,
+    mut tracker: ResMut<AchievementTracker>,
+    gold: Res<Gold>,
+) {
+    for _ in events.iter() {
+        tracker.best_dawn_gold = tracker.best_dawn_gold.max(gold.amount);
+        if !tracker.denied {
+            tracker.nights_without_denying += 1;
+        }
+        tracker.denied = false;
+    }
+}
+
+fn unlock_achievements(
+    tracker: Res<AchievementTracker>,
+    mut achievements: ResMut<Achievements>,
+    achievement_assets: Res<AchievementAssets>,
+    lists: Res<Assets<AchievementList>>,
+    mut unlocked: EventWriter<AchievementUnlocked>,
+) {
+    if !tracker.is_changed() {
+        return;
+    }
+    let list = match lists.get(&achievement_assets.achievements) {
+        Some(list) => list,
+        None => return,
+    };
+    for achievement in list.achievements.iter() {
+        if achievements.is_unlocked(&achievement.id) || !achievement.condition.is_met(&tracker) {
+            continue;
+        }
+        achievements.unlocked.push(achievement.id.clone());
+        unlocked.send(AchievementUnlocked {
+            id: achievement.id.clone(),
+        });
+    }
+}
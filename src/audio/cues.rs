@@ -0,0 +1,314 @@
+//! Sound effects are played through sound cues instead of calling the audio channels directly.
+//! Systems in here listen to things happening in the game and send a [`PlaySoundEvent`]. Which
+//! sounds actually play for an event is decided by the cue table in `assets/audio/sfx.cues.ron`.
+//! A cue can pick from several variations, jitter their pitch and volume, and has a cooldown so
+//! the same cue doesn't stack when its event fires many times in a row.
+//! Events without an entry in the table are silent
+
+use crate::audio::{SfxChannel, UiChannel};
+use crate::barter::customers::{Customer, CustomerState};
+use crate::barter::{
+    BarterResolutionTypes, BarterResolved, BeginBarter, HaggleResult, HaggleResultEvent,
+};
+use crate::loading::AudioAssets;
+use crate::persistence::{RonAsset, RonAssetLoader};
+use crate::player::Gold;
+use crate::settings::{Settings, VolumeSetting};
+use crate::{GameState, PausedState};
+use bevy::asset::{AssetPath, LoadContext};
+use bevy::prelude::{
+    AddAsset, Added, App, Assets, Button, Changed, EventReader, EventWriter, Handle, Interaction,
+    Local, Plugin, Query, RemovedComponents, Res, Time, Timer, TimerMode, With,
+};
+use bevy::reflect::TypeUuid;
+use bevy::utils::HashMap;
+use bevy_kira_audio::{AudioChannel, AudioControl, AudioSource};
+use iyes_loopless::prelude::ConditionSet;
+use rand::seq::SliceRandom;
+use rand::{thread_rng, Rng};
+use serde::Deserialize;
+
+pub struct SoundCuePlugin;
+
+impl Plugin for SoundCuePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_asset::<SoundCueTable>()
+            .init_asset_loader::<RonAssetLoader<SoundCueTable>>()
+            .add_event::<PlaySoundEvent>()
+            .add_system_set(
+                ConditionSet::new()
+                    .run_if_resource_exists::<AudioAssets>()
+                    .with_system(route_button_clicks)
+                    .with_system(play_sound_cues)
+                    .into(),
+            )
+            .add_system_set(
+                ConditionSet::new()
+                    .run_in_state(GameState::Playing)
+                    .with_system(route_haggle_results)
+                    .with_system(route_barter_events)
+                    .with_system(route_customer_events)
+                    .with_system(route_gold_changes)
+                    .into(),
+            )
+            .add_system_set(
+                ConditionSet::new()
+                    .run_in_state(GameState::Playing)
+                    .run_in_state(PausedState::Playing)
+                    .with_system(route_footsteps)
+                    .into(),
+            );
+    }
+}
+
+/// How often a footstep plays while customers are walking around
+const FOOTSTEP_INTERVAL: f32 = 0.4;
+
+/// The game events that can have a sound cue attached to them in the cue table
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Deserialize)]
+pub enum SoundEvent {
+    ButtonClicked,
+    BarterStarted,
+    HaggleSuccess,
+    HaggleFailure,
+    BarterApproved,
+    BarterDenied,
+    CustomerArrived,
+    CustomerLeft,
+    CustomerFootstep,
+    GoldGained,
+    GoldLost,
+}
+
+/// Request to play the cue attached to the event
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct PlaySoundEvent(pub SoundEvent);
+
+/// The channel a cue plays on, which decides which volume setting applies to it
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, Deserialize)]
+pub enum CueChannel {
+    Ui,
+    #[default]
+    Sfx,
+}
+
+impl CueChannel {
+    pub fn get_volume_setting(&self) -> VolumeSetting {
+        return match self {
+            CueChannel::Ui => VolumeSetting::Ui,
+            CueChannel::Sfx => VolumeSetting::Sfx,
+        };
+    }
+}
+
+#[derive(Clone, PartialEq, Debug, Deserialize)]
+#[serde(default)]
+pub struct SoundCue {
+    /// Paths of the variations of the cue, relative to the assets folder. One is picked at random
+    pub sounds: Vec<String>,
+    pub channel: CueChannel,
+    pub volume: f64,
+    /// How far the volume can randomly stray from `volume`
+    pub volume_jitter: f64,
+    /// How far the playback rate can randomly stray from 1.0, changing the pitch of the sound
+    pub pitch_jitter: f64,
+    /// Seconds before the cue can play again. Events within the cooldown are dropped
+    pub cooldown: f64,
+    #[serde(skip)]
+    handles: Vec<Handle<AudioSource>>,
+}
+
+impl Default for SoundCue {
+    fn default() -> Self {
+        SoundCue {
+            sounds: vec![],
+            channel: CueChannel::default(),
+            volume: 1.0,
+            volume_jitter: 0.0,
+            pitch_jitter: 0.0,
+            cooldown: 0.0,
+            handles: vec![],
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug, Default, Deserialize, TypeUuid)]
+#[uuid = "6f5c1e8a-3b1d-4a3e-9d8c-2f4b7e9a1c55"]
+pub struct SoundCueTable {
+    pub cues: HashMap<SoundEvent, SoundCue>,
+}
+
+/// Cue tables are `.cues.ron` files. The sounds of every cue are loaded along with the table
+impl RonAsset for SoundCueTable {
+    const EXTENSIONS: &'static [&'static str] = &["cues.ron"];
+
+    fn load_dependencies(&mut self, load_context: &mut LoadContext) -> Vec<AssetPath<'static>> {
+        let mut dependencies = vec![];
+        for cue in self.cues.values_mut() {
+            for path in cue.sounds.iter() {
+                let path = AssetPath::from(path.as_str());
+                cue.handles.push(load_context.get_handle(path.clone()));
+                dependencies.push(path.to_owned());
+            }
+        }
+        dependencies
+    }
+}
+
+fn play_cue(
+    channel: &impl AudioControl,
+    sound: Handle<AudioSource>,
+    volume: f64,
+    playback_rate: f64,
+) {
+    channel
+        .play(sound)
+        .with_volume(volume)
+        .with_playback_rate(playback_rate);
+}
+
+#[allow(clippy::too_many_arguments)]
+fn play_sound_cues(
+    mut events: EventReader<PlaySoundEvent>,
+    mut last_played: Local<HashMap<SoundEvent, f64>>,
+    audio_assets: Res<AudioAssets>,
+    cue_tables: Res<Assets<SoundCueTable>>,
+    settings: Res<Settings>,
+    time: Res<Time>,
+    ui: Res<AudioChannel<UiChannel>>,
+    sfx: Res<AudioChannel<SfxChannel>>,
+) {
+    let table = match cue_tables.get(&audio_assets.sound_cues) {
+        Some(table) => table,
+        None => return,
+    };
+    let now = time.elapsed_seconds_f64();
+    let mut rng = thread_rng();
+
+    for PlaySoundEvent(event) in events.iter() {
+        let cue = match table.cues.get(event) {
+            Some(cue) => cue,
+            None => continue,
+        };
+        if let Some(last) = last_played.get(event) {
+            if now - last < cue.cooldown {
+                continue;
+            }
+        }
+        let sound = match cue.handles.choose(&mut rng) {
+            Some(sound) => sound.clone(),
+            None => continue,
+        };
+        last_played.insert(*event, now);
+
+        let volume = (cue.volume + rng.gen_range(-1.0..=1.0) * cue.volume_jitter).max(0.0)
+            * settings.get_channel_volume(cue.channel.get_volume_setting());
+        let playback_rate = 1.0 + rng.gen_range(-1.0..=1.0) * cue.pitch_jitter;
+        match cue.channel {
+            CueChannel::Ui => play_cue(&*ui, sound, volume, playback_rate),
+            CueChannel::Sfx => play_cue(&*sfx, sound, volume, playback_rate),
+        }
+    }
+}
+
+fn route_button_clicks(
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<Button>)>,
+    mut sounds: EventWriter<PlaySoundEvent>,
+) {
+    for interaction in interaction_query.iter() {
+        if let Interaction::Clicked = interaction {
+            sounds.send(PlaySoundEvent(SoundEvent::ButtonClicked));
+        }
+    }
+}
+
+fn route_haggle_results(
+    mut results: EventReader<HaggleResultEvent>,
+    mut sounds: EventWriter<PlaySoundEvent>,
+) {
+    for result in results.iter() {
+        sounds.send(PlaySoundEvent(match result.result {
+            HaggleResult::Success => SoundEvent::HaggleSuccess,
+            HaggleResult::Failure => SoundEvent::HaggleFailure,
+        }));
+    }
+}
+
+fn route_barter_events(
+    mut begin: EventReader<BeginBarter>,
+    mut resolved: EventReader<BarterResolved>,
+    mut sounds: EventWriter<PlaySoundEvent>,
+) {
+    for _ in begin.iter() {
+        sounds.send(PlaySoundEvent(SoundEvent::BarterStarted));
+    }
+    for event in resolved.iter() {
+        sounds.send(PlaySoundEvent(match event.resolution_type {
+            BarterResolutionTypes::Approve { .. } => SoundEvent::BarterApproved,
+            BarterResolutionTypes::Deny => SoundEvent::BarterDenied,
+        }));
+    }
+}
+
+fn route_customer_events(
+    arrived: Query<(), Added<Customer>>,
+    left: RemovedComponents<Customer>,
+    mut sounds: EventWriter<PlaySoundEvent>,
+) {
+    for _ in arrived.iter() {
+        sounds.send(PlaySoundEvent(SoundEvent::CustomerArrived));
+    }
+    for _ in left.iter() {
+        sounds.send(PlaySoundEvent(SoundEvent::CustomerLeft));
+    }
+}
+
+fn route_gold_changes(
+    gold: Res<Gold>,
+    mut last_amount: Local<Option<u32>>,
+    mut sounds: EventWriter<PlaySoundEvent>,
+) {
+    if !gold.is_changed() {
+        return;
+    }
+    if let Some(last_amount) = *last_amount {
+        if gold.amount > last_amount {
+            sounds.send(PlaySoundEvent(SoundEvent::GoldGained));
+        } else if gold.amount < last_amount {
+            sounds.send(PlaySoundEvent(SoundEvent::GoldLost));
+        }
+    }
+    *last_amount = Some(gold.amount);
+}
+
+pub struct FootstepTimer {
+    timer: Timer,
+}
+
+impl Default for FootstepTimer {
+    fn default() -> Self {
+        Self {
+            timer: Timer::from_seconds(FOOTSTEP_INTERVAL, TimerMode::Repeating),
+        }
+    }
+}
+
+fn route_footsteps(
+    customers: Query<&CustomerState, With<Customer>>,
+    mut footstep_timer: Local<FootstepTimer>,
+    time: Res<Time>,
+    mut sounds: EventWriter<PlaySoundEvent>,
+) {
+    let anyone_walking = customers
+        .iter()
+        .any(|state| matches!(state, CustomerState::MoveLeft | CustomerState::MoveRight));
+    if !anyone_walking {
+        footstep_timer.timer.reset();
+        return;
+    }
+
+    footstep_timer.timer.tick(time.delta());
+    if footstep_timer.timer.just_finished() {
+        sounds.send(PlaySoundEvent(SoundEvent::CustomerFootstep));
+    }
+}
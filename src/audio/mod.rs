@@ -0,0 +1,63 @@
+use crate::audio::cues::SoundCuePlugin;
+use crate::audio::music::MusicPlugin;
+use crate::loading::AudioAssets;
+use crate::settings::{Settings, VolumeSetting};
+use crate::GameState;
+use bevy::prelude::{App, Plugin, Res, Resource};
+use bevy_kira_audio::{AudioApp, AudioChannel, AudioControl, AudioPlugin};
+use iyes_loopless::prelude::AppLooplessStateExt;
+
+pub mod cues;
+mod music;
+
+pub struct InternalAudioPlugin;
+
+// This plugin is responsible to control the game audio
+// Every kind of sound plays on its own channel so that they can be mixed separately in the options
+// Sound effects are played through the cue table of the SoundCuePlugin
+impl Plugin for InternalAudioPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugin(AudioPlugin)
+            .add_audio_channel::<MusicChannel>()
+            .add_audio_channel::<AmbienceChannel>()
+            .add_audio_channel::<UiChannel>()
+            .add_audio_channel::<SfxChannel>()
+            .add_plugin(MusicPlugin)
+            .add_enter_system(GameState::Playing, start_audio)
+            .add_exit_system(GameState::Playing, stop_audio)
+            .add_plugin(SoundCuePlugin)
+            .add_system(apply_volume_settings);
+    }
+}
+
+/// Background music. Its volume is handled per layer by the `MusicPlugin`
+#[derive(Resource)]
+pub struct MusicChannel;
+
+/// Looping background noises of the shop and the night outside
+#[derive(Resource)]
+pub struct AmbienceChannel;
+
+/// Feedback for interacting with the interface. Its volume is applied per sound cue
+#[derive(Resource)]
+pub struct UiChannel;
+
+/// Sound effects from things happening in the game. Its volume is applied per sound cue
+#[derive(Resource)]
+pub struct SfxChannel;
+
+fn start_audio(audio_assets: Res<AudioAssets>, ambience: Res<AudioChannel<AmbienceChannel>>) {
+    ambience.play(audio_assets.night_ambience.clone()).looped();
+}
+
+fn stop_audio(ambience: Res<AudioChannel<AmbienceChannel>>) {
+    ambience.stop();
+}
+
+/// Sound cues pick their volume when they are played, so only the ambience has to follow the settings
+fn apply_volume_settings(settings: Res<Settings>, ambience: Res<AudioChannel<AmbienceChannel>>) {
+    if !settings.is_changed() {
+        return;
+    }
+    ambience.set_volume(settings.get_channel_volume(VolumeSetting::Ambience));
+}
@@ -0,0 +1,164 @@
+//! The music is made out of two layers that always play in sync, the calm shop music and a tense
+//! negotiation track. Outside of barters only the calm layer can be heard. When a barter starts
+//! the music crossfades to the tense layer, which swells the longer the haggling drags on.
+//! Haggle results are punctuated with a short stinger on top of the layers
+
+use crate::audio::MusicChannel;
+use crate::barter::{Barter, BarterState, HaggleResult, HaggleResultEvent};
+use crate::loading::AudioAssets;
+use crate::settings::{Settings, VolumeSetting};
+use crate::GameState;
+use bevy::prelude::{App, Assets, Commands, EventReader, Handle, Plugin, Res, ResMut, Resource};
+use bevy_kira_audio::{AudioChannel, AudioControl, AudioInstance, AudioTween};
+use iyes_loopless::prelude::{AppLooplessStateExt, ConditionSet, CurrentState};
+use std::time::Duration;
+
+pub struct MusicPlugin;
+
+impl Plugin for MusicPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_enter_system(GameState::Playing, start_music)
+            .add_exit_system(GameState::Playing, stop_music)
+            .add_system_set(
+                ConditionSet::new()
+                    .run_in_state(GameState::Playing)
+                    .run_if_resource_exists::<MusicLayers>()
+                    .with_system(update_music_layers)
+                    .with_system(play_stingers)
+                    .into(),
+            );
+    }
+}
+
+/// How long it takes to fade between the calm and the tense layer when a barter starts or ends
+const CROSSFADE_DURATION: f32 = 1.5;
+/// How long it takes the tense layer to react to another haggle
+const SWELL_DURATION: f32 = 0.5;
+/// How loud the tense layer is when a barter starts. It rises to full volume with every haggle
+const TENSE_BASE_MIX: f64 = 0.6;
+/// After this many haggles the tense layer is at full volume
+const HAGGLES_TO_FULL_TENSION: usize = 5;
+
+/// The instances of the music layers and the volume they were last faded to
+#[derive(Resource)]
+pub struct MusicLayers {
+    calm: Handle<AudioInstance>,
+    tense: Handle<AudioInstance>,
+    calm_volume: f64,
+    tense_volume: f64,
+}
+
+fn start_music(
+    mut commands: Commands,
+    audio_assets: Res<AudioAssets>,
+    music: Res<AudioChannel<MusicChannel>>,
+    settings: Res<Settings>,
+) {
+    let volume = settings.get_channel_volume(VolumeSetting::Music);
+    // both layers start together so they stay in sync, the tense one is just silent for now
+    let calm = music
+        .play(audio_assets.shop_music.clone())
+        .looped()
+        .with_volume(volume)
+        .handle();
+    let tense = music
+        .play(audio_assets.barter_music.clone())
+        .looped()
+        .with_volume(0.0)
+        .handle();
+
+    commands.insert_resource(MusicLayers {
+        calm,
+        tense,
+        calm_volume: volume,
+        tense_volume: 0.0,
+    });
+}
+
+fn stop_music(mut commands: Commands, music: Res<AudioChannel<MusicChannel>>) {
+    music.stop();
+    commands.remove_resource::<MusicLayers>();
+}
+
+/// How much of each layer should be heard, returned as (calm, tense)
+fn get_layer_mix(barter_state: &BarterState, barter: Option<&Barter>) -> (f64, f64) {
+    match barter_state {
+        BarterState::Bartering => {
+            let haggles = barter
+                .map(|barter| barter.get_haggles().len())
+                .unwrap_or(0)
+                .min(HAGGLES_TO_FULL_TENSION);
+            let tension = haggles as f64 / HAGGLES_TO_FULL_TENSION as f64;
+            (0.0, TENSE_BASE_MIX + (1.0 - TENSE_BASE_MIX) * tension)
+        }
+        BarterState::NotBartering => (1.0, 0.0),
+    }
+}
+
+fn fade_layer(
+    instances: &mut Assets<AudioInstance>,
+    layer: &Handle<AudioInstance>,
+    current_volume: &mut f64,
+    volume: f64,
+    duration: f32,
+) {
+    if (*current_volume - volume).abs() < f64::EPSILON {
+        return;
+    }
+    if let Some(instance) = instances.get_mut(layer) {
+        instance.set_volume(
+            volume,
+            AudioTween::linear(Duration::from_secs_f32(duration)),
+        );
+    }
+    *current_volume = volume;
+}
+
+fn update_music_layers(
+    mut layers: ResMut<MusicLayers>,
+    mut instances: ResMut<Assets<AudioInstance>>,
+    settings: Res<Settings>,
+    barter_state: Res<CurrentState<BarterState>>,
+    barter: Option<Res<Barter>>,
+) {
+    let volume = settings.get_channel_volume(VolumeSetting::Music);
+    let (calm_mix, tense_mix) = get_layer_mix(&barter_state.0, barter.as_deref());
+    let duration = if barter_state.is_changed() {
+        CROSSFADE_DURATION
+    } else {
+        SWELL_DURATION
+    };
+
+    let layers = &mut *layers;
+    fade_layer(
+        &mut instances,
+        &layers.calm,
+        &mut layers.calm_volume,
+        volume * calm_mix,
+        duration,
+    );
+    fade_layer(
+        &mut instances,
+        &layers.tense,
+        &mut layers.tense_volume,
+        volume * tense_mix,
+        duration,
+    );
+}
+
+fn play_stingers(
+    mut results: EventReader<HaggleResultEvent>,
+    audio_assets: Res<AudioAssets>,
+    music: Res<AudioChannel<MusicChannel>>,
+    settings: Res<Settings>,
+) {
+    for result in results.iter() {
+        let stinger = match result.result {
+            HaggleResult::Success => audio_assets.stinger_success.clone(),
+            HaggleResult::Failure => audio_assets.stinger_failure.clone(),
+        };
+        music
+            .play(stinger)
+            .with_volume(settings.get_channel_volume(VolumeSetting::Music));
+    }
+}
@@ -0,0 +1,125 @@
+use crate::bank::{Bank, BankTransactionKind};
+use crate::barter::customers::{CustomerBundle, CustomerSettings, CustomerState, Name};
+use crate::ledger::{ExpenseKind, ExpensePaid};
+use crate::loading::CustomerAsepriteHandles;
+use crate::localization::Localization;
+use crate::night::Night;
+use crate::player::{Gold, Reputation};
+use crate::rng::RunRng;
+use crate::{GameState, PausedState};
+use bevy::app::App;
+use bevy::prelude::{
+    Assets, Color, Commands, Component, Entity, EventReader, EventWriter, Plugin, Query, Res,
+    ResMut,
+};
+use bevy_mod_aseprite::Aseprite;
+use iyes_loopless::prelude::ConditionSet;
+
+pub struct CollectorPlugin;
+
+/// Debt collectors are special customers sent by the bank when the player keeps missing loan
+/// payments. They don't queue up to barter, they drop in, take what they are owed and leave
+impl Plugin for CollectorPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<DebtCollectorDispatched>();
+
+        app.add_system_set(
+            ConditionSet::new()
+                .run_in_state(GameState::Playing)
+                .run_on_event::<DebtCollectorDispatched>()
+                .with_system(spawn_debt_collectors)
+                .into(),
+        )
+        .add_system_set(
+            ConditionSet::new()
+                .run_in_state(GameState::Playing)
+                .run_in_state(PausedState::Playing)
+                .with_system(collect_debts)
+                .into(),
+        );
+    }
+}
+
+/// Reputation lost when a collector leaves without getting everything they came for
+pub const UNPAID_COLLECTION_REPUTATION_PENALTY: i32 = 10;
+
+/// Sent by the bank when collectors should come for the overdue gold
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct DebtCollectorDispatched {
+    pub amount: u32,
+}
+
+#[derive(Component, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct DebtCollector {
+    pub amount: u32,
+}
+
+#[allow(clippy::too_many_arguments)]
+fn spawn_debt_collectors(
+    mut commands: Commands,
+    mut events: EventReader<DebtCollectorDispatched>,
+    mut customer_aseprite_handles: ResMut<CustomerAsepriteHandles>,
+    aseprites: Res<Assets<Aseprite>>,
+    customer_settings: Res<CustomerSettings>,
+    mut rng: ResMut<RunRng>,
+    collectors: Query<&DebtCollector>,
+    localization: Res<Localization>,
+) {
+    for event in events.iter() {
+        // only one collector is sent at a time
+        if event.amount == 0 || !collectors.is_empty() {
+            continue;
+        }
+
+        let mut bundle = CustomerBundle::new_random(
+            &mut customer_aseprite_handles,
+            &aseprites,
+            &customer_settings,
+            &mut rng.visitors,
+        );
+        bundle.name = Name {
+            name: localization.get("bank-debt-collector"),
+        };
+        bundle.aseprite_bundle.sprite.color = Color::rgb(1.0, 0.5, 0.5);
+
+        commands.spawn(bundle).insert(DebtCollector {
+            amount: event.amount,
+        });
+    }
+}
+
+/// Once a collector has landed in the shop they take whatever gold they can and leave
+fn collect_debts(
+    mut commands: Commands,
+    mut collectors: Query<(Entity, &DebtCollector, &mut CustomerState)>,
+    mut bank: ResMut<Bank>,
+    mut gold: ResMut<Gold>,
+    mut reputation: ResMut<Reputation>,
+    night: Res<Night>,
+    mut expenses: EventWriter<ExpensePaid>,
+) {
+    for (entity, collector, mut state) in collectors.iter_mut() {
+        match *state {
+            CustomerState::MoveLeft | CustomerState::MoveRight => {}
+            _ => continue,
+        }
+
+        let seized = collector.amount.min(gold.amount);
+        gold.amount -= seized;
+        bank.pay_overdue(seized);
+        if seized > 0 {
+            bank.log_transaction(night.number, BankTransactionKind::Collection, seized);
+            expenses.send(ExpensePaid {
+                kind: ExpenseKind::DebtCollection,
+                amount: seized,
+                night: night.number,
+            });
+        }
+        if seized < collector.amount {
+            reputation.change(-UNPAID_COLLECTION_REPUTATION_PENALTY);
+        }
+
+        *state = CustomerState::Despawning;
+        commands.entity(entity).remove::<DebtCollector>();
+    }
+}
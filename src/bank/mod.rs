@@ -0,0 +1,324 @@
+//! The bank lets the player borrow gold against future nights and earn interest on gold they
+//! don't need right now. Loans are repaid in nightly installments, if the player can't cover an
+//! installment the shops reputation suffers and eventually debt collectors come to visit
+
+use crate::bank::collectors::{CollectorPlugin, DebtCollectorDispatched};
+use crate::difficulty::Difficulty;
+use crate::ledger::{ExpenseKind, ExpensePaid};
+use crate::night::{Night, NightEnded};
+use crate::player::{Gold, Reputation};
+use bevy::prelude::{App, EventReader, EventWriter, Plugin, Res, ResMut, Resource};
+use iyes_loopless::prelude::ConditionSet;
+
+pub mod collectors;
+
+pub struct BankPlugin;
+
+impl Plugin for BankPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Bank>().add_event::<BankAction>();
+
+        app.add_plugin(CollectorPlugin);
+
+        app.add_system_set(
+            ConditionSet::new()
+                .run_on_event::<BankAction>()
+                .with_system(handle_bank_actions)
+                .into(),
+        )
+        .add_system_set(
+            ConditionSet::new()
+                .run_on_event::<NightEnded>()
+                .with_system(process_nightly_banking)
+                .into(),
+        );
+    }
+}
+
+/// Interest paid on deposits at the end of every night
+pub const DEPOSIT_INTEREST_RATE: f32 = 0.02;
+/// How much gold is moved with a single deposit or withdrawal
+pub const DEPOSIT_STEP: u32 = 50;
+pub const MAX_ACTIVE_LOANS: usize = 3;
+pub const MISSED_PAYMENT_REPUTATION_PENALTY: i32 = 5;
+/// How many payments can be missed on a loan before the bank sends collectors
+pub const MISSED_PAYMENTS_BEFORE_COLLECTORS: u32 = 2;
+/// How many transactions the bank remembers
+pub const MAX_HISTORY: usize = 100;
+
+/// The loans the bank is willing to give out
+pub const LOAN_OFFERS: [LoanOffer; 3] = [
+    LoanOffer {
+        principal: 100,
+        interest_rate: 0.10,
+        nights: 3,
+    },
+    LoanOffer {
+        principal: 250,
+        interest_rate: 0.15,
+        nights: 5,
+    },
+    LoanOffer {
+        principal: 500,
+        interest_rate: 0.20,
+        nights: 7,
+    },
+];
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct LoanOffer {
+    pub principal: u32,
+    pub interest_rate: f32,
+    pub nights: u32,
+}
+
+impl LoanOffer {
+    pub fn total_owed(&self) -> u32 {
+        (self.principal as f32 * (1.0 + self.interest_rate)).round() as u32
+    }
+
+    pub fn installment(&self) -> u32 {
+        (self.total_owed() as f32 / self.nights as f32).ceil() as u32
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Loan {
+    pub principal: u32,
+    /// Gold still owed on the loan, including overdue gold
+    pub remaining: u32,
+    pub installment: u32,
+    /// Gold from missed installments that is now overdue
+    pub overdue: u32,
+    pub missed_payments: u32,
+}
+
+impl Loan {
+    pub fn from_offer(offer: &LoanOffer) -> Loan {
+        Loan {
+            principal: offer.principal,
+            remaining: offer.total_owed(),
+            installment: offer.installment(),
+            overdue: 0,
+            missed_payments: 0,
+        }
+    }
+
+    pub fn is_paid_off(&self) -> bool {
+        self.remaining == 0
+    }
+
+    /// Reduces the debt by the given amount, paying off overdue gold first
+    pub fn pay(&mut self, amount: u32) {
+        self.remaining = self.remaining.saturating_sub(amount);
+        self.overdue = self.overdue.saturating_sub(amount).min(self.remaining);
+        if self.overdue == 0 {
+            self.missed_payments = 0;
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum BankTransactionKind {
+    LoanTaken,
+    LoanPayment,
+    MissedPayment,
+    Deposit,
+    Withdrawal,
+    Interest,
+    Collection,
+}
+
+impl BankTransactionKind {
+    /// Id of the name of the transaction in the translations
+    pub fn get_locale_key(&self) -> &'static str {
+        return match self {
+            BankTransactionKind::LoanTaken => "bank-transaction-loan-taken",
+            BankTransactionKind::LoanPayment => "bank-transaction-loan-payment",
+            BankTransactionKind::MissedPayment => "bank-transaction-missed-payment",
+            BankTransactionKind::Deposit => "bank-transaction-deposit",
+            BankTransactionKind::Withdrawal => "bank-transaction-withdrawal",
+            BankTransactionKind::Interest => "bank-transaction-interest",
+            BankTransactionKind::Collection => "bank-transaction-collection",
+        };
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct BankTransaction {
+    pub night: u32,
+    pub kind: BankTransactionKind,
+    pub amount: u32,
+}
+
+#[derive(Clone, PartialEq, Debug, Default, Resource)]
+pub struct Bank {
+    pub deposit: u32,
+    pub loans: Vec<Loan>,
+    pub history: Vec<BankTransaction>,
+}
+
+impl Bank {
+    pub fn log_transaction(&mut self, night: u32, kind: BankTransactionKind, amount: u32) {
+        self.history.push(BankTransaction {
+            night,
+            kind,
+            amount,
+        });
+        if self.history.len() > MAX_HISTORY {
+            self.history.remove(0);
+        }
+    }
+
+    /// Total gold owed across every loan
+    pub fn total_owed(&self) -> u32 {
+        self.loans.iter().map(|loan| loan.remaining).sum()
+    }
+
+    /// Total gold owed from missed installments across every loan
+    pub fn total_overdue(&self) -> u32 {
+        self.loans.iter().map(|loan| loan.overdue).sum()
+    }
+
+    pub fn can_take_loan(&self) -> bool {
+        self.loans.len() < MAX_ACTIVE_LOANS
+    }
+
+    /// Pays the given amount towards overdue debt, oldest loans first
+    pub fn pay_overdue(&mut self, mut amount: u32) {
+        for loan in self.loans.iter_mut() {
+            let paid = amount.min(loan.overdue);
+            loan.pay(paid);
+            amount -= paid;
+        }
+        self.loans.retain(|loan| !loan.is_paid_off());
+    }
+}
+
+/// Requests made by the player at the bank
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum BankAction {
+    /// Takes the loan at the given index of [`LOAN_OFFERS`]
+    TakeLoan(usize),
+    Deposit(u32),
+    Withdraw(u32),
+    /// Pays as much of the overdue gold as the player has on them
+    PayOverdue,
+}
+
+fn handle_bank_actions(
+    mut events: EventReader<BankAction>,
+    mut bank: ResMut<Bank>,
+    mut gold: ResMut<Gold>,
+    night: Res<Night>,
+    difficulty: Res<Difficulty>,
+    mut expenses: EventWriter<ExpensePaid>,
+) {
+    for event in events.iter() {
+        match *event {
+            BankAction::TakeLoan(index) => {
+                let offer = match LOAN_OFFERS.get(index) {
+                    Some(offer) => difficulty.scale_loan(offer),
+                    None => continue,
+                };
+                if !bank.can_take_loan() {
+                    continue;
+                }
+                bank.loans.push(Loan::from_offer(&offer));
+                gold.amount = gold.amount.saturating_add(offer.principal);
+                bank.log_transaction(night.number, BankTransactionKind::LoanTaken, offer.principal);
+            }
+            BankAction::Deposit(amount) => {
+                let amount = amount.min(gold.amount);
+                if amount == 0 {
+                    continue;
+                }
+                gold.amount -= amount;
+                bank.deposit += amount;
+                bank.log_transaction(night.number, BankTransactionKind::Deposit, amount);
+            }
+            BankAction::Withdraw(amount) => {
+                let amount = amount.min(bank.deposit);
+                if amount == 0 {
+                    continue;
+                }
+                bank.deposit -= amount;
+                gold.amount = gold.amount.saturating_add(amount);
+                bank.log_transaction(night.number, BankTransactionKind::Withdrawal, amount);
+            }
+            BankAction::PayOverdue => {
+                let amount = bank.total_overdue().min(gold.amount);
+                if amount == 0 {
+                    continue;
+                }
+                gold.amount -= amount;
+                bank.pay_overdue(amount);
+                bank.log_transaction(night.number, BankTransactionKind::LoanPayment, amount);
+                expenses.send(ExpensePaid {
+                    kind: ExpenseKind::LoanPayment,
+                    amount,
+                    night: night.number,
+                });
+            }
+        }
+    }
+}
+
+fn process_nightly_banking(
+    mut events: EventReader<NightEnded>,
+    mut bank: ResMut<Bank>,
+    mut gold: ResMut<Gold>,
+    mut reputation: ResMut<Reputation>,
+    mut collectors: EventWriter<DebtCollectorDispatched>,
+    mut expenses: EventWriter<ExpensePaid>,
+) {
+    for event in events.iter() {
+        let interest = (bank.deposit as f32 * DEPOSIT_INTEREST_RATE).floor() as u32;
+        if interest > 0 {
+            bank.deposit += interest;
+            bank.log_transaction(event.night, BankTransactionKind::Interest, interest);
+        }
+
+        let mut payments = vec![];
+        let mut missed = vec![];
+        for loan in bank.loans.iter_mut() {
+            let due = loan.installment.min(loan.remaining - loan.overdue);
+            if due == 0 {
+                continue;
+            }
+            if gold.amount >= due {
+                gold.amount -= due;
+                loan.pay(due);
+                payments.push(due);
+            } else {
+                loan.overdue += due;
+                loan.missed_payments += 1;
+                missed.push(due);
+            }
+        }
+
+        for payment in payments {
+            bank.log_transaction(event.night, BankTransactionKind::LoanPayment, payment);
+            expenses.send(ExpensePaid {
+                kind: ExpenseKind::LoanPayment,
+                amount: payment,
+                night: event.night,
+            });
+        }
+        for missed_payment in missed {
+            reputation.change(-MISSED_PAYMENT_REPUTATION_PENALTY);
+            bank.log_transaction(event.night, BankTransactionKind::MissedPayment, missed_payment);
+        }
+        bank.loans.retain(|loan| !loan.is_paid_off());
+
+        if bank
+            .loans
+            .iter()
+            .any(|loan| loan.missed_payments >= MISSED_PAYMENTS_BEFORE_COLLECTORS)
+        {
+            collectors.send(DebtCollectorDispatched {
+                amount: bank.total_overdue(),
+            });
+        }
+    }
+}
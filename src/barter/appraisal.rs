@@ -0,0 +1,147 @@
+//! Appraising a customer gives a rough idea of how much they resist each kind of haggle and how
+//! much gold they have on them. Every estimate is a range that the real value is somewhere in, the
+//! appraiser skill and every level of display cases make the ranges narrower. Appraising the same
+//! customer again narrows the ranges down further, but every look costs some of their patience
+
+use crate::barter::customers::{CustomerDifficulty, CustomerHandler, MaxPurchaseAmount};
+use crate::barter::{Barter, CustomerOutOfPatience, HaggleType, DEFAULT_RESISTANCE};
+use crate::rng::RunRng;
+use crate::skills::{ActiveSkills, Skill};
+use crate::ui::UiState;
+use crate::upgrades::ShopUpgrades;
+use crate::{GameState, PausedState};
+use bevy::prelude::{App, EventReader, EventWriter, Plugin, Query, Res, ResMut};
+use iyes_loopless::prelude::ConditionSet;
+use rand::Rng;
+use std::ops::RangeInclusive;
+
+pub struct AppraisalPlugin;
+
+impl Plugin for AppraisalPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<AppraiseEvent>().add_system_set(
+            ConditionSet::new()
+                .run_in_state(GameState::Playing)
+                .run_in_state(UiState::Barter)
+                .run_in_state(PausedState::Playing)
+                .run_on_event::<AppraiseEvent>()
+                .with_system(handle_appraise_events)
+                .into(),
+        );
+    }
+}
+
+/// How much patience looking the customer over costs
+pub const APPRAISAL_PATIENCE_COST: u32 = 2;
+/// How wide the estimate of a resistance is, out of 100
+const RESISTANCE_SPREAD: u32 = 40;
+/// How wide the estimate of the budget is, in percent of the budget
+const BUDGET_SPREAD: u32 = 60;
+/// The appraiser skill divides the width of every estimate by this
+const APPRAISER_PRECISION: u32 = 2;
+
+/// The haggles the customer has a resistance of their own to, charm plays on the lowest of them
+pub const APPRAISED_HAGGLES: [HaggleType; 3] =
+    [HaggleType::Bully, HaggleType::Plea, HaggleType::Persuade];
+
+/// The player wants to look the customer over
+pub struct AppraiseEvent;
+
+/// What the player could tell about the customer
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Appraisal {
+    /// The range of the resistance to each of the [`APPRAISED_HAGGLES`], in the same order
+    pub resistances: Vec<RangeInclusive<i32>>,
+    pub budget: RangeInclusive<u32>,
+}
+
+impl Appraisal {
+    /// Keeps only what both appraisals agree on. Both contain the real values, so the ranges
+    /// always overlap
+    pub fn narrow(&self, other: &Appraisal) -> Appraisal {
+        Appraisal {
+            resistances: self
+                .resistances
+                .iter()
+                .zip(other.resistances.iter())
+                .map(|(a, b)| *a.start().max(b.start())..=*a.end().min(b.end()))
+                .collect(),
+            budget: *self.budget.start().max(other.budget.start())
+                ..=*self.budget.end().min(other.budget.end()),
+        }
+    }
+}
+
+/// A range `width` wide that `value` is somewhere in, so the middle of it gives nothing away
+fn estimate(value: u32, width: u32, rng: &mut impl Rng) -> RangeInclusive<u32> {
+    let below = rng.gen_range(0..=width);
+    let start = value.saturating_sub(below);
+    start..=start + width
+}
+
+fn appraise(
+    difficulty: Option<&CustomerDifficulty>,
+    budget: u32,
+    precision: u32,
+    rng: &mut impl Rng,
+) -> Appraisal {
+    let resistance_width = RESISTANCE_SPREAD / precision;
+    let resistances = APPRAISED_HAGGLES
+        .iter()
+        .map(|haggle_type| {
+            let resistance = difficulty
+                .map(|difficulty| difficulty.get_resistance(haggle_type))
+                .unwrap_or(DEFAULT_RESISTANCE)
+                .clamp(0, 100) as u32;
+            let range = estimate(resistance, resistance_width, rng);
+            *range.start() as i32..=(*range.end()).min(100) as i32
+        })
+        .collect();
+    let budget_width = budget * BUDGET_SPREAD / 100 / precision;
+    Appraisal {
+        resistances,
+        budget: estimate(budget, budget_width, rng),
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn handle_appraise_events(
+    mut events: EventReader<AppraiseEvent>,
+    mut out_of_patience: EventWriter<CustomerOutOfPatience>,
+    mut barter: ResMut<Barter>,
+    customer_handler: Res<CustomerHandler>,
+    customers: Query<(&CustomerDifficulty, &MaxPurchaseAmount)>,
+    skills: Res<ActiveSkills>,
+    upgrades: Res<ShopUpgrades>,
+    mut rng: ResMut<RunRng>,
+) {
+    let customer = customer_handler
+        .get_next_customer()
+        .and_then(|customer| customers.get(customer).ok());
+    let mut precision = 1 + upgrades.get_appraisal_precision();
+    if skills.has(Skill::Appraiser) {
+        precision *= APPRAISER_PRECISION;
+    }
+    for _ in events.iter() {
+        // the customer already left or the deal is done, there is nobody to look at
+        if barter.is_resolved() || barter.is_out_of_patience() {
+            continue;
+        }
+
+        let (difficulty, budget) = match customer {
+            Some((difficulty, budget)) => (Some(difficulty), budget.max),
+            // without a budget of their own the customer pays whatever the item is worth
+            None => (None, barter.starting_price),
+        };
+        let appraisal = appraise(difficulty, budget, precision, &mut rng.appraisals);
+        barter.appraisal = Some(match &barter.appraisal {
+            Some(previous) => previous.narrow(&appraisal),
+            None => appraisal,
+        });
+
+        barter.spend_patience(APPRAISAL_PATIENCE_COST);
+        if barter.is_out_of_patience() {
+            out_of_patience.send(CustomerOutOfPatience);
+        }
+    }
+}
@@ -1,16 +1,23 @@
-﻿use crate::loading::CustomerAsepriteHandles;
+﻿use crate::barter::HaggleType;
+use crate::loading::CustomerAsepriteHandles;
+use crate::rng::RunRng;
+use crate::settings::Settings;
+use crate::upgrades::ShopUpgrades;
 use crate::{GameState, PausedState};
 use bevy::app::App;
 use bevy::math::Vec3;
 use bevy::prelude::{
-    Assets, Bundle, Commands, Component, Entity, Local, Plugin, Query, Res, ResMut, Resource,
-    TextureAtlasSprite, Time, Transform, With,
+    Assets, Bundle, Commands, Component, DespawnRecursiveExt, Entity, Local, Plugin, Query, Res,
+    ResMut, Resource, TextureAtlasSprite, Time, Transform, With,
 };
 use bevy::time::{Timer, TimerMode};
 use bevy::utils::default;
 use bevy_mod_aseprite::{Aseprite, AsepriteAnimation, AsepriteBundle};
 use iyes_loopless::prelude::ConditionSet;
+use rand::seq::SliceRandom;
 use rand::{thread_rng, Rng};
+use serde::Deserialize;
+use std::ops::Range;
 use std::time::Duration;
 
 pub struct CustomerPlugin;
@@ -40,14 +47,29 @@ pub const MAX_RIGHT_BARTERING_BOUNDS: f32 = -85.0;
 
 pub const FLOOR_LEVEL: f32 = -46.0;
 
-#[derive(Resource)]
+/// How many customers fit into the shop and the ranges new customers are rolled from
+#[derive(Resource, Clone, PartialEq, Debug)]
 pub struct CustomerSettings {
     pub max_num: u32,
+    /// Resistance to every kind of haggle, out of 100
+    pub resistance: Range<i32>,
+    /// The most gold a customer will pay for anything
+    pub budget: Range<u32>,
+    /// How much haggling a customer puts up with before they walk away
+    pub patience: Range<u32>,
+    /// Seconds between two customers coming in
+    pub spawn_interval: f32,
 }
 
 impl Default for CustomerSettings {
     fn default() -> Self {
-        CustomerSettings { max_num: 5 }
+        CustomerSettings {
+            max_num: 5,
+            resistance: 25..75,
+            budget: 100..500,
+            patience: 8..16,
+            spawn_interval: 5.0,
+        }
     }
 }
 
@@ -62,14 +84,19 @@ impl CustomerHandler {
         commands: &mut Commands,
         mut customer_aseprite_handles: &mut ResMut<CustomerAsepriteHandles>,
         aseprites: &Res<Assets<Aseprite>>,
-    ) {
+        settings: &CustomerSettings,
+        rng: &mut impl Rng,
+    ) -> Entity {
         let entity = commands
             .spawn(CustomerBundle::new_random(
                 &mut customer_aseprite_handles,
                 &aseprites,
+                settings,
+                rng,
             ))
             .id();
-        self.active_customers.push(entity)
+        self.active_customers.push(entity);
+        entity
     }
 
     pub fn get_next_customer(&self) -> Option<Entity> {
@@ -84,9 +111,11 @@ impl CustomerHandler {
 #[derive(Bundle)]
 pub struct CustomerBundle {
     pub customer: Customer,
+    pub archetype: CustomerArchetype,
     pub name: Name,
     pub max_purchase_amount: MaxPurchaseAmount,
     pub customer_difficulty: CustomerDifficulty,
+    pub patience: Patience,
     pub orientation: CustomerState,
     pub aseprite_bundle: AsepriteBundle,
 }
@@ -95,9 +124,9 @@ impl CustomerBundle {
     pub fn new_random(
         customer_aseprite_handles: &mut ResMut<CustomerAsepriteHandles>,
         aseprites: &Res<Assets<Aseprite>>,
+        settings: &CustomerSettings,
+        rng: &mut impl Rng,
     ) -> CustomerBundle {
-        let mut rng = thread_rng();
-
         let handle_index = rng.gen_range(0..customer_aseprite_handles.len());
 
         let aseprite_handle = &customer_aseprite_handles[handle_index];
@@ -106,17 +135,21 @@ impl CustomerBundle {
 
         CustomerBundle {
             customer: Customer,
+            archetype: *CustomerArchetype::ALL.choose(rng).unwrap(),
             name: Name {
                 // TODO - generate random names somehow
                 name: "Bob".to_string(),
             },
             max_purchase_amount: MaxPurchaseAmount {
-                max: rng.gen_range(100..500),
+                max: rng.gen_range(settings.budget.clone()),
             },
             customer_difficulty: CustomerDifficulty {
-                bully_resistance: rng.gen_range(25..75),
-                persuade_resistance: rng.gen_range(25..75),
-                plead_resistance: rng.gen_range(25..75),
+                bully_resistance: rng.gen_range(settings.resistance.clone()),
+                persuade_resistance: rng.gen_range(settings.resistance.clone()),
+                plead_resistance: rng.gen_range(settings.resistance.clone()),
+            },
+            patience: Patience {
+                max: rng.gen_range(settings.patience.clone()),
             },
             orientation: CustomerState::Spawned,
             aseprite_bundle: AsepriteBundle {
@@ -146,6 +179,24 @@ pub struct Customer;
 #[derive(Component)]
 pub struct IsActiveCustomer;
 
+/// The kind of person the customer is, which decides what they say while bartering
+#[derive(Component, Clone, Copy, PartialEq, Eq, Hash, Debug, Deserialize)]
+pub enum CustomerArchetype {
+    Commoner,
+    Noble,
+    Adventurer,
+    Scholar,
+}
+
+impl CustomerArchetype {
+    pub const ALL: [CustomerArchetype; 4] = [
+        CustomerArchetype::Commoner,
+        CustomerArchetype::Noble,
+        CustomerArchetype::Adventurer,
+        CustomerArchetype::Scholar,
+    ];
+}
+
 #[derive(Component)]
 pub struct Name {
     pub name: String,
@@ -156,6 +207,8 @@ pub struct MaxPurchaseAmount {
     pub max: u32,
 }
 
+/// How hard the customer is to haggle with. A haggle succeeds when a roll out of 100 beats the
+/// resistance to it
 #[derive(Component)]
 pub struct CustomerDifficulty {
     pub bully_resistance: i32,
@@ -163,6 +216,26 @@ pub struct CustomerDifficulty {
     pub plead_resistance: i32,
 }
 
+impl CustomerDifficulty {
+    pub fn get_resistance(&self, haggle_type: &HaggleType) -> i32 {
+        return match haggle_type {
+            HaggleType::Plea => self.plead_resistance,
+            HaggleType::Persuade => self.persuade_resistance,
+            HaggleType::Bully => self.bully_resistance,
+            HaggleType::Charm => self
+                .plead_resistance
+                .min(self.persuade_resistance)
+                .min(self.bully_resistance),
+        };
+    }
+}
+
+/// How much haggling the customer puts up with before they walk away
+#[derive(Component)]
+pub struct Patience {
+    pub max: u32,
+}
+
 #[derive(Component, Debug, Clone, Copy)]
 pub enum CustomerState {
     MoveLeft,
@@ -184,6 +257,7 @@ impl Default for SpawnTimer {
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn spawn_customers_if_below_max_num(
     mut customers: ResMut<CustomerHandler>,
     mut commands: Commands,
@@ -192,19 +266,28 @@ pub fn spawn_customers_if_below_max_num(
     aseprites: Res<Assets<Aseprite>>,
     mut spawn_timer: Local<SpawnTimer>,
     time: Res<Time>,
+    mut rng: ResMut<RunRng>,
+    upgrades: Res<ShopUpgrades>,
 ) {
     spawn_timer
         .timer
         .tick(Duration::from_secs_f32(time.delta_seconds()));
-    if customers.active_customers.len() < settings.max_num as usize && spawn_timer.timer.finished()
+    // a bigger counter makes room for more customers in line
+    let max_num = settings.max_num + upgrades.get_extra_customers();
+    if customers.active_customers.len() < max_num as usize && spawn_timer.timer.finished()
     {
         println!("spawning customers");
         customers.spawn_new_random_customer(
             &mut commands,
             &mut customer_aseprite_handles,
             &aseprites,
+            &settings,
+            &mut rng.customers,
+        );
+        spawn_timer.timer = Timer::new(
+            Duration::from_secs_f32(settings.spawn_interval),
+            TimerMode::Once,
         );
-        spawn_timer.timer = Timer::new(Duration::from_secs_f32(5.0), TimerMode::Once);
     }
 }
 
@@ -219,6 +302,7 @@ pub fn move_customers(
         With<Customer>,
     >,
     time: Res<Time>,
+    settings: Res<Settings>,
     mut commands: Commands,
 ) {
     for (entity, mut transform, mut orientation, is_active_customer) in customers.iter_mut() {
@@ -249,6 +333,10 @@ pub fn move_customers(
             }
 
             CustomerState::Spawned => {
+                // with reduced motion customers appear on the floor instead of dropping in
+                if settings.reduced_motion {
+                    transform.translation.y = transform.translation.y.min(FLOOR_LEVEL);
+                }
                 if transform.translation.y > FLOOR_LEVEL {
                     transform.translation.y -= 250.0 * time.delta_seconds();
                 } else {
@@ -269,10 +357,12 @@ pub fn move_customers(
                 }
             }
             CustomerState::Despawning => {
-                if transform.translation.y > -200.0 {
+                // and vanish instead of falling through the floor
+                if transform.translation.y > -200.0 && !settings.reduced_motion {
                     transform.translation.y -= 250.0 * time.delta_seconds();
                 } else {
-                    commands.entity(entity).despawn();
+                    // recursive so the speech bubble of the customer goes with them
+                    commands.entity(entity).despawn_recursive();
                 }
             }
         }
@@ -2,12 +2,25 @@
 //! A Barter is the entire barter, trying to sell an item, attempting to barter, etc
 //! A Haggle is an individual attempt to adjust the price
 
-use crate::barter::customers::{CustomerHandler, CustomerPlugin, CustomerState};
+use crate::barter::appraisal::{Appraisal, AppraisalPlugin};
+use crate::barter::customers::{
+    CustomerDifficulty, CustomerHandler, CustomerPlugin, CustomerState, MaxPurchaseAmount, Patience,
+};
+use crate::difficulty::Difficulty;
+use crate::market::items::{Inventory, ItemCategory};
+use crate::market::Market;
+use crate::rng::RunRng;
+use crate::skills::ActiveSkills;
 use crate::ui::UiState;
-use bevy::prelude::{App, Commands, EventReader, EventWriter, Plugin, ResMut, Resource};
+use crate::upgrades::ShopUpgrades;
+use bevy::prelude::{
+    App, Commands, EventReader, EventWriter, Plugin, Query, Res, ResMut, Resource,
+};
 use iyes_loopless::prelude::{AppLooplessStateExt, ConditionSet, NextState};
-use rand::{thread_rng, Rng};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
 
+pub mod appraisal;
 pub mod customers;
 
 pub struct BarterPlugin;
@@ -20,9 +33,11 @@ impl Plugin for BarterPlugin {
             .add_event::<EndBarter>()
             .add_event::<BarterResolved>()
             .add_event::<HaggleAttemptEvent>()
-            .add_event::<HaggleResultEvent>();
+            .add_event::<HaggleResultEvent>()
+            .add_event::<CustomerOutOfPatience>();
 
-        app.add_plugin(CustomerPlugin);
+        app.add_plugin(CustomerPlugin)
+            .add_plugin(AppraisalPlugin);
 
         app.add_system_set(
             ConditionSet::new()
@@ -38,6 +53,12 @@ impl Plugin for BarterPlugin {
                 .run_on_event::<HaggleAttemptEvent>()
                 .with_system(handle_haggle_attempt_events)
                 .into(),
+        )
+        .add_system_set(
+            ConditionSet::new()
+                .run_on_event::<BarterResolved>()
+                .with_system(handle_barter_resolved)
+                .into(),
         );
     }
 }
@@ -52,10 +73,30 @@ pub struct BeginBarter;
 
 pub struct EndBarter;
 
+/// How much patience a successful haggle costs the customer
+pub const HAGGLE_SUCCESS_PATIENCE_COST: u32 = 2;
+/// How much patience a failed haggle costs the customer. Failing annoys them more than losing
+pub const HAGGLE_FAILURE_PATIENCE_COST: u32 = 3;
+/// Patience used for a barter when the customer doesn't have any
+pub const DEFAULT_PATIENCE: u32 = 10;
+/// Resistance used for a haggle when the customer doesn't have any, an even chance
+pub const DEFAULT_RESISTANCE: i32 = 50;
+
+/// Sent when the customer has run out of patience and walks away from the barter
+pub struct CustomerOutOfPatience;
+
 #[derive(Clone, PartialEq, Eq, Hash, Resource)]
 pub struct Barter {
+    item: ItemCategory,
+    starting_price: u32,
     sell_price: u32,
     haggles: Vec<HaggleResultEvent>,
+    patience: u32,
+    max_patience: u32,
+    /// What the player found out about the customer by appraising them
+    appraisal: Option<Appraisal>,
+    /// Set once the barter was approved, denied or walked away from, nothing changes it after
+    resolved: bool,
 }
 
 impl Barter {
@@ -70,29 +111,78 @@ impl Barter {
     pub fn get_price(&self) -> u32{
         self.sell_price
     }
+
+    pub fn get_item(&self) -> ItemCategory {
+        self.item
+    }
+
+    pub fn get_starting_price(&self) -> u32 {
+        self.starting_price
+    }
+
+    pub fn get_haggles(&self) -> &Vec<HaggleResultEvent> {
+        &self.haggles
+    }
+
+    pub fn get_patience(&self) -> u32 {
+        self.patience
+    }
+
+    pub fn get_max_patience(&self) -> u32 {
+        self.max_patience
+    }
+
+    pub fn get_appraisal(&self) -> Option<&Appraisal> {
+        self.appraisal.as_ref()
+    }
+
+    /// How much of the customers patience is left, from 1.0 when the barter starts to 0.0
+    pub fn get_patience_fraction(&self) -> f32 {
+        if self.max_patience == 0 {
+            return 0.0;
+        }
+        self.patience as f32 / self.max_patience as f32
+    }
+
+    pub fn is_out_of_patience(&self) -> bool {
+        self.patience == 0
+    }
+
+    pub fn spend_patience(&mut self, amount: u32) {
+        self.patience = self.patience.saturating_sub(amount);
+    }
+
+    pub fn is_resolved(&self) -> bool {
+        self.resolved
+    }
+
+    /// Marks the barter as resolved. Returns false if it already was, so it is only resolved once
+    pub fn resolve(&mut self) -> bool {
+        if self.resolved {
+            return false;
+        }
+        self.resolved = true;
+        true
+    }
 }
 
-#[derive(Clone, PartialEq, Eq, Hash)]
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
 pub enum HaggleType {
     Plea,
     Persuade,
     Bully,
+    /// Unlocked by [`crate::skills::Skill::Charm`], works against whatever the customer resists least
+    Charm,
 }
 
 impl HaggleType {
-    pub fn get_string_name(barter_type: HaggleType) -> String {
-        return match barter_type {
-            HaggleType::Bully => String::from("Bully"),
-            HaggleType::Persuade => String::from("Persuade"),
-            HaggleType::Plea => String::from("Plea"),
-        };
-    }
-
-    pub fn get_string_name_from_instance(&self) -> String {
+    /// Id of the name of the haggle in the translations
+    pub fn get_locale_key(&self) -> &'static str {
         return match self {
-            HaggleType::Bully => String::from("Bully"),
-            HaggleType::Persuade => String::from("Persuade"),
-            HaggleType::Plea => String::from("Plea"),
+            HaggleType::Bully => "haggle-bully",
+            HaggleType::Persuade => "haggle-persuade",
+            HaggleType::Plea => "haggle-plea",
+            HaggleType::Charm => "haggle-charm",
         };
     }
 }
@@ -104,7 +194,7 @@ pub struct HaggleAttemptEvent {
 }
 
 /// This is the result of a haggle attempt event
-#[derive(Clone, PartialEq, Eq, Hash)]
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Serialize)]
 pub struct HaggleResultEvent {
     pub result: HaggleResult,
     pub attempt_type: HaggleType,
@@ -112,7 +202,7 @@ pub struct HaggleResultEvent {
 }
 
 /// This is an enum used to match the the result of a haggle attempt event
-#[derive(Clone, PartialEq, Eq, Hash)]
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Serialize)]
 pub enum HaggleResult {
     Success,
     Failure,
@@ -121,6 +211,7 @@ pub enum HaggleResult {
 #[derive(Clone, PartialEq, Eq, Hash)]
 pub struct BarterResolved {
     pub(crate) resolution_type: BarterResolutionTypes,
+    pub(crate) item: ItemCategory,
 }
 
 #[derive(Clone, PartialEq, Default, Eq, Hash)]
@@ -134,49 +225,120 @@ pub enum BarterResolutionTypes {
 
 fn handle_bartering() {}
 
+#[allow(clippy::too_many_arguments)]
 fn handle_haggle_attempt_events(
     mut events: EventReader<HaggleAttemptEvent>,
     mut results: EventWriter<HaggleResultEvent>,
+    mut out_of_patience: EventWriter<CustomerOutOfPatience>,
     mut barter: ResMut<Barter>,
+    customer_handler: Res<CustomerHandler>,
+    customers: Query<(&CustomerDifficulty, &MaxPurchaseAmount)>,
+    difficulty: Res<Difficulty>,
+    skills: Res<ActiveSkills>,
+    mut rng: ResMut<RunRng>,
 ) {
+    let customer = customer_handler
+        .get_next_customer()
+        .and_then(|customer| customers.get(customer).ok());
+    let price_step = difficulty.values.price_step;
     for event in events.iter() {
-        let mut rng = thread_rng();
-        let chance = rng.gen_range(0..=1);
-        let result = match chance {
-            0 => {
-                barter.sell_price = barter.sell_price.saturating_add(10);
-                HaggleResultEvent {
-                    result: HaggleResult::Success,
-                    attempt_type: event.attempt_type.clone(),
-                    new_price: barter.sell_price,
-                }
+        // the customer already left or the deal is done, they won't listen to any more haggling
+        if barter.is_resolved() || barter.is_out_of_patience() {
+            continue;
+        }
+
+        let (resistance, budget) = match customer {
+            Some((difficulty, budget)) => {
+                (difficulty.get_resistance(&event.attempt_type), budget.max)
+            }
+            None => (DEFAULT_RESISTANCE, u32::MAX),
+        };
+        let resistance = resistance - skills.get_resistance_bonus(&event.attempt_type);
+        let result = if rng.haggles.gen_range(0..100) >= resistance {
+            // no amount of haggling gets a customer to pay more than they have
+            barter.sell_price = barter
+                .sell_price
+                .saturating_add(skills.get_price_step(&event.attempt_type, price_step))
+                .min(budget.max(barter.sell_price));
+            HaggleResultEvent {
+                result: HaggleResult::Success,
+                attempt_type: event.attempt_type.clone(),
+                new_price: barter.sell_price,
             }
-            _ => {
-                barter.sell_price = barter.sell_price.saturating_sub(10);
-                HaggleResultEvent {
-                    result: HaggleResult::Failure,
-                    attempt_type: event.attempt_type.clone(),
-                    new_price: barter.sell_price,
-                }
+        } else {
+            barter.sell_price = barter.sell_price.saturating_sub(price_step);
+            HaggleResultEvent {
+                result: HaggleResult::Failure,
+                attempt_type: event.attempt_type.clone(),
+                new_price: barter.sell_price,
             }
         };
 
+        barter.spend_patience(match result.result {
+            HaggleResult::Success => HAGGLE_SUCCESS_PATIENCE_COST,
+            HaggleResult::Failure => HAGGLE_FAILURE_PATIENCE_COST,
+        });
         barter.log_result(result.clone());
-        results.send(result)
+        results.send(result);
+
+        if barter.is_out_of_patience() {
+            out_of_patience.send(CustomerOutOfPatience);
+        }
     }
 }
 
-fn begin_barter(mut commands: Commands) {
-    let mut rng = thread_rng();
-    let price = rng.gen_range(35..50);
+#[allow(clippy::too_many_arguments)]
+fn begin_barter(
+    mut commands: Commands,
+    market: Res<Market>,
+    inventory: Res<Inventory>,
+    customer_handler: Res<CustomerHandler>,
+    patience: Query<&Patience>,
+    mut begin: EventWriter<BeginBarter>,
+    skills: Res<ActiveSkills>,
+    upgrades: Res<ShopUpgrades>,
+    mut rng: ResMut<RunRng>,
+) {
+    let rng = &mut rng.barters;
+    let item = inventory
+        .random_in_stock(rng)
+        .expect("We should only start bartering when we have something to sell");
+    // customers open a little above or below what the item is worth on the market tonight,
+    // which display cases make look like more
+    let value = upgrades.get_item_value(market.get_value(item));
+    let price = (value as f32 * rng.gen_range(0.85..1.2)).round() as u32;
+    let patience = customer_handler
+        .get_next_customer()
+        .and_then(|customer| patience.get(customer).ok())
+        .map(|patience| patience.max)
+        .unwrap_or(DEFAULT_PATIENCE)
+        + skills.get_patience_bonus();
 
     commands.insert_resource(Barter {
+        item,
+        starting_price: price,
         sell_price: price,
         haggles: vec![],
+        patience,
+        max_patience: patience,
+        appraisal: None,
+        resolved: false,
     });
+    begin.send(BeginBarter);
     commands.insert_resource(NextState(UiState::Barter));
 }
 
+fn handle_barter_resolved(
+    mut events: EventReader<BarterResolved>,
+    mut inventory: ResMut<Inventory>,
+) {
+    for event in events.iter() {
+        if let BarterResolutionTypes::Approve { .. } = event.resolution_type {
+            inventory.remove_one(event.item);
+        }
+    }
+}
+
 fn end_barter(mut commands: Commands, mut customer_handler: ResMut<CustomerHandler>) {
     commands.insert_resource(NextState(UiState::Normal));
     commands
@@ -0,0 +1,476 @@
+//! The campaign tells a story over a few chapters, read from the file in `assets/campaign`.
+//! Every chapter gives the player a handful of nights to reach its [`ChapterObjective`]s, like
+//! earning an amount of gold, reaching a reputation or selling a specific item. Finishing them
+//! shows the outro of the chapter and moves on to the next one, running out of nights fails the
+//! chapter and sends the player back to the main menu.
+//! Progress is saved to [`CAMPAIGN_FILE`] at the start of every chapter, with the gold, reputation,
+//! stock, night and story flags the chapter started with, so continuing the campaign picks up at
+//! the start of the chapter the player was on
+
+use crate::barter::{BarterResolutionTypes, BarterResolved};
+use crate::dialogue::tree::StoryFlags;
+use crate::difficulty::Difficulty;
+use crate::loading::CampaignAssets;
+use crate::localization::{get_localized, Localization, LocalizedText};
+use crate::market::items::{Inventory, ItemCategory};
+use crate::night::{Night, NightEnded};
+use crate::persistence::{load_ron, save_on_change, RonAsset, RonAssetLoader};
+use crate::player::{Gold, Reputation};
+use crate::run::RunMode;
+use crate::ui::UiState;
+use crate::upgrades::ShopUpgrades;
+use crate::{GameState, PausedState};
+use bevy::prelude::{AddAsset, App, Assets, Commands, EventReader, Plugin, Res, ResMut, Resource};
+use bevy::reflect::TypeUuid;
+use bevy::utils::HashMap;
+use iyes_loopless::prelude::{AppLooplessStateExt, ConditionSet, CurrentState, NextState};
+use serde::{Deserialize, Serialize};
+
+pub struct CampaignPlugin;
+
+impl Plugin for CampaignPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_asset::<Campaign>()
+            .init_asset_loader::<RonAssetLoader<Campaign>>()
+            .insert_resource(load_ron::<CampaignSave>(CAMPAIGN_FILE).unwrap_or_default())
+            .add_event::<ContinueCampaign>()
+            .add_enter_system(GameState::Playing, start_campaign_run)
+            .add_exit_system(GameState::Playing, end_campaign_run)
+            .add_enter_system(UiState::Chapter, pause_for_chapter)
+            .add_exit_system(UiState::Chapter, resume_after_chapter)
+            .add_system(save_on_change::<CampaignSave>(CAMPAIGN_FILE))
+            .add_system_set(
+                ConditionSet::new()
+                    .run_in_state(GameState::Playing)
+                    .run_if_resource_exists::<CampaignProgress>()
+                    .with_system(track_objectives)
+                    .into(),
+            )
+            .add_system_set(
+                ConditionSet::new()
+                    .run_in_state(UiState::Chapter)
+                    .run_if_resource_exists::<CampaignProgress>()
+                    .with_system(continue_campaign)
+                    .into(),
+            );
+    }
+}
+
+pub const CAMPAIGN_FILE: &str = "campaign.ron";
+
+/// Something the player has to do before a chapter runs out of nights
+#[derive(Clone, PartialEq, Eq, Debug, Deserialize)]
+pub enum ChapterObjective {
+    /// Make this much gold from sales during the chapter
+    EarnGold(u32),
+    ReachReputation(i32),
+    /// Sell this many of the item during the chapter
+    SellItem(ItemCategory, u32),
+    /// A dialogue tree set the flag. Flags aren't meant to be read by players, so the objective
+    /// brings its own description
+    StoryFlag {
+        flag: String,
+        description: LocalizedText,
+    },
+}
+
+impl ChapterObjective {
+    pub fn is_complete(
+        &self,
+        progress: &CampaignProgress,
+        reputation: &Reputation,
+        flags: &StoryFlags,
+    ) -> bool {
+        return match self {
+            ChapterObjective::EarnGold(amount) => progress.gold_earned >= *amount,
+            ChapterObjective::ReachReputation(amount) => reputation.amount >= *amount,
+            ChapterObjective::SellItem(item, count) => progress.get_sold(*item) >= *count,
+            ChapterObjective::StoryFlag { flag, .. } => flags.is_set(flag),
+        };
+    }
+
+    /// What the player has to do, along with how far they got
+    pub fn get_text(
+        &self,
+        progress: &CampaignProgress,
+        reputation: &Reputation,
+        localization: &Localization,
+    ) -> String {
+        return match self {
+            ChapterObjective::EarnGold(amount) => localization.get_with(
+                "objective-earn-gold",
+                &[
+                    ("amount", (*amount).into()),
+                    ("progress", progress.gold_earned.min(*amount).into()),
+                ],
+            ),
+            ChapterObjective::ReachReputation(amount) => localization.get_with(
+                "objective-reach-reputation",
+                &[
+                    ("amount", (*amount).into()),
+                    ("progress", reputation.amount.min(*amount).into()),
+                ],
+            ),
+            ChapterObjective::SellItem(item, count) => localization.get_with(
+                "objective-sell-item",
+                &[
+                    ("item", localization.get(item.get_locale_key()).into()),
+                    ("count", (*count).into()),
+                    ("progress", progress.get_sold(*item).min(*count).into()),
+                ],
+            ),
+            ChapterObjective::StoryFlag { description, .. } => {
+                get_localized(description, localization.get_language()).to_string()
+            }
+        };
+    }
+}
+
+#[derive(Clone, PartialEq, Debug, Deserialize)]
+pub struct Chapter {
+    pub title: LocalizedText,
+    /// Shown before the chapter starts
+    pub intro: LocalizedText,
+    /// Shown once every objective is done
+    pub outro: LocalizedText,
+    /// How many nights the player has for the objectives, counting the night the chapter starts on
+    pub nights: u32,
+    pub objectives: Vec<ChapterObjective>,
+}
+
+#[derive(Clone, PartialEq, Debug, Deserialize)]
+pub struct CampaignEnding {
+    pub title: LocalizedText,
+    pub text: LocalizedText,
+}
+
+/// Read from a `.campaign.ron` file
+#[derive(Clone, PartialEq, Debug, Deserialize, TypeUuid)]
+#[uuid = "5d2e8f14-7b39-4a6c-9e01-c4b7a3d8f562"]
+pub struct Campaign {
+    pub chapters: Vec<Chapter>,
+    /// Shown after the outro of the last chapter
+    pub ending: CampaignEnding,
+    /// Shown when a chapter runs out of nights
+    pub failed: LocalizedText,
+}
+
+impl Campaign {
+    /// The title and text the chapter screen shows
+    pub fn get_screen_text(
+        &self,
+        screen: ChapterScreen,
+    ) -> Option<(&LocalizedText, &LocalizedText)> {
+        return match screen {
+            ChapterScreen::Intro(chapter) => self
+                .chapters
+                .get(chapter)
+                .map(|chapter| (&chapter.title, &chapter.intro)),
+            ChapterScreen::Outro(chapter) => self
+                .chapters
+                .get(chapter)
+                .map(|chapter| (&chapter.title, &chapter.outro)),
+            ChapterScreen::Failed(chapter) => self
+                .chapters
+                .get(chapter)
+                .map(|chapter| (&chapter.title, &self.failed)),
+            ChapterScreen::Ending => Some((&self.ending.title, &self.ending.text)),
+        };
+    }
+}
+
+impl RonAsset for Campaign {
+    const EXTENSIONS: &'static [&'static str] = &["campaign.ron"];
+}
+
+/// The shop as it was when a chapter started
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct CampaignCheckpoint {
+    pub gold: u32,
+    pub reputation: i32,
+    pub inventory: Inventory,
+    pub night: u32,
+    pub flags: StoryFlags,
+    #[serde(default)]
+    pub upgrades: ShopUpgrades,
+}
+
+impl CampaignCheckpoint {
+    pub fn new(
+        gold: &Gold,
+        reputation: &Reputation,
+        inventory: &Inventory,
+        night: &Night,
+        flags: &StoryFlags,
+        upgrades: &ShopUpgrades,
+    ) -> CampaignCheckpoint {
+        CampaignCheckpoint {
+            gold: gold.amount,
+            reputation: reputation.amount,
+            inventory: inventory.clone(),
+            night: night.number,
+            flags: flags.clone(),
+            upgrades: upgrades.clone(),
+        }
+    }
+
+    pub fn restore(
+        &self,
+        gold: &mut Gold,
+        reputation: &mut Reputation,
+        inventory: &mut Inventory,
+        night: &mut Night,
+        flags: &mut StoryFlags,
+        upgrades: &mut ShopUpgrades,
+    ) {
+        gold.amount = self.gold;
+        reputation.amount = self.reputation;
+        *inventory = self.inventory.clone();
+        night.number = self.night;
+        night.elapsed = 0.0;
+        *flags = self.flags.clone();
+        *upgrades = self.upgrades.clone();
+    }
+}
+
+/// How far the player got in the campaign, saved in [`CAMPAIGN_FILE`]
+#[derive(Resource, Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CampaignSave {
+    pub chapter: usize,
+    /// The shop at the start of `chapter`. None until the campaign was started
+    pub checkpoint: Option<CampaignCheckpoint>,
+    /// The player saw the ending
+    pub finished: bool,
+    /// Picked when the campaign was started, continuing the campaign plays on with it
+    pub difficulty: Difficulty,
+}
+
+impl CampaignSave {
+    pub fn can_continue(&self) -> bool {
+        self.checkpoint.is_some() && !self.finished
+    }
+
+    /// Forgets the old campaign, the checkpoint of the first chapter is stored once it starts
+    pub fn start_new(&mut self, difficulty: &Difficulty) {
+        *self = CampaignSave {
+            difficulty: difficulty.clone(),
+            ..CampaignSave::default()
+        };
+    }
+}
+
+/// The chapter that is being played, only exists during campaign runs
+#[derive(Resource, Clone, PartialEq, Debug)]
+pub struct CampaignProgress {
+    pub chapter: usize,
+    /// The night the chapter started on
+    pub first_night: u32,
+    /// Gold made from sales since the chapter started
+    pub gold_earned: u32,
+    /// Items sold since the chapter started
+    pub sold: HashMap<ItemCategory, u32>,
+    /// The last night of the chapter is over
+    pub out_of_nights: bool,
+    /// The chapter was completed or failed and the player was told about it
+    pub finished: bool,
+}
+
+impl CampaignProgress {
+    pub fn new(chapter: usize, first_night: u32) -> CampaignProgress {
+        CampaignProgress {
+            chapter,
+            first_night,
+            gold_earned: 0,
+            sold: HashMap::default(),
+            out_of_nights: false,
+            finished: false,
+        }
+    }
+
+    pub fn get_sold(&self, item: ItemCategory) -> u32 {
+        self.sold.get(&item).copied().unwrap_or_default()
+    }
+
+    /// The last night the objectives of the chapter can be done on
+    pub fn get_last_night(&self, chapter: &Chapter) -> u32 {
+        self.first_night + chapter.nights.max(1) - 1
+    }
+
+    /// How many nights are left including the current one
+    pub fn get_nights_left(&self, chapter: &Chapter, night: &Night) -> u32 {
+        (self.get_last_night(chapter) + 1).saturating_sub(night.number)
+    }
+}
+
+/// What the chapter screen is showing
+#[derive(Resource, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ChapterScreen {
+    Intro(usize),
+    Outro(usize),
+    Ending,
+    Failed(usize),
+}
+
+/// Sent by the chapter screen when the player is done reading it
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct ContinueCampaign;
+
+#[allow(clippy::too_many_arguments)]
+fn start_campaign_run(
+    mut commands: Commands,
+    run_mode: Res<RunMode>,
+    mut save: ResMut<CampaignSave>,
+    mut gold: ResMut<Gold>,
+    mut reputation: ResMut<Reputation>,
+    mut inventory: ResMut<Inventory>,
+    mut night: ResMut<Night>,
+    mut flags: ResMut<StoryFlags>,
+    mut upgrades: ResMut<ShopUpgrades>,
+) {
+    if *run_mode != RunMode::Campaign {
+        return;
+    }
+    match &save.checkpoint {
+        Some(checkpoint) => checkpoint.restore(
+            &mut gold,
+            &mut reputation,
+            &mut inventory,
+            &mut night,
+            &mut flags,
+            &mut upgrades,
+        ),
+        None => {
+            save.checkpoint = Some(CampaignCheckpoint::new(
+                &gold,
+                &reputation,
+                &inventory,
+                &night,
+                &flags,
+                &upgrades,
+            ))
+        }
+    }
+    commands.insert_resource(CampaignProgress::new(save.chapter, night.number));
+    commands.insert_resource(ChapterScreen::Intro(save.chapter));
+    commands.insert_resource(NextState(UiState::Chapter));
+}
+
+fn end_campaign_run(mut commands: Commands) {
+    commands.remove_resource::<CampaignProgress>();
+    commands.remove_resource::<ChapterScreen>();
+}
+
+/// The nights don't go on while the player reads
+fn pause_for_chapter(mut commands: Commands) {
+    commands.insert_resource(NextState(PausedState::Paused));
+}
+
+fn resume_after_chapter(mut commands: Commands) {
+    commands.insert_resource(NextState(PausedState::Playing));
+}
+
+#[allow(clippy::too_many_arguments)]
+fn track_objectives(
+    mut commands: Commands,
+    mut resolved: EventReader<BarterResolved>,
+    mut night_ended: EventReader<NightEnded>,
+    mut progress: ResMut<CampaignProgress>,
+    campaign_assets: Res<CampaignAssets>,
+    campaigns: Res<Assets<Campaign>>,
+    reputation: Res<Reputation>,
+    flags: Res<StoryFlags>,
+    ui_state: Res<CurrentState<UiState>>,
+) {
+    for event in resolved.iter() {
+        if let BarterResolutionTypes::Approve { amount } = event.resolution_type {
+            progress.gold_earned += amount;
+            *progress.sold.entry(event.item).or_default() += 1;
+        }
+    }
+    let chapter = match campaigns
+        .get(&campaign_assets.campaign)
+        .and_then(|campaign| campaign.chapters.get(progress.chapter))
+    {
+        Some(chapter) => chapter,
+        None => return,
+    };
+    for event in night_ended.iter() {
+        if event.night >= progress.get_last_night(chapter) {
+            progress.out_of_nights = true;
+        }
+    }
+
+    // a barter or dialogue that is going on is finished first
+    if progress.finished || ui_state.0 != UiState::Normal {
+        return;
+    }
+    let complete = chapter
+        .objectives
+        .iter()
+        .all(|objective| objective.is_complete(&progress, &reputation, &flags));
+    let screen = if complete {
+        ChapterScreen::Outro(progress.chapter)
+    } else if progress.out_of_nights {
+        ChapterScreen::Failed(progress.chapter)
+    } else {
+        return;
+    };
+    progress.finished = true;
+    commands.insert_resource(screen);
+    commands.insert_resource(NextState(UiState::Chapter));
+}
+
+#[allow(clippy::too_many_arguments)]
+fn continue_campaign(
+    mut commands: Commands,
+    mut events: EventReader<ContinueCampaign>,
+    screen: Res<ChapterScreen>,
+    mut progress: ResMut<CampaignProgress>,
+    mut save: ResMut<CampaignSave>,
+    campaign_assets: Res<CampaignAssets>,
+    campaigns: Res<Assets<Campaign>>,
+    gold: Res<Gold>,
+    reputation: Res<Reputation>,
+    inventory: Res<Inventory>,
+    night: Res<Night>,
+    flags: Res<StoryFlags>,
+    upgrades: Res<ShopUpgrades>,
+) {
+    if events.iter().count() == 0 {
+        return;
+    }
+    let chapter_count = campaigns
+        .get(&campaign_assets.campaign)
+        .map(|campaign| campaign.chapters.len())
+        .unwrap_or_default();
+
+    match *screen {
+        ChapterScreen::Intro(_) => commands.insert_resource(NextState(UiState::Normal)),
+        ChapterScreen::Outro(chapter) => {
+            let next = chapter + 1;
+            if next < chapter_count {
+                *progress = CampaignProgress::new(next, night.number);
+                save.chapter = next;
+                save.checkpoint = Some(CampaignCheckpoint::new(
+                    &gold,
+                    &reputation,
+                    &inventory,
+                    &night,
+                    &flags,
+                    &upgrades,
+                ));
+                commands.insert_resource(ChapterScreen::Intro(next));
+            } else {
+                save.finished = true;
+                commands.insert_resource(ChapterScreen::Ending);
+            }
+            // entering the chapter screen again rebuilds it for the new screen
+            commands.insert_resource(NextState(UiState::Chapter));
+        }
+        // a failed chapter can be tried again by continuing the campaign from the menu
+        ChapterScreen::Ending | ChapterScreen::Failed(_) => {
+            commands.insert_resource(NextState(GameState::MainMenu))
+        }
+    }
+}
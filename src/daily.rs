@@ -0,0 +1,133 @@
+//! The daily challenge is an endless run everyone plays with the same seed on the same day, so
+//! the same customers come in with the same items to sell. The seed comes from the date, and
+//! every day can only be attempted once. Attempts and their results are kept in [`DAILY_FILE`]
+
+use crate::endless::EndlessRun;
+use crate::ledger::Ledger;
+use crate::persistence::{load_ron, save_on_change};
+use crate::rng::{mix_seed, RunRng};
+use crate::run::RunMode;
+use crate::GameState;
+use bevy::prelude::{App, Plugin, Res, ResMut, Resource};
+use iyes_loopless::prelude::{AppLooplessStateExt, ConditionSet};
+use serde::{Deserialize, Serialize};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub struct DailyPlugin;
+
+impl Plugin for DailyPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(load_ron::<DailyScores>(DAILY_FILE).unwrap_or_default())
+            .add_enter_system(GameState::Playing, start_daily_run)
+            .add_system(save_on_change::<DailyScores>(DAILY_FILE))
+            .add_system_set(
+                ConditionSet::new()
+                    .run_in_state(GameState::Playing)
+                    .run_if_resource_exists::<EndlessRun>()
+                    .with_system(record_daily_result)
+                    .into(),
+            );
+    }
+}
+
+pub const DAILY_FILE: &str = "daily.ron";
+const SECONDS_PER_DAY: u64 = 60 * 60 * 24;
+
+/// Days since the first of january 1970, in UTC so everyone switches to the next challenge at
+/// the same time
+pub fn today() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs() / SECONDS_PER_DAY)
+        .unwrap_or(0)
+}
+
+pub fn get_daily_seed(day: u64) -> u64 {
+    mix_seed(day)
+}
+
+/// The day as `YYYY-MM-DD`
+pub fn format_day(day: u64) -> String {
+    // days to a civil date, see http://howardhinnant.github.io/date_algorithms.html
+    let z = day as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day_of_month = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!("{:04}-{:02}-{:02}", year, month, day_of_month)
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+pub struct DailyAttempt {
+    pub day: u64,
+    pub seed: u64,
+    pub nights: u32,
+    pub gold: u32,
+    pub score: u32,
+}
+
+/// Every daily challenge the player attempted, saved in [`DAILY_FILE`]
+#[derive(Resource, Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DailyScores {
+    pub attempts: Vec<DailyAttempt>,
+}
+
+impl DailyScores {
+    pub fn get_attempt(&self, day: u64) -> Option<&DailyAttempt> {
+        self.attempts.iter().find(|attempt| attempt.day == day)
+    }
+
+    pub fn has_attempted(&self, day: u64) -> bool {
+        self.get_attempt(day).is_some()
+    }
+}
+
+/// The attempt counts as soon as the run starts, leaving early doesn't give another try
+fn start_daily_run(run_mode: Res<RunMode>, mut scores: ResMut<DailyScores>) {
+    if *run_mode != RunMode::Daily {
+        return;
+    }
+    let day = today();
+    if scores.has_attempted(day) {
+        return;
+    }
+    scores.attempts.push(DailyAttempt {
+        day,
+        seed: get_daily_seed(day),
+        ..DailyAttempt::default()
+    });
+}
+
+/// Keeps the attempt up to date with the run, so leaving at any point keeps the result
+fn record_daily_result(
+    run_mode: Res<RunMode>,
+    run: Res<EndlessRun>,
+    ledger: Res<Ledger>,
+    rng: Res<RunRng>,
+    mut scores: ResMut<DailyScores>,
+) {
+    if *run_mode != RunMode::Daily || (!run.is_changed() && !ledger.is_changed()) {
+        return;
+    }
+    let gold = ledger.stats_all_time().revenue;
+    // found by seed, the run may go on past midnight
+    let attempt = scores
+        .attempts
+        .iter_mut()
+        .find(|attempt| attempt.seed == rng.seed);
+    if let Some(attempt) = attempt {
+        attempt.nights = run.nights_survived;
+        attempt.gold = gold;
+        attempt.score = run.get_score(gold);
+    }
+}
@@ -0,0 +1,185 @@
+//! Customers talk while they barter. They greet the player, react to every haggle and comment on
+//! how the barter ended. What they say depends on their [`CustomerArchetype`], every archetype has
+//! its own dialogue file in `assets/dialogue` with a few lines per [`DialogueMoment`] and language.
+//! One of them is picked at random each time. Languages missing from a file fall back to English.
+//! The lines end up in a [`CustomerSpoke`] event, the interface decides how to show them
+
+use crate::barter::customers::{CustomerArchetype, CustomerHandler};
+use crate::barter::{
+    Barter, BarterResolutionTypes, BarterResolved, BeginBarter, HaggleResult, HaggleResultEvent,
+    HaggleType,
+};
+use crate::dialogue::tree::DialogueTreePlugin;
+use crate::loading::DialogueAssets;
+use crate::localization::{Localization, DEFAULT_LANGUAGE};
+use crate::persistence::{RonAsset, RonAssetLoader};
+use crate::GameState;
+use bevy::prelude::{AddAsset, App, Assets, Entity, EventReader, EventWriter, Plugin, Query, Res};
+use bevy::reflect::TypeUuid;
+use bevy::utils::HashMap;
+use iyes_loopless::prelude::ConditionSet;
+use rand::seq::SliceRandom;
+use rand::thread_rng;
+use serde::Deserialize;
+
+pub mod tree;
+
+pub struct DialoguePlugin;
+
+impl Plugin for DialoguePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_asset::<CustomerDialogue>()
+            .init_asset_loader::<RonAssetLoader<CustomerDialogue>>()
+            .add_event::<CustomerSpoke>()
+            .add_plugin(DialogueTreePlugin)
+            .add_system_set(
+                ConditionSet::new()
+                    .run_in_state(GameState::Playing)
+                    .with_system(route_barter_dialogue)
+                    .into(),
+            );
+    }
+}
+
+/// The moments of a barter a customer has something to say about
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Deserialize)]
+pub enum DialogueMoment {
+    Greeting,
+    PleaSuccess,
+    PleaFailure,
+    PersuadeSuccess,
+    PersuadeFailure,
+    BullySuccess,
+    BullyFailure,
+    DealApproved,
+    DealDenied,
+    /// The customer ran out of patience and left
+    WalkedAway,
+}
+
+impl DialogueMoment {
+    pub fn from_haggle(attempt_type: &HaggleType, result: &HaggleResult) -> DialogueMoment {
+        return match (attempt_type, result) {
+            (HaggleType::Plea, HaggleResult::Success) => DialogueMoment::PleaSuccess,
+            (HaggleType::Plea, HaggleResult::Failure) => DialogueMoment::PleaFailure,
+            (HaggleType::Persuade, HaggleResult::Success) => DialogueMoment::PersuadeSuccess,
+            (HaggleType::Persuade, HaggleResult::Failure) => DialogueMoment::PersuadeFailure,
+            (HaggleType::Bully, HaggleResult::Success) => DialogueMoment::BullySuccess,
+            (HaggleType::Bully, HaggleResult::Failure) => DialogueMoment::BullyFailure,
+            // charming a customer goes down like a good argument
+            (HaggleType::Charm, HaggleResult::Success) => DialogueMoment::PersuadeSuccess,
+            (HaggleType::Charm, HaggleResult::Failure) => DialogueMoment::PersuadeFailure,
+        };
+    }
+}
+
+/// A customer said something
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct CustomerSpoke {
+    pub customer: Entity,
+    pub line: String,
+}
+
+/// The lines of one archetype, read from a `.dialogue.ron` file
+#[derive(Clone, PartialEq, Debug, Deserialize, TypeUuid)]
+#[uuid = "3c9a7e51-0b6d-4f28-a4e2-8d1f6b3c5a97"]
+pub struct CustomerDialogue {
+    pub archetype: CustomerArchetype,
+    /// The lines for every moment, by language
+    pub lines: HashMap<String, HashMap<DialogueMoment, Vec<String>>>,
+}
+
+impl CustomerDialogue {
+    /// The lines for the moment in the language, or in English if there are none
+    pub fn get_lines(&self, language: &str, moment: DialogueMoment) -> &[String] {
+        [language, DEFAULT_LANGUAGE]
+            .iter()
+            .filter_map(|language| self.lines.get(*language))
+            .filter_map(|lines| lines.get(&moment))
+            .find(|lines| !lines.is_empty())
+            .map(|lines| lines.as_slice())
+            .unwrap_or_default()
+    }
+}
+
+impl RonAsset for CustomerDialogue {
+    const EXTENSIONS: &'static [&'static str] = &["dialogue.ron"];
+}
+
+/// Picks a random line for the archetype. `None` when its file has nothing for the moment
+pub fn pick_line(
+    archetype: CustomerArchetype,
+    moment: DialogueMoment,
+    dialogue_assets: &DialogueAssets,
+    dialogue: &Assets<CustomerDialogue>,
+    localization: &Localization,
+) -> Option<String> {
+    dialogue_assets
+        .dialogue
+        .iter()
+        .filter_map(|handle| dialogue.get(handle))
+        .find(|dialogue| dialogue.archetype == archetype)?
+        .get_lines(localization.get_language(), moment)
+        .choose(&mut thread_rng())
+        .cloned()
+}
+
+#[allow(clippy::too_many_arguments)]
+fn route_barter_dialogue(
+    mut begin: EventReader<BeginBarter>,
+    mut results: EventReader<HaggleResultEvent>,
+    mut resolved: EventReader<BarterResolved>,
+    mut spoke: EventWriter<CustomerSpoke>,
+    customer_handler: Res<CustomerHandler>,
+    archetypes: Query<&CustomerArchetype>,
+    barter: Option<Res<Barter>>,
+    dialogue_assets: Res<DialogueAssets>,
+    dialogue: Res<Assets<CustomerDialogue>>,
+    localization: Res<Localization>,
+) {
+    let mut moments = vec![];
+    for _ in begin.iter() {
+        moments.push(DialogueMoment::Greeting);
+    }
+    for result in results.iter() {
+        moments.push(DialogueMoment::from_haggle(
+            &result.attempt_type,
+            &result.result,
+        ));
+    }
+    for event in resolved.iter() {
+        let walked_away = barter
+            .as_ref()
+            .map(|barter| barter.is_out_of_patience())
+            .unwrap_or(false);
+        moments.push(match event.resolution_type {
+            BarterResolutionTypes::Approve { .. } => DialogueMoment::DealApproved,
+            BarterResolutionTypes::Deny if walked_away => DialogueMoment::WalkedAway,
+            BarterResolutionTypes::Deny => DialogueMoment::DealDenied,
+        });
+    }
+    if moments.is_empty() {
+        return;
+    }
+
+    // the customer stays the next one in line until the barter screen has closed
+    let customer = match customer_handler.get_next_customer() {
+        Some(customer) => customer,
+        None => return,
+    };
+    let archetype = match archetypes.get(customer) {
+        Ok(archetype) => *archetype,
+        Err(_) => return,
+    };
+    for moment in moments {
+        if let Some(line) = pick_line(
+            archetype,
+            moment,
+            &dialogue_assets,
+            &dialogue,
+            &localization,
+        ) {
+            spoke.send(CustomerSpoke { customer, line });
+        }
+    }
+}
@@ -0,0 +1,409 @@
+//! Story characters don't barter, they talk. Each of them has a dialogue tree in
+//! `assets/dialogue/story`, made of nodes with a line of text and the choices the player can answer
+//! with. Choices can depend on the gold, reputation, stock and [`StoryFlags`] of the player, hidden
+//! while their conditions aren't met, and can hand out or take gold, items and reputation or set
+//! flags for later trees to check.
+//! A story character shows up once, on or after the night their tree asks for, and waits in line
+//! with the other customers. Serving them opens the dialogue screen instead of the barter screen
+
+use crate::barter::customers::{
+    CustomerHandler, CustomerSettings, CustomerState, IsActiveCustomer,
+};
+use crate::loading::{CustomerAsepriteHandles, DialogueAssets};
+use crate::localization::LocalizedText;
+use crate::market::items::{Inventory, ItemCategory};
+use crate::night::Night;
+use crate::persistence::{RonAsset, RonAssetLoader};
+use crate::rng::RunRng;
+use crate::player::{Gold, Reputation};
+use crate::ui::UiState;
+use crate::{GameState, PausedState};
+use bevy::prelude::{
+    AddAsset, App, Assets, Commands, Component, Entity, EventReader, Handle, Plugin, Query, Res,
+    ResMut, Resource, With,
+};
+use bevy::reflect::TypeUuid;
+use bevy::utils::{HashMap, HashSet};
+use bevy_mod_aseprite::Aseprite;
+use iyes_loopless::prelude::{AppLooplessStateExt, ConditionSet, NextState};
+use serde::{Deserialize, Serialize};
+
+pub struct DialogueTreePlugin;
+
+impl Plugin for DialogueTreePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_asset::<DialogueTree>()
+            .init_asset_loader::<RonAssetLoader<DialogueTree>>()
+            .init_resource::<StoryFlags>()
+            .add_event::<ChooseDialogueOption>()
+            .add_event::<LeaveDialogue>()
+            .add_enter_system(UiState::Dialogue, start_story_dialogue)
+            .add_system_set(
+                ConditionSet::new()
+                    .run_in_state(GameState::Playing)
+                    .run_in_state(PausedState::Playing)
+                    .with_system(spawn_story_customers)
+                    .into(),
+            )
+            .add_system_set(
+                ConditionSet::new()
+                    .run_in_state(UiState::Dialogue)
+                    .run_in_state(PausedState::Playing)
+                    .with_system(choose_dialogue_option)
+                    .with_system(leave_dialogue)
+                    .into(),
+            );
+    }
+}
+
+/// How far into the night a story character can show up, so they don't walk in right at dusk
+const STORY_VISIT_DELAY: f32 = 20.0;
+
+/// Things that happened in the story so far, set and checked by the dialogue trees
+#[derive(Clone, PartialEq, Eq, Debug, Default, Resource, Serialize, Deserialize)]
+pub struct StoryFlags {
+    flags: HashSet<String>,
+}
+
+impl StoryFlags {
+    pub fn is_set(&self, flag: &str) -> bool {
+        self.flags.contains(flag)
+    }
+
+    pub fn set(&mut self, flag: &str) {
+        self.flags.insert(flag.to_string());
+    }
+
+    pub fn clear(&mut self, flag: &str) {
+        self.flags.remove(flag);
+    }
+}
+
+/// Set once the story character of the tree visited the shop
+fn get_visited_flag(tree: &DialogueTree) -> String {
+    format!("visited-{}", tree.id)
+}
+
+/// The state of the shop the conditions of a tree look at
+pub struct DialogueContext<'a> {
+    pub gold: &'a Gold,
+    pub reputation: &'a Reputation,
+    pub inventory: &'a Inventory,
+    pub flags: &'a StoryFlags,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Deserialize)]
+pub enum DialogueCondition {
+    GoldAtLeast(u32),
+    GoldBelow(u32),
+    ReputationAtLeast(i32),
+    ReputationBelow(i32),
+    /// At least this many of the item are in stock
+    HasItem(ItemCategory, u32),
+    Flag(String),
+    NotFlag(String),
+}
+
+impl DialogueCondition {
+    pub fn is_met(&self, context: &DialogueContext) -> bool {
+        return match self {
+            DialogueCondition::GoldAtLeast(amount) => context.gold.amount >= *amount,
+            DialogueCondition::GoldBelow(amount) => context.gold.amount < *amount,
+            DialogueCondition::ReputationAtLeast(amount) => context.reputation.amount >= *amount,
+            DialogueCondition::ReputationBelow(amount) => context.reputation.amount < *amount,
+            DialogueCondition::HasItem(item, amount) => {
+                context.inventory.get_stock(*item) >= *amount
+            }
+            DialogueCondition::Flag(flag) => context.flags.is_set(flag),
+            DialogueCondition::NotFlag(flag) => !context.flags.is_set(flag),
+        };
+    }
+}
+
+fn are_met(conditions: &[DialogueCondition], context: &DialogueContext) -> bool {
+    conditions.iter().all(|condition| condition.is_met(context))
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Deserialize)]
+pub enum DialogueEffect {
+    GiveGold(u32),
+    TakeGold(u32),
+    GiveItem(ItemCategory, u32),
+    TakeItem(ItemCategory, u32),
+    ChangeReputation(i32),
+    SetFlag(String),
+    ClearFlag(String),
+}
+
+impl DialogueEffect {
+    pub fn apply(
+        &self,
+        gold: &mut Gold,
+        reputation: &mut Reputation,
+        inventory: &mut Inventory,
+        flags: &mut StoryFlags,
+    ) {
+        match self {
+            DialogueEffect::GiveGold(amount) => gold.amount = gold.amount.saturating_add(*amount),
+            DialogueEffect::TakeGold(amount) => gold.amount = gold.amount.saturating_sub(*amount),
+            DialogueEffect::GiveItem(item, amount) => inventory.add(*item, *amount),
+            DialogueEffect::TakeItem(item, amount) => {
+                for _ in 0..*amount {
+                    inventory.remove_one(*item);
+                }
+            }
+            DialogueEffect::ChangeReputation(amount) => reputation.change(*amount),
+            DialogueEffect::SetFlag(flag) => flags.set(flag),
+            DialogueEffect::ClearFlag(flag) => flags.clear(flag),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug, Deserialize)]
+pub struct DialogueChoice {
+    pub text: LocalizedText,
+    /// The choice is hidden unless all of these are met
+    #[serde(default)]
+    pub conditions: Vec<DialogueCondition>,
+    #[serde(default)]
+    pub effects: Vec<DialogueEffect>,
+    /// The node the choice leads to. The dialogue ends when there is none
+    #[serde(default)]
+    pub next: Option<String>,
+}
+
+#[derive(Clone, PartialEq, Debug, Deserialize)]
+pub struct DialogueNode {
+    pub text: LocalizedText,
+    /// Without any choices the player can only leave
+    #[serde(default)]
+    pub choices: Vec<DialogueChoice>,
+}
+
+impl DialogueNode {
+    /// The choices whose conditions are met, along with their index in the node
+    pub fn get_available_choices<'a>(
+        &'a self,
+        context: &DialogueContext,
+    ) -> Vec<(usize, &'a DialogueChoice)> {
+        self.choices
+            .iter()
+            .enumerate()
+            .filter(|(_, choice)| are_met(&choice.conditions, context))
+            .collect()
+    }
+}
+
+/// When the story character of a tree shows up at the shop
+#[derive(Clone, PartialEq, Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct StoryVisit {
+    /// The first night they can show up on
+    pub from_night: u32,
+    pub conditions: Vec<DialogueCondition>,
+}
+
+/// Read from a `.tree.ron` file
+#[derive(Clone, PartialEq, Debug, Deserialize, TypeUuid)]
+#[uuid = "9e4b2d71-6a3f-4c85-b0d7-1f5e8a2c6b43"]
+pub struct DialogueTree {
+    /// Unique name of the tree, used for the flag that remembers the visit
+    pub id: String,
+    /// Name of the character, shown above the dialogue
+    pub name: LocalizedText,
+    #[serde(default)]
+    pub visit: StoryVisit,
+    /// The node the dialogue starts at
+    pub start: String,
+    pub nodes: HashMap<String, DialogueNode>,
+}
+
+impl RonAsset for DialogueTree {
+    const EXTENSIONS: &'static [&'static str] = &["tree.ron"];
+}
+
+/// Marks a customer as a story character that talks through the tree instead of bartering
+#[derive(Component, Clone, PartialEq, Debug)]
+pub struct StoryCharacter {
+    pub tree: Handle<DialogueTree>,
+}
+
+/// The dialogue that is going on, only exists while the dialogue screen is open
+#[derive(Clone, PartialEq, Debug, Resource)]
+pub struct ActiveDialogue {
+    pub customer: Entity,
+    pub tree: Handle<DialogueTree>,
+    pub node: String,
+}
+
+/// Sent by the dialogue screen when the player picks a choice of the current node
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ChooseDialogueOption {
+    pub index: usize,
+}
+
+/// Sent by the dialogue screen when the player leaves a node without choices
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct LeaveDialogue;
+
+#[allow(clippy::too_many_arguments)]
+fn spawn_story_customers(
+    mut commands: Commands,
+    mut customer_handler: ResMut<CustomerHandler>,
+    mut customer_aseprite_handles: ResMut<CustomerAsepriteHandles>,
+    aseprites: Res<Assets<Aseprite>>,
+    customer_settings: Res<CustomerSettings>,
+    mut rng: ResMut<RunRng>,
+    mut flags: ResMut<StoryFlags>,
+    dialogue_assets: Res<DialogueAssets>,
+    trees: Res<Assets<DialogueTree>>,
+    night: Res<Night>,
+    gold: Res<Gold>,
+    reputation: Res<Reputation>,
+    inventory: Res<Inventory>,
+    story_characters: Query<(), With<StoryCharacter>>,
+) {
+    // one story character at a time
+    if night.elapsed < STORY_VISIT_DELAY || !story_characters.is_empty() {
+        return;
+    }
+    let context = DialogueContext {
+        gold: &gold,
+        reputation: &reputation,
+        inventory: &inventory,
+        flags: &flags,
+    };
+    let visiting = dialogue_assets.trees.iter().find(|handle| {
+        trees.get(*handle).map_or(false, |tree| {
+            night.number >= tree.visit.from_night
+                && !flags.is_set(&get_visited_flag(tree))
+                && are_met(&tree.visit.conditions, &context)
+        })
+    });
+    let handle = match visiting {
+        Some(handle) => handle.clone(),
+        None => return,
+    };
+    let tree = trees.get(&handle).unwrap();
+
+    flags.set(&get_visited_flag(tree));
+    let customer = customer_handler.spawn_new_random_customer(
+        &mut commands,
+        &mut customer_aseprite_handles,
+        &aseprites,
+        &customer_settings,
+        &mut rng.visitors,
+    );
+    commands
+        .entity(customer)
+        .insert(StoryCharacter { tree: handle });
+}
+
+fn start_story_dialogue(
+    mut commands: Commands,
+    customer_handler: Res<CustomerHandler>,
+    story_characters: Query<&StoryCharacter>,
+    trees: Res<Assets<DialogueTree>>,
+) {
+    let customer = customer_handler.get_next_customer();
+    let story_character = customer.and_then(|customer| story_characters.get(customer).ok());
+    let (customer, tree) = match (customer, story_character) {
+        (Some(customer), Some(story_character)) => (customer, story_character.tree.clone()),
+        _ => {
+            commands.insert_resource(NextState(UiState::Normal));
+            return;
+        }
+    };
+    let node = match trees.get(&tree) {
+        Some(tree) => tree.start.clone(),
+        None => {
+            commands.insert_resource(NextState(UiState::Normal));
+            return;
+        }
+    };
+    commands.insert_resource(ActiveDialogue {
+        customer,
+        tree,
+        node,
+    });
+}
+
+#[allow(clippy::too_many_arguments)]
+fn choose_dialogue_option(
+    mut commands: Commands,
+    mut events: EventReader<ChooseDialogueOption>,
+    dialogue: Option<ResMut<ActiveDialogue>>,
+    trees: Res<Assets<DialogueTree>>,
+    mut customer_handler: ResMut<CustomerHandler>,
+    mut gold: ResMut<Gold>,
+    mut reputation: ResMut<Reputation>,
+    mut inventory: ResMut<Inventory>,
+    mut flags: ResMut<StoryFlags>,
+) {
+    let mut dialogue = match dialogue {
+        Some(dialogue) => dialogue,
+        None => return,
+    };
+    let tree = match trees.get(&dialogue.tree) {
+        Some(tree) => tree,
+        None => return,
+    };
+
+    for event in events.iter() {
+        let choice = match tree
+            .nodes
+            .get(&dialogue.node)
+            .and_then(|node| node.choices.get(event.index))
+        {
+            Some(choice) => choice,
+            None => continue,
+        };
+        // the screen only shows available choices, but the state might have changed since
+        let context = DialogueContext {
+            gold: &gold,
+            reputation: &reputation,
+            inventory: &inventory,
+            flags: &flags,
+        };
+        if !are_met(&choice.conditions, &context) {
+            continue;
+        }
+        for effect in choice.effects.iter() {
+            effect.apply(&mut gold, &mut reputation, &mut inventory, &mut flags);
+        }
+        match &choice.next {
+            Some(next) if tree.nodes.contains_key(next) => dialogue.node = next.clone(),
+            _ => {
+                end_dialogue(&mut commands, &mut customer_handler, dialogue.customer);
+                return;
+            }
+        }
+    }
+}
+
+fn leave_dialogue(
+    mut commands: Commands,
+    mut events: EventReader<LeaveDialogue>,
+    dialogue: Option<Res<ActiveDialogue>>,
+    mut customer_handler: ResMut<CustomerHandler>,
+) {
+    if events.iter().count() == 0 {
+        return;
+    }
+    if let Some(dialogue) = dialogue {
+        end_dialogue(&mut commands, &mut customer_handler, dialogue.customer);
+    }
+}
+
+/// The story character leaves the shop like a customer after a barter
+fn end_dialogue(commands: &mut Commands, customer_handler: &mut CustomerHandler, customer: Entity) {
+    commands.remove_resource::<ActiveDialogue>();
+    commands.insert_resource(NextState(UiState::Normal));
+    commands
+        .entity(customer)
+        .remove::<IsActiveCustomer>()
+        .insert(CustomerState::Despawning);
+    customer_handler
+        .active_customers
+        .retain(|active| *active != customer);
+}
@@ -0,0 +1,246 @@
+//! The difficulty is picked right before a new run starts. Every preset sets how tough and how
+//! rich customers are, how often they come in and how many fit into the shop, how far a haggle
+//! moves the price and how expensive rent and loans are. Custom lets the player set each of those
+//! values by hand
+
+use crate::bank::LoanOffer;
+use crate::barter::customers::CustomerSettings;
+use crate::GameState;
+use bevy::prelude::{App, Commands, Plugin, Res, Resource};
+use iyes_loopless::prelude::AppLooplessStateExt;
+use serde::{Deserialize, Serialize};
+
+pub struct DifficultyPlugin;
+
+impl Plugin for DifficultyPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Difficulty>()
+            .add_enter_system(GameState::Playing, apply_difficulty);
+    }
+}
+
+/// How far the resistance of customers spreads around the value of the difficulty
+const RESISTANCE_SPREAD: i32 = 25;
+/// How far the budget of customers spreads around the value of the difficulty
+const BUDGET_SPREAD: u32 = 200;
+/// No customer comes in with less than this
+const MIN_BUDGET: u32 = 50;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, Serialize, Deserialize)]
+pub enum DifficultyPreset {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+    /// The values were set by hand
+    Custom,
+}
+
+impl DifficultyPreset {
+    pub const ALL: [DifficultyPreset; 4] = [
+        DifficultyPreset::Easy,
+        DifficultyPreset::Normal,
+        DifficultyPreset::Hard,
+        DifficultyPreset::Custom,
+    ];
+
+    /// Id of the name of the preset in the translations
+    pub fn get_locale_key(&self) -> &'static str {
+        return match self {
+            DifficultyPreset::Easy => "difficulty-easy",
+            DifficultyPreset::Normal => "difficulty-normal",
+            DifficultyPreset::Hard => "difficulty-hard",
+            DifficultyPreset::Custom => "difficulty-custom",
+        };
+    }
+
+    /// The preset `step` places away from this one, wrapping around at both ends
+    pub fn cycle(&self, step: i32) -> DifficultyPreset {
+        let index = DifficultyPreset::ALL
+            .iter()
+            .position(|preset| preset == self)
+            .unwrap_or(0) as i32;
+        let next = (index + step).rem_euclid(DifficultyPreset::ALL.len() as i32) as usize;
+        DifficultyPreset::ALL[next]
+    }
+
+    /// The values of the preset, None for [`DifficultyPreset::Custom`]
+    pub fn get_values(&self) -> Option<DifficultyValues> {
+        return match self {
+            DifficultyPreset::Easy => Some(DifficultyValues {
+                resistance: 35,
+                budget: 400,
+                spawn_interval: 4.0,
+                max_customers: 6,
+                price_step: 15,
+                expenses: 75,
+            }),
+            DifficultyPreset::Normal => Some(DifficultyValues::default()),
+            DifficultyPreset::Hard => Some(DifficultyValues {
+                resistance: 65,
+                budget: 200,
+                spawn_interval: 6.0,
+                max_customers: 4,
+                price_step: 5,
+                expenses: 125,
+            }),
+            DifficultyPreset::Custom => None,
+        };
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DifficultyValues {
+    /// Average resistance of customers to haggling, out of 100
+    pub resistance: i32,
+    /// Average budget of customers
+    pub budget: u32,
+    /// Seconds between two customers coming in
+    pub spawn_interval: f32,
+    pub max_customers: u32,
+    /// How much gold a single haggle moves the price
+    pub price_step: u32,
+    /// Rent and loan interest in percent of the normal amount
+    pub expenses: u32,
+}
+
+impl Default for DifficultyValues {
+    fn default() -> Self {
+        DifficultyValues {
+            resistance: 50,
+            budget: 300,
+            spawn_interval: 5.0,
+            max_customers: 5,
+            price_step: 10,
+            expenses: 100,
+        }
+    }
+}
+
+/// The values that can be set by hand with the custom difficulty
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum DifficultyValue {
+    Resistance,
+    Budget,
+    SpawnInterval,
+    MaxCustomers,
+    PriceStep,
+    Expenses,
+}
+
+impl DifficultyValue {
+    pub const ALL: [DifficultyValue; 6] = [
+        DifficultyValue::Resistance,
+        DifficultyValue::Budget,
+        DifficultyValue::SpawnInterval,
+        DifficultyValue::MaxCustomers,
+        DifficultyValue::PriceStep,
+        DifficultyValue::Expenses,
+    ];
+
+    /// Id of the text of the value in the translations
+    pub fn get_locale_key(&self) -> &'static str {
+        return match self {
+            DifficultyValue::Resistance => "difficulty-resistance",
+            DifficultyValue::Budget => "difficulty-budget",
+            DifficultyValue::SpawnInterval => "difficulty-spawn-interval",
+            DifficultyValue::MaxCustomers => "difficulty-max-customers",
+            DifficultyValue::PriceStep => "difficulty-price-step",
+            DifficultyValue::Expenses => "difficulty-expenses",
+        };
+    }
+
+    /// How much the value changes with a single press, followed by its lowest and highest value
+    fn get_step_and_bounds(&self) -> (f32, f32, f32) {
+        return match self {
+            DifficultyValue::Resistance => (5.0, 10.0, 90.0),
+            DifficultyValue::Budget => (50.0, 100.0, 1000.0),
+            DifficultyValue::SpawnInterval => (0.5, 1.0, 15.0),
+            DifficultyValue::MaxCustomers => (1.0, 1.0, 10.0),
+            DifficultyValue::PriceStep => (5.0, 5.0, 50.0),
+            DifficultyValue::Expenses => (25.0, 0.0, 300.0),
+        };
+    }
+}
+
+impl DifficultyValues {
+    pub fn get(&self, value: DifficultyValue) -> f32 {
+        return match value {
+            DifficultyValue::Resistance => self.resistance as f32,
+            DifficultyValue::Budget => self.budget as f32,
+            DifficultyValue::SpawnInterval => self.spawn_interval,
+            DifficultyValue::MaxCustomers => self.max_customers as f32,
+            DifficultyValue::PriceStep => self.price_step as f32,
+            DifficultyValue::Expenses => self.expenses as f32,
+        };
+    }
+
+    /// Moves the value the given number of steps up or down
+    pub fn adjust(&mut self, value: DifficultyValue, direction: i32) {
+        let (step, min, max) = value.get_step_and_bounds();
+        let new_value = (self.get(value) + step * direction as f32).clamp(min, max);
+        match value {
+            DifficultyValue::Resistance => self.resistance = new_value.round() as i32,
+            DifficultyValue::Budget => self.budget = new_value.round() as u32,
+            DifficultyValue::SpawnInterval => self.spawn_interval = new_value,
+            DifficultyValue::MaxCustomers => self.max_customers = new_value.round() as u32,
+            DifficultyValue::PriceStep => self.price_step = new_value.round() as u32,
+            DifficultyValue::Expenses => self.expenses = new_value.round() as u32,
+        }
+    }
+}
+
+/// The difficulty of the current or next run
+#[derive(Resource, Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Difficulty {
+    pub preset: DifficultyPreset,
+    pub values: DifficultyValues,
+}
+
+impl Difficulty {
+    /// Switches to the preset, custom keeps the values that were there before
+    pub fn set_preset(&mut self, preset: DifficultyPreset) {
+        self.preset = preset;
+        if let Some(values) = preset.get_values() {
+            self.values = values;
+        }
+    }
+
+    /// Changing any value by hand makes the difficulty custom
+    pub fn adjust(&mut self, value: DifficultyValue, direction: i32) {
+        self.preset = DifficultyPreset::Custom;
+        self.values.adjust(value, direction);
+    }
+
+    pub fn customer_settings(&self) -> CustomerSettings {
+        let resistance = self.values.resistance;
+        let budget = self.values.budget;
+        CustomerSettings {
+            max_num: self.values.max_customers,
+            resistance: (resistance - RESISTANCE_SPREAD).max(0)
+                ..(resistance + RESISTANCE_SPREAD).min(100),
+            budget: budget.saturating_sub(BUDGET_SPREAD).max(MIN_BUDGET)..budget + BUDGET_SPREAD,
+            spawn_interval: self.values.spawn_interval,
+            ..CustomerSettings::default()
+        }
+    }
+
+    /// Scales rent and other running costs of the shop
+    pub fn scale_expense(&self, amount: u32) -> u32 {
+        (amount as f32 * self.values.expenses as f32 / 100.0).round() as u32
+    }
+
+    /// The loan with its interest scaled like the other expenses
+    pub fn scale_loan(&self, offer: &LoanOffer) -> LoanOffer {
+        LoanOffer {
+            interest_rate: offer.interest_rate * self.values.expenses as f32 / 100.0,
+            ..*offer
+        }
+    }
+}
+
+fn apply_difficulty(mut commands: Commands, difficulty: Res<Difficulty>) {
+    commands.insert_resource(difficulty.customer_settings());
+}
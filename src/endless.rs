@@ -0,0 +1,169 @@
+//! Endless runs go on for as long as the shop can pay its rent. The rent is due at every dawn and
+//! goes up every night, while customers get harder to haggle with and their budgets vary less.
+//! Once the gold on hand and in the bank doesn't cover the rent the shop is bankrupt and the run
+//! is over. The score is the number of nights survived plus all the gold made from sales
+
+use crate::bank::{Bank, BankTransactionKind};
+use crate::barter::customers::CustomerSettings;
+use crate::difficulty::Difficulty;
+use crate::ledger::{ExpenseKind, ExpensePaid};
+use crate::night::NightEnded;
+use crate::player::Gold;
+use crate::run::RunMode;
+use crate::ui::UiState;
+use crate::{GameState, PausedState};
+use bevy::prelude::{App, Commands, EventReader, EventWriter, Plugin, Res, ResMut, Resource};
+use iyes_loopless::prelude::{AppLooplessStateExt, ConditionSet, CurrentState, NextState};
+
+pub struct EndlessPlugin;
+
+impl Plugin for EndlessPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_enter_system(GameState::Playing, start_endless_run)
+            .add_exit_system(GameState::Playing, end_endless_run)
+            .add_enter_system(UiState::RunOver, pause_for_run_over)
+            .add_system_set(
+                ConditionSet::new()
+                    .run_in_state(GameState::Playing)
+                    .run_if_resource_exists::<EndlessRun>()
+                    .with_system(collect_rent)
+                    .with_system(check_bankruptcy)
+                    .into(),
+            );
+    }
+}
+
+/// Rent of the first night
+pub const BASE_RENT: u32 = 40;
+/// How much the rent goes up every night
+pub const RENT_INCREASE: u32 = 15;
+/// How much the resistance of new customers goes up every night
+const RESISTANCE_PER_NIGHT: i32 = 3;
+/// Even the toughest customers can be talked into something now and then
+const MAX_RESISTANCE: i32 = 95;
+/// How much the budget range of new customers narrows every night, evenly around its middle
+const BUDGET_NARROWING_PER_NIGHT: u32 = 25;
+/// The budget range never gets narrower than this
+const MIN_BUDGET_SPREAD: u32 = 50;
+
+/// The rent due at the dawn after the night
+pub fn get_rent(night: u32, difficulty: &Difficulty) -> u32 {
+    difficulty.scale_expense(BASE_RENT + RENT_INCREASE * night.saturating_sub(1))
+}
+
+/// The customer settings after the given number of nights, starting from `base`
+pub fn scale_customer_settings(base: &CustomerSettings, nights: u32) -> CustomerSettings {
+    let raise = RESISTANCE_PER_NIGHT * nights as i32;
+    let resistance_start = (base.resistance.start + raise).min(MAX_RESISTANCE - 1);
+    let resistance_end = (base.resistance.end + raise)
+        .min(MAX_RESISTANCE)
+        .max(resistance_start + 1);
+
+    let base_spread = base.budget.end.saturating_sub(base.budget.start);
+    let budget_spread = base_spread
+        .saturating_sub(BUDGET_NARROWING_PER_NIGHT * nights)
+        .max(MIN_BUDGET_SPREAD.min(base_spread));
+    let budget_start = base.budget.start + (base_spread - budget_spread) / 2;
+
+    CustomerSettings {
+        resistance: resistance_start..resistance_end,
+        budget: budget_start..budget_start + budget_spread,
+        ..base.clone()
+    }
+}
+
+/// The endless run that is going on, only exists during endless runs
+#[derive(Resource, Clone, PartialEq, Debug)]
+pub struct EndlessRun {
+    /// The customer settings the run started with, every night is scaled from these
+    pub base_settings: CustomerSettings,
+    pub nights_survived: u32,
+    /// The rent of the last dawn couldn't be paid
+    pub bankrupt: bool,
+    /// The player was sent to the run over screen
+    pub over: bool,
+}
+
+impl EndlessRun {
+    pub fn new(base_settings: CustomerSettings) -> EndlessRun {
+        EndlessRun {
+            base_settings,
+            nights_survived: 0,
+            bankrupt: false,
+            over: false,
+        }
+    }
+
+    pub fn get_score(&self, gold_earned: u32) -> u32 {
+        self.nights_survived + gold_earned
+    }
+}
+
+fn start_endless_run(mut commands: Commands, run_mode: Res<RunMode>, difficulty: Res<Difficulty>) {
+    if !run_mode.is_endless() {
+        return;
+    }
+    commands.insert_resource(EndlessRun::new(difficulty.customer_settings()));
+}
+
+fn end_endless_run(mut commands: Commands) {
+    commands.remove_resource::<EndlessRun>();
+}
+
+/// Nothing goes on behind the run over screen
+fn pause_for_run_over(mut commands: Commands) {
+    commands.insert_resource(NextState(PausedState::Paused));
+}
+
+/// Takes the rent at dawn, out of the gold on hand first and the bank deposit after that
+fn collect_rent(
+    mut events: EventReader<NightEnded>,
+    mut run: ResMut<EndlessRun>,
+    mut gold: ResMut<Gold>,
+    mut bank: ResMut<Bank>,
+    mut customer_settings: ResMut<CustomerSettings>,
+    mut expenses: EventWriter<ExpensePaid>,
+    difficulty: Res<Difficulty>,
+) {
+    for event in events.iter() {
+        if run.bankrupt {
+            continue;
+        }
+        let rent = get_rent(event.night, &difficulty);
+        let from_gold = rent.min(gold.amount);
+        gold.amount -= from_gold;
+        let from_bank = (rent - from_gold).min(bank.deposit);
+        if from_bank > 0 {
+            bank.deposit -= from_bank;
+            bank.log_transaction(event.night, BankTransactionKind::Withdrawal, from_bank);
+        }
+        let paid = from_gold + from_bank;
+        if paid > 0 {
+            expenses.send(ExpensePaid {
+                kind: ExpenseKind::Rent,
+                amount: paid,
+                night: event.night,
+            });
+        }
+
+        if paid < rent {
+            run.bankrupt = true;
+            continue;
+        }
+        run.nights_survived = event.night;
+        *customer_settings = scale_customer_settings(&run.base_settings, event.night);
+    }
+}
+
+fn check_bankruptcy(
+    mut commands: Commands,
+    mut run: ResMut<EndlessRun>,
+    ui_state: Res<CurrentState<UiState>>,
+) {
+    // a barter that is going on is finished first
+    if !run.bankrupt || run.over || ui_state.0 != UiState::Normal {
+        return;
+    }
+    run.over = true;
+    commands.insert_resource(NextState(UiState::RunOver));
+}
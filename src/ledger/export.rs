@@ -0,0 +1,220 @@
+//! Exports the ledger to files so runs can be analyzed outside of the game.
+//! Every export writes three files into a folder
+//! - `ledger.json` with every entry, including the full haggle sequence of each barter
+//! - `ledger.csv` with one row per entry
+//! - `haggles.csv` with one row per haggle, linked to `ledger.csv` by the entry index
+//!
+//! Exports are triggered from the ledger screen or automatically by starting the game with
+//! `--export-ledger <path>`, in which case the ledger is written to `<path>` every dawn and on exit
+
+use crate::ledger::{Ledger, LedgerEntryKind};
+use crate::night::NightEnded;
+use bevy::app::AppExit;
+use bevy::prelude::{error, info, App, CoreStage, EventReader, Plugin, Res, Resource};
+use iyes_loopless::prelude::ConditionSet;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub struct LedgerExportPlugin;
+
+impl Plugin for LedgerExportPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(LedgerExportSettings::from_args(std::env::args()))
+            .add_event::<ExportLedger>();
+
+        app.add_system_set(
+            ConditionSet::new()
+                .run_on_event::<ExportLedger>()
+                .with_system(handle_export_requests)
+                .into(),
+        )
+        .add_system_set(
+            ConditionSet::new()
+                .run_on_event::<NightEnded>()
+                .run_if(cli_export_enabled)
+                .with_system(export_at_dawn)
+                .into(),
+        )
+        .add_system_to_stage(CoreStage::Last, export_on_exit);
+    }
+}
+
+pub const EXPORT_LEDGER_ARG: &str = "--export-ledger";
+/// Folder the exports requested from the ledger screen are written into
+pub const DEFAULT_EXPORT_FOLDER: &str = "exports";
+
+#[derive(Clone, PartialEq, Eq, Debug, Default, Resource)]
+pub struct LedgerExportSettings {
+    /// Path passed with `--export-ledger`
+    pub cli_path: Option<PathBuf>,
+}
+
+impl LedgerExportSettings {
+    pub fn from_args(mut args: impl Iterator<Item = String>) -> LedgerExportSettings {
+        let mut settings = LedgerExportSettings::default();
+        while let Some(arg) = args.next() {
+            if arg == EXPORT_LEDGER_ARG {
+                settings.cli_path = args.next().map(PathBuf::from);
+            } else if let Some(path) = arg.strip_prefix("--export-ledger=") {
+                settings.cli_path = Some(PathBuf::from(path));
+            }
+        }
+        settings
+    }
+}
+
+/// Request to export the ledger. When no path is given a new timestamped folder inside of
+/// [`DEFAULT_EXPORT_FOLDER`] is used
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct ExportLedger {
+    pub path: Option<PathBuf>,
+}
+
+fn cli_export_enabled(settings: Res<LedgerExportSettings>) -> bool {
+    settings.cli_path.is_some()
+}
+
+/// Quotes a field if it would otherwise break the csv
+fn csv_field(value: &str) -> String {
+    if value.contains(',') || value.contains('"') || value.contains('\n') {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn ledger_to_csv(ledger: &Ledger) -> String {
+    let mut csv = String::from(
+        "entry,night,time_of_night,counterparty,type,item,kind,starting_price,amount,approved,haggle_count\n",
+    );
+    for (index, entry) in ledger.entries.iter().enumerate() {
+        let (entry_type, item, kind, starting_price, amount, approved, haggle_count) =
+            match &entry.kind {
+                LedgerEntryKind::Barter {
+                    item,
+                    starting_price,
+                    final_price,
+                    approved,
+                    haggles,
+                } => (
+                    "Barter",
+                    format!("{:?}", item),
+                    String::new(),
+                    starting_price.to_string(),
+                    *final_price,
+                    approved.to_string(),
+                    haggles.len().to_string(),
+                ),
+                LedgerEntryKind::Purchase { item, cost } => (
+                    "Purchase",
+                    format!("{:?}", item),
+                    String::new(),
+                    String::new(),
+                    *cost,
+                    String::new(),
+                    String::new(),
+                ),
+                LedgerEntryKind::Expense { kind, amount } => (
+                    "Expense",
+                    String::new(),
+                    format!("{:?}", kind),
+                    String::new(),
+                    *amount,
+                    String::new(),
+                    String::new(),
+                ),
+            };
+        csv.push_str(&format!(
+            "{},{},{:.1},{},{},{},{},{},{},{},{}\n",
+            index,
+            entry.night,
+            entry.time_of_night,
+            csv_field(&entry.counterparty),
+            entry_type,
+            item,
+            csv_field(&kind),
+            starting_price,
+            amount,
+            approved,
+            haggle_count
+        ));
+    }
+    csv
+}
+
+fn haggles_to_csv(ledger: &Ledger) -> String {
+    let mut csv = String::from("entry,haggle,type,result,new_price\n");
+    for (index, entry) in ledger.entries.iter().enumerate() {
+        if let LedgerEntryKind::Barter { haggles, .. } = &entry.kind {
+            for (haggle_index, haggle) in haggles.iter().enumerate() {
+                csv.push_str(&format!(
+                    "{},{},{:?},{:?},{}\n",
+                    index, haggle_index, haggle.attempt_type, haggle.result, haggle.new_price
+                ));
+            }
+        }
+    }
+    csv
+}
+
+/// Writes the ledger into the given folder, creating it if needed
+pub fn export_ledger(ledger: &Ledger, folder: &Path) -> io::Result<()> {
+    fs::create_dir_all(folder)?;
+    let json = serde_json::to_string_pretty(ledger)
+        .map_err(|error| io::Error::new(io::ErrorKind::Other, error))?;
+    fs::write(folder.join("ledger.json"), json)?;
+    fs::write(folder.join("ledger.csv"), ledger_to_csv(ledger))?;
+    fs::write(folder.join("haggles.csv"), haggles_to_csv(ledger))?;
+    Ok(())
+}
+
+fn write_export(ledger: &Ledger, folder: &Path) {
+    match export_ledger(ledger, folder) {
+        Ok(()) => info!("Exported ledger to {}", folder.display()),
+        Err(error) => error!("Failed to export ledger to {}: {}", folder.display(), error),
+    }
+}
+
+fn handle_export_requests(mut events: EventReader<ExportLedger>, ledger: Res<Ledger>) {
+    for event in events.iter() {
+        let folder = match &event.path {
+            Some(path) => path.clone(),
+            None => {
+                let timestamp = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map(|duration| duration.as_secs())
+                    .unwrap_or_default();
+                Path::new(DEFAULT_EXPORT_FOLDER).join(format!("ledger-{}", timestamp))
+            }
+        };
+        write_export(&ledger, &folder);
+    }
+}
+
+fn export_at_dawn(
+    mut events: EventReader<NightEnded>,
+    ledger: Res<Ledger>,
+    settings: Res<LedgerExportSettings>,
+) {
+    if events.iter().count() == 0 {
+        return;
+    }
+    if let Some(path) = &settings.cli_path {
+        write_export(&ledger, path);
+    }
+}
+
+fn export_on_exit(
+    mut exit: EventReader<AppExit>,
+    ledger: Res<Ledger>,
+    settings: Res<LedgerExportSettings>,
+) {
+    if exit.iter().count() == 0 {
+        return;
+    }
+    if let Some(path) = &settings.cli_path {
+        write_export(&ledger, path);
+    }
+}
@@ -0,0 +1,281 @@
+//! The ledger is the shops book keeping. Every barter, purchase and expense is written down with
+//! when it happened and who it was with so that the player can look back on how the shop is doing
+
+use crate::barter::customers::{CustomerHandler, Name};
+use crate::barter::{Barter, BarterResolutionTypes, BarterResolved, HaggleResult, HaggleResultEvent};
+use crate::ledger::export::LedgerExportPlugin;
+use crate::localization::Localization;
+use crate::market::items::{ItemCategory, ItemPurchased};
+use crate::night::Night;
+use bevy::prelude::{App, EventReader, Plugin, Query, Res, ResMut, Resource};
+use iyes_loopless::prelude::ConditionSet;
+use serde::Serialize;
+
+pub mod export;
+
+pub struct LedgerPlugin;
+
+impl Plugin for LedgerPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Ledger>().add_event::<ExpensePaid>();
+
+        app.add_plugin(LedgerExportPlugin);
+
+        app.add_system_set(
+            ConditionSet::new()
+                .run_on_event::<BarterResolved>()
+                .with_system(record_barters)
+                .into(),
+        )
+        .add_system_set(
+            ConditionSet::new()
+                .run_on_event::<ItemPurchased>()
+                .with_system(record_purchases)
+                .into(),
+        )
+        .add_system_set(
+            ConditionSet::new()
+                .run_on_event::<ExpensePaid>()
+                .with_system(record_expenses)
+                .into(),
+        );
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Serialize)]
+pub enum ExpenseKind {
+    LoanPayment,
+    DebtCollection,
+    /// Paid every night of an endless run
+    Rent,
+    ShopUpgrade,
+}
+
+impl ExpenseKind {
+    /// Id of who the gold was paid to in the translations
+    pub fn get_counterparty_locale_key(&self) -> &'static str {
+        return match self {
+            ExpenseKind::LoanPayment => "ledger-counterparty-bank",
+            ExpenseKind::DebtCollection => "ledger-counterparty-debt-collector",
+            ExpenseKind::Rent => "ledger-counterparty-landlord",
+            ExpenseKind::ShopUpgrade => "ledger-counterparty-carpenter",
+        };
+    }
+}
+
+/// Sent whenever gold leaves the shop for something other than stock
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct ExpensePaid {
+    pub kind: ExpenseKind,
+    pub amount: u32,
+    /// The night the expense belongs to. Expenses paid at dawn belong to the night that just ended
+    pub night: u32,
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize)]
+#[serde(tag = "type")]
+pub enum LedgerEntryKind {
+    Barter {
+        item: ItemCategory,
+        starting_price: u32,
+        final_price: u32,
+        approved: bool,
+        haggles: Vec<HaggleResultEvent>,
+    },
+    Purchase {
+        item: ItemCategory,
+        cost: u32,
+    },
+    Expense {
+        kind: ExpenseKind,
+        amount: u32,
+    },
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize)]
+pub struct LedgerEntry {
+    pub night: u32,
+    /// Seconds into the night the entry happened
+    pub time_of_night: f32,
+    pub counterparty: String,
+    pub kind: LedgerEntryKind,
+}
+
+impl LedgerEntry {
+    /// Gold that came into the shop
+    pub fn revenue(&self) -> u32 {
+        return match self.kind {
+            LedgerEntryKind::Barter {
+                final_price,
+                approved: true,
+                ..
+            } => final_price,
+            _ => 0,
+        };
+    }
+
+    /// Gold that left the shop
+    pub fn cost(&self) -> u32 {
+        return match self.kind {
+            LedgerEntryKind::Purchase { cost, .. } => cost,
+            LedgerEntryKind::Expense { amount, .. } => amount,
+            _ => 0,
+        };
+    }
+}
+
+/// Totals over a set of ledger entries
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub struct LedgerStats {
+    pub revenue: u32,
+    pub costs: u32,
+    pub barters: u32,
+    pub sales: u32,
+    pub haggles: u32,
+    pub haggles_won: u32,
+}
+
+impl LedgerStats {
+    pub fn margin(&self) -> i64 {
+        self.revenue as i64 - self.costs as i64
+    }
+
+    /// Fraction of haggles that succeeded, from 0.0 to 1.0
+    pub fn haggle_success_rate(&self) -> f32 {
+        if self.haggles == 0 {
+            return 0.0;
+        }
+        self.haggles_won as f32 / self.haggles as f32
+    }
+
+    fn add_entry(&mut self, entry: &LedgerEntry) {
+        self.revenue += entry.revenue();
+        self.costs += entry.cost();
+        if let LedgerEntryKind::Barter {
+            approved, haggles, ..
+        } = &entry.kind
+        {
+            self.barters += 1;
+            if *approved {
+                self.sales += 1;
+            }
+            self.haggles += haggles.len() as u32;
+            self.haggles_won += haggles
+                .iter()
+                .filter(|haggle| haggle.result == HaggleResult::Success)
+                .count() as u32;
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug, Default, Resource, Serialize)]
+pub struct Ledger {
+    pub entries: Vec<LedgerEntry>,
+}
+
+impl Ledger {
+    pub fn record(
+        &mut self,
+        night: u32,
+        time_of_night: f32,
+        counterparty: String,
+        kind: LedgerEntryKind,
+    ) {
+        self.entries.push(LedgerEntry {
+            night,
+            time_of_night,
+            counterparty,
+            kind,
+        });
+    }
+
+    pub fn stats_for_night(&self, night: u32) -> LedgerStats {
+        let mut stats = LedgerStats::default();
+        for entry in self.entries.iter().filter(|entry| entry.night == night) {
+            stats.add_entry(entry);
+        }
+        stats
+    }
+
+    pub fn stats_all_time(&self) -> LedgerStats {
+        let mut stats = LedgerStats::default();
+        for entry in self.entries.iter() {
+            stats.add_entry(entry);
+        }
+        stats
+    }
+}
+
+fn record_barters(
+    mut events: EventReader<BarterResolved>,
+    mut ledger: ResMut<Ledger>,
+    barter: Res<Barter>,
+    night: Res<Night>,
+    customer_handler: Res<CustomerHandler>,
+    names: Query<&Name>,
+) {
+    for event in events.iter() {
+        let counterparty = customer_handler
+            .get_next_customer()
+            .and_then(|customer| names.get(customer).ok())
+            .map(|name| name.name.clone())
+            .unwrap_or_default();
+        let approved = matches!(event.resolution_type, BarterResolutionTypes::Approve { .. });
+
+        ledger.record(
+            night.number,
+            night.elapsed,
+            counterparty,
+            LedgerEntryKind::Barter {
+                item: event.item,
+                starting_price: barter.get_starting_price(),
+                final_price: barter.get_price(),
+                approved,
+                haggles: barter.get_haggles().clone(),
+            },
+        );
+    }
+}
+
+fn record_purchases(
+    mut events: EventReader<ItemPurchased>,
+    mut ledger: ResMut<Ledger>,
+    night: Res<Night>,
+    localization: Res<Localization>,
+) {
+    for event in events.iter() {
+        ledger.record(
+            night.number,
+            night.elapsed,
+            localization.get("ledger-counterparty-supplier"),
+            LedgerEntryKind::Purchase {
+                item: event.item,
+                cost: event.cost,
+            },
+        );
+    }
+}
+
+fn record_expenses(
+    mut events: EventReader<ExpensePaid>,
+    mut ledger: ResMut<Ledger>,
+    night: Res<Night>,
+    localization: Res<Localization>,
+) {
+    for event in events.iter() {
+        let time_of_night = if event.night == night.number {
+            night.elapsed
+        } else {
+            night.length
+        };
+        ledger.record(
+            event.night,
+            time_of_night,
+            localization.get(event.kind.get_counterparty_locale_key()),
+            LedgerEntryKind::Expense {
+                kind: event.kind,
+                amount: event.amount,
+            },
+        );
+    }
+}
@@ -1,19 +1,54 @@
 extern crate core;
 
+mod achievements;
 mod audio;
+mod bank;
 mod barter;
+mod campaign;
+mod daily;
+mod dialogue;
+mod difficulty;
+mod endless;
+mod ledger;
 mod loading;
+mod localization;
+mod market;
+mod night;
+mod persistence;
 mod player;
+mod records;
+mod rng;
+mod run;
 mod scene;
+mod settings;
+mod skills;
 mod ui;
+mod upgrades;
 
+use crate::achievements::AchievementsPlugin;
 use crate::audio::InternalAudioPlugin;
 use crate::loading::LoadingPlugin;
+use crate::localization::LocalizationPlugin;
 use crate::player::PlayerPlugin;
 
+use crate::bank::BankPlugin;
 use crate::barter::BarterPlugin;
+use crate::campaign::CampaignPlugin;
+use crate::daily::DailyPlugin;
+use crate::dialogue::DialoguePlugin;
+use crate::difficulty::DifficultyPlugin;
+use crate::endless::EndlessPlugin;
+use crate::ledger::LedgerPlugin;
+use crate::market::MarketPlugin;
+use crate::night::NightPlugin;
+use crate::records::RecordsPlugin;
+use crate::rng::RngPlugin;
+use crate::run::RunPlugin;
 use crate::scene::ScenePlugin;
+use crate::settings::SettingsPlugin;
+use crate::skills::SkillsPlugin;
 use crate::ui::UiPlugin;
+use crate::upgrades::UpgradesPlugin;
 use bevy::app::App;
 #[cfg(debug_assertions)]
 use bevy::diagnostic::{FrameTimeDiagnosticsPlugin, LogDiagnosticsPlugin};
@@ -56,10 +91,27 @@ impl Plugin for GamePlugin {
             });
 
         app.add_plugin(LoadingPlugin)
+            .add_plugin(SettingsPlugin)
+            .add_plugin(LocalizationPlugin)
             .add_plugin(UiPlugin)
             .add_plugin(InternalAudioPlugin)
             .add_plugin(PlayerPlugin)
             .add_plugin(BarterPlugin)
+            .add_plugin(DialoguePlugin)
+            .add_plugin(RunPlugin)
+            .add_plugin(RngPlugin)
+            .add_plugin(DifficultyPlugin)
+            .add_plugin(CampaignPlugin)
+            .add_plugin(EndlessPlugin)
+            .add_plugin(DailyPlugin)
+            .add_plugin(RecordsPlugin)
+            .add_plugin(AchievementsPlugin)
+            .add_plugin(SkillsPlugin)
+            .add_plugin(NightPlugin)
+            .add_plugin(MarketPlugin)
+            .add_plugin(BankPlugin)
+            .add_plugin(UpgradesPlugin)
+            .add_plugin(LedgerPlugin)
             .add_plugin(ScenePlugin);
 
         app.add_loopless_state(PausedState::Playing);
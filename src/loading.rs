@@ -1,3 +1,10 @@
+use crate::achievements::AchievementList;
+use crate::audio::cues::SoundCueTable;
+use crate::campaign::Campaign;
+use crate::dialogue::tree::DialogueTree;
+use crate::dialogue::CustomerDialogue;
+use crate::localization::Translations;
+use crate::ui::theme::UiTheme;
 use crate::GameState;
 use bevy::prelude::*;
 use bevy_asset_loader::prelude::*;
@@ -18,7 +25,11 @@ impl Plugin for LoadingPlugin {
             .add_enter_system(GameState::Loading, load_asperite)
             .add_loading_state(
                 LoadingState::new(GameState::Loading)
-                    .with_collection::<FontAssets>()
+                    .with_collection::<ThemeAssets>()
+                    .with_collection::<LocaleAssets>()
+                    .with_collection::<DialogueAssets>()
+                    .with_collection::<CampaignAssets>()
+                    .with_collection::<AchievementAssets>()
                     .with_collection::<AudioAssets>()
                     .with_collection::<TextureAssets>()
                     .continue_to_state(GameState::Setup),
@@ -38,19 +49,86 @@ aseprite!(pub ShopBackground, "textures/shop_bg.aseprite");
 // the following asset collections will be loaded during the State `GameState::Loading`
 // when done loading, they will be inserted as resources (see <https://github.com/NiklasEi/bevy_asset_loader>)
 
+/// Every theme that can be picked in the options, the first one is used when the theme in the
+/// settings doesn't exist
 #[derive(AssetCollection, Resource)]
-pub struct FontAssets {
-    #[asset(path = "fonts/FiraSans-Bold.ttf")]
-    pub fira_sans: Handle<Font>,
+pub struct ThemeAssets {
+    #[asset(
+        paths("themes/default.theme.ron", "themes/abaddon.theme.ron"),
+        collection(typed)
+    )]
+    pub themes: Vec<Handle<UiTheme>>,
 }
 
+/// Every language that can be picked in the options, see [`crate::localization`]
 #[derive(AssetCollection, Resource)]
-pub struct AudioAssets {}
+pub struct LocaleAssets {
+    #[asset(paths("locales/en-US.ftl", "locales/de.ftl"), collection(typed))]
+    pub locales: Vec<Handle<Translations>>,
+}
+
+/// The lines of every customer archetype and the trees of the story characters, see
+/// [`crate::dialogue`]
+#[derive(AssetCollection, Resource)]
+pub struct DialogueAssets {
+    #[asset(
+        paths(
+            "dialogue/commoner.dialogue.ron",
+            "dialogue/noble.dialogue.ron",
+            "dialogue/adventurer.dialogue.ron",
+            "dialogue/scholar.dialogue.ron"
+        ),
+        collection(typed)
+    )]
+    pub dialogue: Vec<Handle<CustomerDialogue>>,
+    #[asset(
+        paths(
+            "dialogue/story/collector.tree.ron",
+            "dialogue/story/inspector.tree.ron"
+        ),
+        collection(typed)
+    )]
+    pub trees: Vec<Handle<DialogueTree>>,
+}
+
+/// The chapters of the campaign, see [`crate::campaign`]
+#[derive(AssetCollection, Resource)]
+pub struct CampaignAssets {
+    #[asset(path = "campaign/story.campaign.ron")]
+    pub campaign: Handle<Campaign>,
+}
+
+/// Every achievement that can be unlocked, see [`crate::achievements`]
+#[derive(AssetCollection, Resource)]
+pub struct AchievementAssets {
+    #[asset(path = "achievements/list.achievements.ron")]
+    pub achievements: Handle<AchievementList>,
+}
+
+#[derive(AssetCollection, Resource)]
+pub struct AudioAssets {
+    #[asset(path = "audio/music/shop_calm.wav")]
+    pub shop_music: Handle<AudioSource>,
+    #[asset(path = "audio/music/barter_tense.wav")]
+    pub barter_music: Handle<AudioSource>,
+    #[asset(path = "audio/music/stinger_success.wav")]
+    pub stinger_success: Handle<AudioSource>,
+    #[asset(path = "audio/music/stinger_failure.wav")]
+    pub stinger_failure: Handle<AudioSource>,
+    #[asset(path = "audio/ambience/night.wav")]
+    pub night_ambience: Handle<AudioSource>,
+    #[asset(path = "audio/sfx.cues.ron")]
+    pub sound_cues: Handle<SoundCueTable>,
+}
 
 #[derive(AssetCollection, Resource)]
 pub struct TextureAssets {
     #[asset(path = "textures/bevy.png")]
     pub texture_bevy: Handle<Image>,
+    #[asset(path = "textures/icons/success.png")]
+    pub icon_success: Handle<Image>,
+    #[asset(path = "textures/icons/failure.png")]
+    pub icon_failure: Handle<Image>,
 }
 
 fn load_asperite(
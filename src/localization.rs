@@ -0,0 +1,264 @@
+//! Every piece of text shown to the player goes through [`Localization`]. The translations are
+//! [Fluent](https://projectfluent.org) files in `assets/locales`, one per language and named after
+//! the language they are for, so plurals and word order are up to each translation.
+//! Numbers should be passed through `NUMBER()` in the translations, which groups their digits with
+//! the separator the translation defines in `number-group-separator`.
+//! Messages missing from the selected language fall back to English
+
+use crate::loading::LocaleAssets;
+use crate::settings::Settings;
+use crate::GameState;
+use bevy::asset::{AssetLoader, BoxedFuture, LoadContext, LoadedAsset};
+use bevy::prelude::{
+    warn, AddAsset, App, AssetEvent, Assets, EventReader, EventWriter, Plugin, Res, ResMut,
+    Resource,
+};
+use bevy::reflect::TypeUuid;
+use bevy::utils::HashMap;
+use fluent_bundle::concurrent::FluentBundle;
+use fluent_bundle::{FluentArgs, FluentResource, FluentValue};
+use iyes_loopless::prelude::{AppLooplessStateExt, ConditionSet};
+use unic_langid::LanguageIdentifier;
+
+pub struct LocalizationPlugin;
+
+/// This plugin keeps the [`Localization`] in line with the language picked in the settings and
+/// rebuilds it whenever a translation is reloaded
+impl Plugin for LocalizationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_asset::<Translations>()
+            .init_asset_loader::<TranslationsLoader>()
+            .init_resource::<Localization>()
+            .add_event::<LanguageChanged>()
+            .add_enter_system(GameState::Setup, update_localization)
+            .add_system_set(
+                ConditionSet::new()
+                    .run_if_resource_exists::<LocaleAssets>()
+                    .with_system(update_localization)
+                    .into(),
+            );
+    }
+}
+
+/// The language every translation falls back to
+pub const DEFAULT_LANGUAGE: &str = "en-US";
+
+/// Sent after the [`Localization`] switched to another language or a translation was reloaded
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct LanguageChanged;
+
+/// The contents of a `.ftl` file
+#[derive(Clone, PartialEq, Eq, Debug, TypeUuid)]
+#[uuid = "b5f1d7a4-92c3-4e08-8a6d-3c1e5f7b9d20"]
+pub struct Translations {
+    /// Language identifier taken from the file name, like `en-US` for `en-US.ftl`
+    pub language: String,
+    pub source: String,
+}
+
+#[derive(Default)]
+pub struct TranslationsLoader;
+
+impl AssetLoader for TranslationsLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), bevy::asset::Error>> {
+        Box::pin(async move {
+            let language = load_context
+                .path()
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+                .unwrap_or_else(|| String::from(DEFAULT_LANGUAGE));
+            let source = String::from_utf8(bytes.to_vec())?;
+            load_context.set_default_asset(LoadedAsset::new(Translations { language, source }));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["ftl"]
+    }
+}
+
+/// Looks up the text for a message id in the selected language
+#[derive(Resource, Default)]
+pub struct Localization {
+    language: String,
+    /// The selected language followed by the fallback
+    bundles: Vec<FluentBundle<FluentResource>>,
+}
+
+impl Localization {
+    pub fn get_language(&self) -> &str {
+        &self.language
+    }
+
+    pub fn get(&self, id: &str) -> String {
+        self.format(id, None)
+    }
+
+    /// Like [`Localization::get`] but fills in the variables of the message
+    pub fn get_with(&self, id: &str, args: &[(&str, FluentValue)]) -> String {
+        let mut fluent_args = FluentArgs::new();
+        for (name, value) in args {
+            fluent_args.set(*name, value.clone());
+        }
+        self.format(id, Some(&fluent_args))
+    }
+
+    fn format(&self, id: &str, args: Option<&FluentArgs>) -> String {
+        for bundle in self.bundles.iter() {
+            if let Some(pattern) = bundle.get_message(id).and_then(|message| message.value()) {
+                let mut errors = vec![];
+                let text = bundle.format_pattern(pattern, args, &mut errors);
+                for error in errors {
+                    warn!("Failed to format {}: {}", id, error);
+                }
+                return text.into_owned();
+            }
+        }
+        // shown as is so that missing messages are easy to spot
+        String::from(id)
+    }
+}
+
+/// Text in every language it was written in, for text that lives in data files instead of the
+/// translations
+pub type LocalizedText = HashMap<String, String>;
+
+/// The text in the language, or in English if it wasn't written in that language
+pub fn get_localized<'a>(text: &'a LocalizedText, language: &str) -> &'a str {
+    text.get(language)
+        .or_else(|| text.get(DEFAULT_LANGUAGE))
+        .map(|text| text.as_str())
+        .unwrap_or_default()
+}
+
+/// The languages of every loaded translation, in the order they are listed in [`LocaleAssets`]
+pub fn get_languages(
+    locale_assets: &LocaleAssets,
+    translations: &Assets<Translations>,
+) -> Vec<String> {
+    locale_assets
+        .locales
+        .iter()
+        .filter_map(|handle| translations.get(handle))
+        .map(|translations| translations.language.clone())
+        .collect()
+}
+
+/// Puts `separator` between every group of three digits of the whole part of `number`
+pub fn group_digits(number: f64, separator: &str) -> String {
+    let whole = number.abs().trunc() as u64;
+    let digits = whole.to_string();
+    let mut grouped = String::new();
+    for (index, digit) in digits.chars().enumerate() {
+        if index > 0 && (digits.len() - index) % 3 == 0 {
+            grouped.push_str(separator);
+        }
+        grouped.push(digit);
+    }
+    if number < 0.0 {
+        grouped.insert(0, '-');
+    }
+    grouped
+}
+
+fn build_bundle(translations: &Translations) -> Option<FluentBundle<FluentResource>> {
+    let language: LanguageIdentifier = match translations.language.parse() {
+        Ok(language) => language,
+        Err(error) => {
+            warn!("{} is not a language: {}", translations.language, error);
+            return None;
+        }
+    };
+    let resource = FluentResource::try_new(translations.source.clone()).unwrap_or_else(
+        |(resource, errors)| {
+            for error in errors {
+                warn!(
+                    "Error in the {} translation: {}",
+                    translations.language, error
+                );
+            }
+            resource
+        },
+    );
+
+    let mut bundle = FluentBundle::new_concurrent(vec![language]);
+    // fluent wraps variables in unicode isolation marks, which the game fonts draw as boxes
+    bundle.set_use_isolating(false);
+    if let Err(errors) = bundle.add_resource(resource) {
+        for error in errors {
+            warn!(
+                "Error in the {} translation: {}",
+                translations.language, error
+            );
+        }
+    }
+
+    let separator = bundle
+        .get_message("number-group-separator")
+        .and_then(|message| message.value())
+        .map(|pattern| {
+            bundle
+                .format_pattern(pattern, None, &mut vec![])
+                .into_owned()
+        })
+        .unwrap_or_default();
+    let result = bundle.add_function("NUMBER", move |positional, _named| {
+        match positional.first() {
+            Some(FluentValue::Number(number)) => {
+                FluentValue::from(group_digits(number.value, &separator))
+            }
+            Some(value) => value.clone(),
+            None => FluentValue::Error,
+        }
+    });
+    if let Err(error) = result {
+        warn!(
+            "Failed to add NUMBER to the {} translation: {}",
+            translations.language, error
+        );
+    }
+    Some(bundle)
+}
+
+fn update_localization(
+    mut translation_events: EventReader<AssetEvent<Translations>>,
+    locale_assets: Res<LocaleAssets>,
+    translations: Res<Assets<Translations>>,
+    settings: Res<Settings>,
+    mut localization: ResMut<Localization>,
+    mut language_changed: EventWriter<LanguageChanged>,
+) {
+    let reloaded = translation_events.iter().count() > 0;
+    if !reloaded && localization.language == settings.language {
+        return;
+    }
+
+    let loaded: Vec<&Translations> = locale_assets
+        .locales
+        .iter()
+        .filter_map(|handle| translations.get(handle))
+        .collect();
+    let mut languages = vec![settings.language.as_str()];
+    if settings.language != DEFAULT_LANGUAGE {
+        languages.push(DEFAULT_LANGUAGE);
+    }
+    let mut bundles = vec![];
+    for language in languages {
+        if let Some(bundle) = loaded
+            .iter()
+            .find(|translations| translations.language == language)
+            .and_then(|translations| build_bundle(translations))
+        {
+            bundles.push(bundle);
+        }
+    }
+
+    localization.language = settings.language.clone();
+    localization.bundles = bundles;
+    language_changed.send_default();
+}
@@ -33,7 +33,12 @@ fn main() {
                     },
                     ..default()
                 })
-                .set(ImagePlugin::default_nearest()),
+                .set(ImagePlugin::default_nearest())
+                .set(AssetPlugin {
+                    // reload assets like the interface themes when they change on disk
+                    watch_for_changes: cfg!(feature = "dev"),
+                    ..default()
+                }),
         )
         .add_plugin(AsepritePlugin)
         .add_plugin(TiledCameraPlugin)
@@ -0,0 +1,113 @@
+use bevy::prelude::Resource;
+use bevy::utils::HashMap;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+/// The different kinds of goods the shop deals in. Each category has its own market price
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+pub enum ItemCategory {
+    Trinket,
+    Potion,
+    Tome,
+    Weapon,
+    Relic,
+}
+
+impl ItemCategory {
+    pub const ALL: [ItemCategory; 5] = [
+        ItemCategory::Trinket,
+        ItemCategory::Potion,
+        ItemCategory::Tome,
+        ItemCategory::Weapon,
+        ItemCategory::Relic,
+    ];
+
+    /// Id of the name of the item in the translations
+    pub fn get_locale_key(&self) -> &'static str {
+        return match self {
+            ItemCategory::Trinket => "item-trinket",
+            ItemCategory::Potion => "item-potion",
+            ItemCategory::Tome => "item-tome",
+            ItemCategory::Weapon => "item-weapon",
+            ItemCategory::Relic => "item-relic",
+        };
+    }
+
+    /// The value of the category on an average night in a balanced market
+    pub fn base_value(&self) -> u32 {
+        return match self {
+            ItemCategory::Trinket => 25,
+            ItemCategory::Potion => 40,
+            ItemCategory::Tome => 60,
+            ItemCategory::Weapon => 85,
+            ItemCategory::Relic => 130,
+        };
+    }
+}
+
+/// Stock the shop currently has on hand, by category
+#[derive(Clone, PartialEq, Debug, Resource, Serialize, Deserialize)]
+pub struct Inventory {
+    stock: HashMap<ItemCategory, u32>,
+}
+
+impl Default for Inventory {
+    fn default() -> Self {
+        let mut stock = HashMap::default();
+        stock.insert(ItemCategory::Trinket, 3);
+        stock.insert(ItemCategory::Potion, 3);
+        stock.insert(ItemCategory::Tome, 1);
+        stock.insert(ItemCategory::Weapon, 1);
+        stock.insert(ItemCategory::Relic, 0);
+        Inventory { stock }
+    }
+}
+
+impl Inventory {
+    pub fn get_stock(&self, item: ItemCategory) -> u32 {
+        self.stock.get(&item).cloned().unwrap_or(0)
+    }
+
+    /// How many items there are in stock over every category
+    pub fn get_total_stock(&self) -> u32 {
+        self.stock.values().sum()
+    }
+
+    pub fn has_any_stock(&self) -> bool {
+        self.stock.values().any(|amount| *amount > 0)
+    }
+
+    pub fn add(&mut self, item: ItemCategory, amount: u32) {
+        let stock = self.stock.entry(item).or_insert(0);
+        *stock = stock.saturating_add(amount);
+    }
+
+    /// Removes a single item from the stock. Returns false if there was none to remove
+    pub fn remove_one(&mut self, item: ItemCategory) -> bool {
+        match self.stock.get_mut(&item) {
+            Some(stock) if *stock > 0 => {
+                *stock -= 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Picks a random category out of the ones we currently have in stock
+    pub fn random_in_stock(&self, rng: &mut impl Rng) -> Option<ItemCategory> {
+        let in_stock: Vec<ItemCategory> = ItemCategory::ALL
+            .iter()
+            .filter(|item| self.get_stock(**item) > 0)
+            .cloned()
+            .collect();
+        in_stock.choose(rng).cloned()
+    }
+}
+
+/// Sent when the player buys stock from the supplier
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct ItemPurchased {
+    pub item: ItemCategory,
+    pub cost: u32,
+}
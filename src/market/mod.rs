@@ -0,0 +1,192 @@
+//! The market simulates what the outside world is willing to pay for the goods the shop sells.
+//! Every night each item category gets a new price multiplier made up of three parts
+//! - A seasonal trend that slowly cycles over a number of nights
+//! - Demand shocks, random events that spike or crash a category and then fade out again
+//! - Oversupply, caused by the player flooding the market by selling a lot of one category
+
+use crate::barter::{BarterResolutionTypes, BarterResolved};
+use crate::market::items::{Inventory, ItemCategory, ItemPurchased};
+use crate::night::NightEnded;
+use crate::rng::RunRng;
+use bevy::prelude::{App, EventReader, Plugin, ResMut, Resource};
+use bevy::utils::HashMap;
+use iyes_loopless::prelude::ConditionSet;
+use rand::{thread_rng, Rng};
+use std::f32::consts::TAU;
+
+pub mod items;
+
+pub struct MarketPlugin;
+
+impl Plugin for MarketPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Market>()
+            .init_resource::<Inventory>()
+            .add_event::<ItemPurchased>();
+
+        app.add_system_set(
+            ConditionSet::new()
+                .run_on_event::<NightEnded>()
+                .with_system(update_market_prices)
+                .into(),
+        )
+        .add_system_set(
+            ConditionSet::new()
+                .run_on_event::<BarterResolved>()
+                .with_system(record_market_sales)
+                .into(),
+        );
+    }
+}
+
+/// How many nights a full seasonal cycle takes
+pub const SEASON_LENGTH: u32 = 7;
+/// How far the seasonal trend can move the price in either direction
+pub const SEASONAL_STRENGTH: f32 = 0.2;
+/// Chance for each category to be hit by a demand shock on a given night
+pub const DEMAND_SHOCK_CHANCE: f64 = 0.15;
+/// How much of the distance back to normal demand is recovered every night
+pub const DEMAND_RECOVERY: f32 = 0.3;
+/// Oversupply added for every unit the player sells
+pub const OVERSUPPLY_PER_SALE: f32 = 0.08;
+/// How much of the oversupply is left over after a night
+pub const OVERSUPPLY_DECAY: f32 = 0.5;
+/// Fraction of the market value the supplier charges the player
+pub const SUPPLIER_MARGIN: f32 = 0.6;
+
+pub const MIN_PRICE_MULTIPLIER: f32 = 0.3;
+pub const MAX_PRICE_MULTIPLIER: f32 = 2.5;
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct CategoryMarket {
+    /// Long lasting multiplier caused by demand shocks. Drifts back towards 1.0
+    pub demand: f32,
+    /// Offset into the seasonal cycle so that every category doesn't peak on the same night
+    pub season_offset: u32,
+    /// How flooded the market is with this category
+    pub oversupply: f32,
+    /// Units the player sold during the current night
+    pub sold_tonight: u32,
+    pub price_multiplier: f32,
+    pub previous_price_multiplier: f32,
+}
+
+impl CategoryMarket {
+    pub fn new(season_offset: u32) -> CategoryMarket {
+        let mut market = CategoryMarket {
+            demand: 1.0,
+            season_offset,
+            oversupply: 0.0,
+            sold_tonight: 0,
+            price_multiplier: 1.0,
+            previous_price_multiplier: 1.0,
+        };
+        market.price_multiplier = market.calculate_multiplier(1);
+        market.previous_price_multiplier = market.price_multiplier;
+        market
+    }
+
+    fn seasonal_trend(&self, night: u32) -> f32 {
+        let phase = ((night + self.season_offset) % SEASON_LENGTH) as f32 / SEASON_LENGTH as f32;
+        1.0 + SEASONAL_STRENGTH * (phase * TAU).sin()
+    }
+
+    fn calculate_multiplier(&self, night: u32) -> f32 {
+        (self.seasonal_trend(night) * self.demand * (1.0 - self.oversupply).max(0.5))
+            .clamp(MIN_PRICE_MULTIPLIER, MAX_PRICE_MULTIPLIER)
+    }
+
+    /// Moves the category on to the given night
+    pub fn advance_to_night(&mut self, night: u32, rng: &mut impl Rng) {
+        if rng.gen_bool(DEMAND_SHOCK_CHANCE) {
+            self.demand = (self.demand * rng.gen_range(0.6..1.5)).clamp(0.4, 2.0);
+        } else {
+            self.demand += (1.0 - self.demand) * DEMAND_RECOVERY;
+        }
+
+        self.oversupply = (self.oversupply * OVERSUPPLY_DECAY
+            + self.sold_tonight as f32 * OVERSUPPLY_PER_SALE)
+            .min(1.0);
+        self.sold_tonight = 0;
+
+        self.previous_price_multiplier = self.price_multiplier;
+        self.price_multiplier = self.calculate_multiplier(night);
+    }
+}
+
+#[derive(Clone, PartialEq, Debug, Resource)]
+pub struct Market {
+    categories: HashMap<ItemCategory, CategoryMarket>,
+}
+
+impl Default for Market {
+    fn default() -> Self {
+        Market::new(&mut thread_rng())
+    }
+}
+
+impl Market {
+    /// A market with every category at a random point of its season
+    pub fn new(rng: &mut impl Rng) -> Market {
+        let mut categories = HashMap::default();
+        for item in ItemCategory::ALL {
+            categories.insert(item, CategoryMarket::new(rng.gen_range(0..SEASON_LENGTH)));
+        }
+        Market { categories }
+    }
+
+    pub fn get_category(&self, item: ItemCategory) -> &CategoryMarket {
+        self.categories
+            .get(&item)
+            .expect("Every item category should have a market")
+    }
+
+    /// What the item is currently worth to customers
+    pub fn get_value(&self, item: ItemCategory) -> u32 {
+        (item.base_value() as f32 * self.get_category(item).price_multiplier).round() as u32
+    }
+
+    /// What the supplier currently charges the player for the item
+    pub fn get_supplier_cost(&self, item: ItemCategory) -> u32 {
+        ((self.get_value(item) as f32 * SUPPLIER_MARGIN).round() as u32).max(1)
+    }
+
+    /// The change in price since last night, as a fraction of last nights price
+    pub fn get_trend(&self, item: ItemCategory) -> f32 {
+        let category = self.get_category(item);
+        category.price_multiplier / category.previous_price_multiplier - 1.0
+    }
+
+    pub fn record_sale(&mut self, item: ItemCategory) {
+        if let Some(category) = self.categories.get_mut(&item) {
+            category.sold_tonight += 1;
+        }
+    }
+
+    pub fn advance_to_night(&mut self, night: u32, rng: &mut impl Rng) {
+        // in the same order every time, so a seeded run plays out the same
+        for item in ItemCategory::ALL {
+            if let Some(category) = self.categories.get_mut(&item) {
+                category.advance_to_night(night, rng);
+            }
+        }
+    }
+}
+
+fn update_market_prices(
+    mut events: EventReader<NightEnded>,
+    mut market: ResMut<Market>,
+    mut rng: ResMut<RunRng>,
+) {
+    for event in events.iter() {
+        market.advance_to_night(event.night + 1, &mut rng.market);
+    }
+}
+
+fn record_market_sales(mut events: EventReader<BarterResolved>, mut market: ResMut<Market>) {
+    for event in events.iter() {
+        if let BarterResolutionTypes::Approve { .. } = event.resolution_type {
+            market.record_sale(event.item);
+        }
+    }
+}
@@ -0,0 +1,67 @@
+use crate::{GameState, PausedState};
+use bevy::prelude::{App, EventWriter, Plugin, Res, ResMut, Resource, Time};
+use iyes_loopless::prelude::ConditionSet;
+
+pub struct NightPlugin;
+
+/// This plugin keeps track of the passing nights. The shop is open from dusk till dawn and once a
+/// night is over [`NightEnded`] is sent so that everything that works on a nightly basis can update
+impl Plugin for NightPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Night>().add_event::<NightEnded>();
+
+        app.add_system_set(
+            ConditionSet::new()
+                .run_in_state(GameState::Playing)
+                .run_in_state(PausedState::Playing)
+                .with_system(tick_night)
+                .into(),
+        );
+    }
+}
+
+/// How many seconds a single night lasts
+pub const NIGHT_LENGTH: f32 = 180.0;
+
+#[derive(Clone, PartialEq, Debug, Resource)]
+pub struct Night {
+    /// The current night, starting at 1
+    pub number: u32,
+    /// Seconds elapsed in the current night
+    pub elapsed: f32,
+    pub length: f32,
+}
+
+impl Default for Night {
+    fn default() -> Self {
+        Night {
+            number: 1,
+            elapsed: 0.0,
+            length: NIGHT_LENGTH,
+        }
+    }
+}
+
+impl Night {
+    /// How far through the night we are, from 0.0 at dusk to 1.0 at dawn
+    pub fn progress(&self) -> f32 {
+        (self.elapsed / self.length).clamp(0.0, 1.0)
+    }
+}
+
+/// Sent when the night is over. Contains the number of the night that just ended
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct NightEnded {
+    pub night: u32,
+}
+
+fn tick_night(mut night: ResMut<Night>, time: Res<Time>, mut night_ended: EventWriter<NightEnded>) {
+    night.elapsed += time.delta_seconds();
+    if night.elapsed >= night.length {
+        night_ended.send(NightEnded {
+            night: night.number,
+        });
+        night.number += 1;
+        night.elapsed = 0.0;
+    }
+}
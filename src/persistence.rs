@@ -0,0 +1,91 @@
+//! Helpers for the files that persist between runs, like the settings
+//! Everything is stored as ron inside of [`DATA_FOLDER`].
+//! Also holds the [`RonAssetLoader`] every asset written as ron in the assets folder is loaded with
+
+use bevy::asset::{Asset, AssetLoader, AssetPath, BoxedFuture, LoadContext, LoadedAsset};
+use bevy::prelude::{warn, Res, Resource};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fs;
+use std::io;
+use std::marker::PhantomData;
+use std::path::PathBuf;
+
+pub const DATA_FOLDER: &str = "saves";
+
+pub fn data_path(file_name: &str) -> PathBuf {
+    PathBuf::from(DATA_FOLDER).join(file_name)
+}
+
+/// Loads the given file. Returns None if the file doesn't exist or can't be read
+pub fn load_ron<T: DeserializeOwned>(file_name: &str) -> Option<T> {
+    let contents = fs::read_to_string(data_path(file_name)).ok()?;
+    match ron::from_str(&contents) {
+        Ok(value) => Some(value),
+        Err(error) => {
+            warn!("Failed to read {}: {}", file_name, error);
+            None
+        }
+    }
+}
+
+pub fn save_ron<T: Serialize>(file_name: &str, value: &T) -> io::Result<()> {
+    let contents = ron::ser::to_string_pretty(value, ron::ser::PrettyConfig::default())
+        .map_err(|error| io::Error::new(io::ErrorKind::Other, error))?;
+    fs::create_dir_all(DATA_FOLDER)?;
+    fs::write(data_path(file_name), contents)
+}
+
+/// A system that saves the resource to the given file whenever it changes. The resource is loaded
+/// from the file when the app starts, so it isn't saved again right after being added
+pub fn save_on_change<T: Resource + Serialize>(file_name: &'static str) -> impl FnMut(Res<T>) {
+    move |value: Res<T>| {
+        if !value.is_changed() || value.is_added() {
+            return;
+        }
+        if let Err(error) = save_ron(file_name, &*value) {
+            warn!("Failed to save {}: {}", file_name, error);
+        }
+    }
+}
+
+/// An asset written as ron and loaded by a [`RonAssetLoader`]
+pub trait RonAsset: Asset + DeserializeOwned {
+    /// The file endings of the asset, like `"theme.ron"`
+    const EXTENSIONS: &'static [&'static str];
+
+    /// Starts loading the other assets this one needs, like the font of a theme, and returns
+    /// their paths so they are loaded along with it
+    fn load_dependencies(&mut self, _load_context: &mut LoadContext) -> Vec<AssetPath<'static>> {
+        vec![]
+    }
+}
+
+pub struct RonAssetLoader<T> {
+    asset: PhantomData<fn() -> T>,
+}
+
+impl<T> Default for RonAssetLoader<T> {
+    fn default() -> Self {
+        RonAssetLoader { asset: PhantomData }
+    }
+}
+
+impl<T: RonAsset> AssetLoader for RonAssetLoader<T> {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), bevy::asset::Error>> {
+        Box::pin(async move {
+            let mut asset: T = ron::de::from_bytes(bytes)?;
+            let dependencies = asset.load_dependencies(load_context);
+            load_context.set_default_asset(LoadedAsset::new(asset).with_dependencies(dependencies));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        T::EXTENSIONS
+    }
+}
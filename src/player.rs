@@ -11,7 +11,7 @@ pub struct Player;
 
 impl Plugin for PlayerPlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<Gold>();
+        app.init_resource::<Gold>().init_resource::<Reputation>();
         
         app.add_enter_system(GameState::MainMenu, setup_basics)
             .add_system_set(ConditionSet::new().run_in_state(GameState::Playing).into());
@@ -44,7 +44,32 @@ pub struct Gold {
     pub amount: u32,
 }
 
-fn setup_basics(mut commands: Commands) {
+/// How the town sees the shop. Ranges from [`MIN_REPUTATION`] to [`MAX_REPUTATION`]
+#[derive(PartialEq, Clone, Copy, Debug, Resource)]
+pub struct Reputation {
+    pub amount: i32,
+}
+
+pub const MIN_REPUTATION: i32 = 0;
+pub const MAX_REPUTATION: i32 = 100;
+
+impl Default for Reputation {
+    fn default() -> Self {
+        Reputation { amount: 50 }
+    }
+}
+
+impl Reputation {
+    pub fn change(&mut self, amount: i32) {
+        self.amount = (self.amount + amount).clamp(MIN_REPUTATION, MAX_REPUTATION);
+    }
+}
+
+fn setup_basics(mut commands: Commands, cameras: Query<(), With<CameraMarker>>) {
+    // the main menu is opened again after every run, but one camera is all we need
+    if !cameras.is_empty() {
+        return;
+    }
     commands
         .spawn(PixelCameraBundle::from_resolution(320, 240))
         .insert(CameraMarker);
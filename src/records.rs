@@ -0,0 +1,125 @@
+//! Every run that ends is added to [`RECORDS_FILE`], with the mode it was played in, its seed and
+//! how well the shop did. A run ends when the shop goes bankrupt, the campaign is over or the
+//! player quits to the main menu from the pause screen. The records screen in the main menu lists
+//! them
+
+use crate::endless::EndlessRun;
+use crate::ledger::Ledger;
+use crate::night::Night;
+use crate::persistence::{load_ron, save_on_change};
+use crate::player::Reputation;
+use crate::rng::RunRng;
+use crate::run::RunMode;
+use crate::GameState;
+use bevy::prelude::{App, Plugin, Res, ResMut, Resource};
+use iyes_loopless::prelude::AppLooplessStateExt;
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+
+pub struct RecordsPlugin;
+
+impl Plugin for RecordsPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(load_ron::<Records>(RECORDS_FILE).unwrap_or_default())
+            .add_exit_system(GameState::Playing, record_run)
+            .add_system(save_on_change::<Records>(RECORDS_FILE));
+    }
+}
+
+pub const RECORDS_FILE: &str = "records.ron";
+
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct RunRecord {
+    pub mode: RunMode,
+    pub seed: u64,
+    pub nights: u32,
+    /// Gold made from sales over the whole run
+    pub gold: u32,
+    pub reputation: i32,
+    pub haggles_won: u32,
+}
+
+/// What the records can be sorted by, from best to worst
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+pub enum RecordStat {
+    #[default]
+    Newest,
+    Nights,
+    Gold,
+    Reputation,
+    HagglesWon,
+}
+
+impl RecordStat {
+    pub const ALL: [RecordStat; 5] = [
+        RecordStat::Newest,
+        RecordStat::Nights,
+        RecordStat::Gold,
+        RecordStat::Reputation,
+        RecordStat::HagglesWon,
+    ];
+
+    /// Id of the name of the stat in the translations
+    pub fn get_locale_key(&self) -> &'static str {
+        return match self {
+            RecordStat::Newest => "records-sort-newest",
+            RecordStat::Nights => "records-sort-nights",
+            RecordStat::Gold => "records-sort-gold",
+            RecordStat::Reputation => "records-sort-reputation",
+            RecordStat::HagglesWon => "records-sort-haggles",
+        };
+    }
+}
+
+/// Every finished run, oldest first, saved in [`RECORDS_FILE`]
+#[derive(Resource, Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Records {
+    pub runs: Vec<RunRecord>,
+}
+
+impl Records {
+    /// The runs played in the mode, or in any mode for None, best first
+    pub fn get_sorted(&self, mode: Option<RunMode>, stat: RecordStat) -> Vec<&RunRecord> {
+        let mut runs: Vec<&RunRecord> = self
+            .runs
+            .iter()
+            .filter(|run| mode.is_none() || mode == Some(run.mode))
+            .collect();
+        // newest first, which also breaks ties between the other stats
+        runs.reverse();
+        match stat {
+            RecordStat::Newest => {}
+            RecordStat::Nights => runs.sort_by_key(|run| Reverse(run.nights)),
+            RecordStat::Gold => runs.sort_by_key(|run| Reverse(run.gold)),
+            RecordStat::Reputation => runs.sort_by_key(|run| Reverse(run.reputation)),
+            RecordStat::HagglesWon => runs.sort_by_key(|run| Reverse(run.haggles_won)),
+        }
+        runs
+    }
+}
+
+fn record_run(
+    mut records: ResMut<Records>,
+    run_mode: Res<RunMode>,
+    rng: Res<RunRng>,
+    night: Res<Night>,
+    endless_run: Option<Res<EndlessRun>>,
+    ledger: Res<Ledger>,
+    reputation: Res<Reputation>,
+) {
+    let stats = ledger.stats_all_time();
+    // the night that is going on hasn't been survived yet
+    let nights = match endless_run {
+        Some(endless_run) => endless_run.nights_survived,
+        None => night.number.saturating_sub(1),
+    };
+    records.runs.push(RunRecord {
+        mode: *run_mode,
+        seed: rng.seed,
+        nights,
+        gold: stats.revenue,
+        reputation: reputation.amount,
+        haggles_won: stats.haggles_won,
+    });
+}
@@ -0,0 +1,85 @@
+//! Everything random that shapes a run is drawn from [`RunRng`], which is seeded at the start of
+//! every run. Two runs with the same seed get the same market, the same customers in the same
+//! order and the same items put up for barter, no matter how the player plays.
+//! Every part of the run draws from its own stream, so haggling a lot doesn't change who walks in
+//! next. Things that are only for show, like sounds and where customers wander, keep using
+//! `thread_rng`
+
+use crate::market::Market;
+use crate::GameState;
+use bevy::prelude::{App, Plugin, ResMut, Resource};
+use iyes_loopless::prelude::AppLooplessStateExt;
+use rand::rngs::StdRng;
+use rand::{thread_rng, Rng, SeedableRng};
+
+pub struct RngPlugin;
+
+impl Plugin for RngPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RequestedSeed>()
+            .init_resource::<RunRng>()
+            .add_enter_system(GameState::Playing, seed_run);
+    }
+}
+
+/// The seed the next run should use. Runs without one get a random seed
+#[derive(Resource, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct RequestedSeed(pub Option<u64>);
+
+/// The random number generators of the current run
+#[derive(Resource, Clone, Debug)]
+pub struct RunRng {
+    pub seed: u64,
+    /// The customers coming in on their own
+    pub customers: StdRng,
+    /// Customers sent by the story or the bank
+    pub visitors: StdRng,
+    /// The item and opening price of every barter
+    pub barters: StdRng,
+    pub haggles: StdRng,
+    pub market: StdRng,
+    /// The estimates of appraising customers
+    pub appraisals: StdRng,
+}
+
+impl RunRng {
+    pub fn new(seed: u64) -> RunRng {
+        // every stream gets its own seed so they don't repeat each other
+        let stream = |index: u64| StdRng::seed_from_u64(mix_seed(seed.wrapping_add(index)));
+        RunRng {
+            seed,
+            customers: stream(0),
+            visitors: stream(1),
+            barters: stream(2),
+            haggles: stream(3),
+            market: stream(4),
+            appraisals: stream(5),
+        }
+    }
+}
+
+impl Default for RunRng {
+    fn default() -> Self {
+        RunRng::new(thread_rng().gen())
+    }
+}
+
+/// Spreads seeds that are close together, like the days of the daily challenge, far apart
+pub fn mix_seed(seed: u64) -> u64 {
+    // splitmix64
+    let mut z = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// Seeds the run and rolls the market from the seed
+fn seed_run(
+    mut requested: ResMut<RequestedSeed>,
+    mut rng: ResMut<RunRng>,
+    mut market: ResMut<Market>,
+) {
+    let seed = requested.0.take().unwrap_or_else(|| thread_rng().gen());
+    *rng = RunRng::new(seed);
+    *market = Market::new(&mut rng.market);
+}
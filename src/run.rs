@@ -0,0 +1,90 @@
+//! A run is everything from picking a mode in the main menu until the player is back in it.
+//! Leaving [`GameState::Playing`] throws the shop away, so the next run starts from the same
+//! gold, stock and night as the very first one. Modes that carry something over between runs,
+//! like the campaign, put it back once the new run has started
+
+use crate::achievements::AchievementTracker;
+use crate::bank::Bank;
+use crate::barter::customers::{Customer, CustomerHandler, CustomerSettings};
+use crate::barter::Barter;
+use crate::dialogue::tree::{ActiveDialogue, StoryFlags};
+use crate::ledger::Ledger;
+use crate::market::items::Inventory;
+use crate::market::Market;
+use crate::night::Night;
+use crate::player::{Gold, Reputation};
+use crate::ui::UiState;
+use crate::upgrades::ShopUpgrades;
+use crate::{GameState, PausedState};
+use bevy::prelude::{App, Commands, DespawnRecursiveExt, Entity, Plugin, Query, Resource, With};
+use iyes_loopless::prelude::{AppLooplessStateExt, NextState};
+use serde::{Deserialize, Serialize};
+
+pub struct RunPlugin;
+
+impl Plugin for RunPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RunMode>()
+            .add_exit_system(GameState::Playing, reset_run);
+    }
+}
+
+/// What the player picked in the main menu for the current run
+#[derive(Resource, Clone, Copy, PartialEq, Eq, Hash, Debug, Default, Serialize, Deserialize)]
+pub enum RunMode {
+    /// The shop runs for as long as the player likes, without any goals
+    #[default]
+    FreePlay,
+    /// Chapters with objectives, see [`crate::campaign`]
+    Campaign,
+    /// Goes on until the shop can't pay its rent, see [`crate::endless`]
+    Endless,
+    /// An endless run with the seed of the day, see [`crate::daily`]
+    Daily,
+}
+
+impl RunMode {
+    pub const ALL: [RunMode; 4] = [
+        RunMode::FreePlay,
+        RunMode::Campaign,
+        RunMode::Endless,
+        RunMode::Daily,
+    ];
+
+    /// Id of the name of the mode in the translations
+    pub fn get_locale_key(&self) -> &'static str {
+        return match self {
+            RunMode::FreePlay => "mode-free-play",
+            RunMode::Campaign => "mode-campaign",
+            RunMode::Endless => "mode-endless",
+            RunMode::Daily => "mode-daily",
+        };
+    }
+
+    /// The shop pays rent until it goes bankrupt
+    pub fn is_endless(&self) -> bool {
+        matches!(self, RunMode::Endless | RunMode::Daily)
+    }
+}
+
+fn reset_run(mut commands: Commands, customers: Query<Entity, With<Customer>>) {
+    for customer in customers.iter() {
+        commands.entity(customer).despawn_recursive();
+    }
+    commands.insert_resource(CustomerHandler::default());
+    commands.insert_resource(CustomerSettings::default());
+    commands.insert_resource(Gold::default());
+    commands.insert_resource(Reputation::default());
+    commands.insert_resource(Inventory::default());
+    commands.insert_resource(Market::default());
+    commands.insert_resource(Night::default());
+    commands.insert_resource(Bank::default());
+    commands.insert_resource(Ledger::default());
+    commands.insert_resource(StoryFlags::default());
+    commands.insert_resource(AchievementTracker::default());
+    commands.insert_resource(ShopUpgrades::default());
+    commands.remove_resource::<Barter>();
+    commands.remove_resource::<ActiveDialogue>();
+    commands.insert_resource(NextState(UiState::Normal));
+    commands.insert_resource(NextState(PausedState::Playing));
+}
@@ -1,7 +1,8 @@
 ﻿
 use crate::loading::{AsepriteHandles};
+use crate::upgrades::{ShopUpgrade, ShopUpgrades};
 use crate::GameState;
-use bevy::prelude::{default, Assets, Commands, Res, ResMut, TextureAtlasSprite, Transform, Vec3, Plugin, App};
+use bevy::prelude::{default, Assets, Commands, Component, DespawnRecursiveExt, Entity, Query, Res, ResMut, TextureAtlasSprite, Transform, Vec3, Plugin, App, With};
 use bevy_mod_aseprite::{Aseprite, AsepriteAnimation, AsepriteBundle};
 use iyes_loopless::prelude::AppLooplessStateExt;
 
@@ -11,16 +12,30 @@ pub struct ScenePlugin;
 /// Player logic is only active during the State `GameState::Playing`
 impl Plugin for ScenePlugin {
     fn build(&self, app: &mut App) {
-        app.add_enter_system(GameState::MainMenu, setup_background_scene);
+        app.add_enter_system(GameState::MainMenu, setup_background_scene)
+            .add_system(update_upgrade_scene);
         //.add_system_set(ConditionSet::new().run_in_state(GameState::Playing).into());
     }
 }
 
+/// Marks the shop drawn behind everything else
+#[derive(Component)]
+pub struct BackgroundScene;
+
+/// An upgrade of the shop, drawn on top of the [`BackgroundScene`]
+#[derive(Component)]
+pub struct UpgradeScene;
+
 pub fn setup_background_scene(
     aseprite_handles: ResMut<AsepriteHandles>,
     aseprites: Res<Assets<Aseprite>>,
     mut commands: Commands,
+    backgrounds: Query<(), With<BackgroundScene>>,
 ) {
+    // the main menu is opened again after every run, the shop is still there from the last one
+    if !backgrounds.is_empty() {
+        return;
+    }
     let aseprite_handle = &aseprite_handles[0];
     let sprite = aseprites.get(aseprite_handle).unwrap();
     
@@ -43,6 +58,62 @@ pub fn setup_background_scene(
             global_transform: Default::default(),
             visibility: Default::default(),
             computed_visibility: Default::default(),
-        }),
+        }, BackgroundScene),
     );
 }
+
+/// Every level of an upgrade has its own tag in the shop aseprite, drawn on the `Upgrades` layer
+/// together with the levels below it. Its frames only hold the upgrade, so they are drawn on top of
+/// the shop as they are. Levels without a tag are left out
+pub fn update_upgrade_scene(
+    aseprite_handles: Res<AsepriteHandles>,
+    aseprites: Res<Assets<Aseprite>>,
+    upgrades: Res<ShopUpgrades>,
+    mut commands: Commands,
+    upgrade_scenes: Query<Entity, With<UpgradeScene>>,
+) {
+    if !upgrades.is_changed() {
+        return;
+    }
+    let aseprite_handle = match aseprite_handles.get(0) {
+        Some(aseprite_handle) => aseprite_handle,
+        None => return,
+    };
+    let sprite = match aseprites.get(aseprite_handle) {
+        Some(sprite) => sprite,
+        None => return,
+    };
+    for upgrade_scene in upgrade_scenes.iter() {
+        commands.entity(upgrade_scene).despawn_recursive();
+    }
+
+    for (index, upgrade) in ShopUpgrade::ALL.iter().enumerate() {
+        let level = upgrades.get_level(*upgrade);
+        let tag = upgrade.get_tag(level);
+        if level == 0 || !sprite.info().tags.contains_key(&tag) {
+            continue;
+        }
+        let animation = AsepriteAnimation::new(sprite.info(), &tag);
+        commands.spawn((
+            AsepriteBundle {
+                aseprite: aseprite_handle.clone_weak(),
+                sprite: TextureAtlasSprite::new(animation.current_frame()),
+                animation,
+                texture_atlas: sprite.atlas().clone_weak(),
+                transform: Transform {
+                    translation: Vec3 {
+                        x: 0.0,
+                        y: 0.0,
+                        // above the shop, below the customers
+                        z: 1.1 + index as f32 * 0.1,
+                    },
+                    ..default()
+                },
+                global_transform: Default::default(),
+                visibility: Default::default(),
+                computed_visibility: Default::default(),
+            },
+            UpgradeScene,
+        ));
+    }
+}
@@ -0,0 +1,132 @@
+use crate::localization::DEFAULT_LANGUAGE;
+use crate::persistence::{load_ron, save_on_change};
+use crate::ui::palette::Palette;
+use bevy::prelude::{App, Plugin, Resource};
+use serde::{Deserialize, Serialize};
+
+pub struct SettingsPlugin;
+
+/// This plugin loads the players settings on startup and saves them whenever they change
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(load_ron::<Settings>(SETTINGS_FILE).unwrap_or_default())
+            .add_system(save_on_change::<Settings>(SETTINGS_FILE));
+    }
+}
+
+pub const SETTINGS_FILE: &str = "settings.ron";
+/// How much a volume changes with a single press in the options
+pub const VOLUME_STEP: f32 = 0.1;
+/// How much the interface size changes with a single press in the options
+pub const UI_SCALE_STEP: f32 = 0.1;
+pub const MIN_UI_SCALE: f32 = 0.5;
+pub const MAX_UI_SCALE: f32 = 2.0;
+/// Name of the theme used until the player picks another one
+pub const DEFAULT_THEME: &str = "Default";
+
+#[derive(Resource, Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub master_volume: f32,
+    pub music_volume: f32,
+    pub sfx_volume: f32,
+    pub ui_volume: f32,
+    pub ambience_volume: f32,
+    /// Name of the interface theme, see [`crate::ui::theme`]
+    pub theme: String,
+    /// Colorblind and high contrast colors laid over the theme
+    pub palette: Palette,
+    /// Replaces sliding and falling with instant transitions
+    pub reduced_motion: bool,
+    /// Interface size on top of the scaling to the window size, see [`crate::ui::scale`]
+    pub ui_scale: f32,
+    /// Language identifier of the translation, like `en-US`
+    pub language: String,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            master_volume: 0.8,
+            music_volume: 0.6,
+            sfx_volume: 0.8,
+            ui_volume: 0.8,
+            ambience_volume: 0.5,
+            theme: String::from(DEFAULT_THEME),
+            palette: Palette::default(),
+            reduced_motion: false,
+            ui_scale: 1.0,
+            language: String::from(DEFAULT_LANGUAGE),
+        }
+    }
+}
+
+/// The separately controllable volumes
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum VolumeSetting {
+    Master,
+    Music,
+    Sfx,
+    Ui,
+    Ambience,
+}
+
+impl VolumeSetting {
+    pub const ALL: [VolumeSetting; 5] = [
+        VolumeSetting::Master,
+        VolumeSetting::Music,
+        VolumeSetting::Sfx,
+        VolumeSetting::Ui,
+        VolumeSetting::Ambience,
+    ];
+
+    /// Id of the name of the volume in the translations
+    pub fn get_locale_key(&self) -> &'static str {
+        return match self {
+            VolumeSetting::Master => "volume-master",
+            VolumeSetting::Music => "volume-music",
+            VolumeSetting::Sfx => "volume-sfx",
+            VolumeSetting::Ui => "volume-ui",
+            VolumeSetting::Ambience => "volume-ambience",
+        };
+    }
+}
+
+impl Settings {
+    pub fn get_volume(&self, setting: VolumeSetting) -> f32 {
+        return match setting {
+            VolumeSetting::Master => self.master_volume,
+            VolumeSetting::Music => self.music_volume,
+            VolumeSetting::Sfx => self.sfx_volume,
+            VolumeSetting::Ui => self.ui_volume,
+            VolumeSetting::Ambience => self.ambience_volume,
+        };
+    }
+
+    pub fn adjust_volume(&mut self, setting: VolumeSetting, amount: f32) {
+        // round to avoid drifting away from the steps shown in the options
+        let volume = ((self.get_volume(setting) + amount) * 100.0).round() / 100.0;
+        let volume = volume.clamp(0.0, 1.0);
+        match setting {
+            VolumeSetting::Master => self.master_volume = volume,
+            VolumeSetting::Music => self.music_volume = volume,
+            VolumeSetting::Sfx => self.sfx_volume = volume,
+            VolumeSetting::Ui => self.ui_volume = volume,
+            VolumeSetting::Ambience => self.ambience_volume = volume,
+        }
+    }
+
+    pub fn adjust_ui_scale(&mut self, amount: f32) {
+        // round for the same reason as the volumes
+        let ui_scale = ((self.ui_scale + amount) * 100.0).round() / 100.0;
+        self.ui_scale = ui_scale.clamp(MIN_UI_SCALE, MAX_UI_SCALE);
+    }
+
+    /// The volume a channel should play at, taking the master volume into account
+    pub fn get_channel_volume(&self, setting: VolumeSetting) -> f64 {
+        return match setting {
+            VolumeSetting::Master => self.master_volume as f64,
+            _ => (self.master_volume * self.get_volume(setting)) as f64,
+        };
+    }
+}
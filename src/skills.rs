@@ -0,0 +1,272 @@
+//! The shopkeeper earns experience from every barter and gets a skill point with every level.
+//! Points are spent in the skill tree on skills that make one kind of haggle work more often or
+//! move the price further, unlock the charm haggle, give customers more patience or show the
+//! budget of the customer on the barter screen. The progression is saved in [`SKILLS_FILE`] and
+//! carries over between runs, only the daily challenge is played without any skills
+
+use crate::barter::{
+    BarterResolutionTypes, BarterResolved, HaggleResult, HaggleResultEvent, HaggleType,
+};
+use crate::persistence::{load_ron, save_on_change};
+use crate::run::RunMode;
+use crate::GameState;
+use bevy::prelude::{App, Commands, EventReader, Plugin, Res, ResMut, Resource};
+use iyes_loopless::prelude::{AppLooplessStateExt, ConditionSet};
+use serde::{Deserialize, Serialize};
+
+pub struct SkillsPlugin;
+
+impl Plugin for SkillsPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(load_ron::<Progression>(SKILLS_FILE).unwrap_or_default())
+            .init_resource::<ActiveSkills>()
+            .add_enter_system(GameState::Playing, activate_skills)
+            .add_system(save_on_change::<Progression>(SKILLS_FILE))
+            .add_system_set(
+                ConditionSet::new()
+                    .run_in_state(GameState::Playing)
+                    .with_system(gain_haggle_experience)
+                    .with_system(gain_sale_experience)
+                    .into(),
+            );
+    }
+}
+
+pub const SKILLS_FILE: &str = "skills.ron";
+
+/// Experience for every haggle that worked
+const HAGGLE_EXPERIENCE: u32 = 5;
+/// Experience for every item that was sold
+const SALE_EXPERIENCE: u32 = 10;
+/// Experience needed to get from the first to the second level, every level after needs this
+/// much more than the one before
+const LEVEL_EXPERIENCE: u32 = 100;
+/// How much the odds skills lower the resistance of customers, out of 100
+const ODDS_BONUS: i32 = 10;
+/// How much further the swing skills move the price on a successful haggle, in percent
+const SWING_BONUS: u32 = 50;
+/// How much longer customers put up with haggling with the patience skill
+const PATIENCE_BONUS: u32 = 3;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+pub enum Skill {
+    BullyOdds,
+    BullySwing,
+    PersuadeOdds,
+    PersuadeSwing,
+    PleaOdds,
+    PleaSwing,
+    /// Unlocks [`HaggleType::Charm`]
+    Charm,
+    Patience,
+    /// Shows the budget of the customer on the barter screen
+    KeenEye,
+    /// Narrows down the estimates of appraising a customer
+    Appraiser,
+}
+
+impl Skill {
+    pub const ALL: [Skill; 10] = [
+        Skill::BullyOdds,
+        Skill::BullySwing,
+        Skill::PersuadeOdds,
+        Skill::PersuadeSwing,
+        Skill::PleaOdds,
+        Skill::PleaSwing,
+        Skill::Charm,
+        Skill::Patience,
+        Skill::KeenEye,
+        Skill::Appraiser,
+    ];
+
+    /// Id of the name of the skill in the translations, the description is the same id followed
+    /// by `-description`
+    pub fn get_locale_key(&self) -> &'static str {
+        return match self {
+            Skill::BullyOdds => "skill-bully-odds",
+            Skill::BullySwing => "skill-bully-swing",
+            Skill::PersuadeOdds => "skill-persuade-odds",
+            Skill::PersuadeSwing => "skill-persuade-swing",
+            Skill::PleaOdds => "skill-plea-odds",
+            Skill::PleaSwing => "skill-plea-swing",
+            Skill::Charm => "skill-charm",
+            Skill::Patience => "skill-patience",
+            Skill::KeenEye => "skill-keen-eye",
+            Skill::Appraiser => "skill-appraiser",
+        };
+    }
+
+    /// How many skill points learning the skill takes
+    pub fn get_cost(&self) -> u32 {
+        return match self {
+            Skill::BullyOdds | Skill::PersuadeOdds | Skill::PleaOdds | Skill::Patience => 1,
+            Skill::BullySwing
+            | Skill::PersuadeSwing
+            | Skill::PleaSwing
+            | Skill::KeenEye
+            | Skill::Appraiser => 2,
+            Skill::Charm => 3,
+        };
+    }
+
+    /// The skills that have to be learned before this one
+    pub fn get_requirements(&self) -> &'static [Skill] {
+        return match self {
+            Skill::BullySwing => &[Skill::BullyOdds],
+            Skill::PersuadeSwing => &[Skill::PersuadeOdds],
+            Skill::PleaSwing => &[Skill::PleaOdds],
+            Skill::Charm => &[Skill::PersuadeOdds, Skill::PleaOdds],
+            Skill::KeenEye | Skill::Appraiser => &[Skill::Patience],
+            Skill::BullyOdds | Skill::PersuadeOdds | Skill::PleaOdds | Skill::Patience => &[],
+        };
+    }
+
+    fn get_odds_skill(haggle_type: &HaggleType) -> Option<Skill> {
+        return match haggle_type {
+            HaggleType::Bully => Some(Skill::BullyOdds),
+            HaggleType::Persuade => Some(Skill::PersuadeOdds),
+            HaggleType::Plea => Some(Skill::PleaOdds),
+            HaggleType::Charm => None,
+        };
+    }
+
+    fn get_swing_skill(haggle_type: &HaggleType) -> Option<Skill> {
+        return match haggle_type {
+            HaggleType::Bully => Some(Skill::BullySwing),
+            HaggleType::Persuade => Some(Skill::PersuadeSwing),
+            HaggleType::Plea => Some(Skill::PleaSwing),
+            HaggleType::Charm => None,
+        };
+    }
+}
+
+/// The level of the shopkeeper and the skills they learned, saved in [`SKILLS_FILE`]
+#[derive(Resource, Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Progression {
+    pub level: u32,
+    /// Experience gathered since the last level up
+    pub experience: u32,
+    /// Skill points that haven't been spent yet
+    pub points: u32,
+    pub skills: Vec<Skill>,
+}
+
+impl Default for Progression {
+    fn default() -> Self {
+        Progression {
+            level: 1,
+            experience: 0,
+            points: 0,
+            skills: vec![],
+        }
+    }
+}
+
+impl Progression {
+    pub fn get_experience_to_next_level(&self) -> u32 {
+        LEVEL_EXPERIENCE * self.level
+    }
+
+    /// Adds the experience, going up as many levels as it is enough for
+    pub fn add_experience(&mut self, amount: u32) {
+        self.experience += amount;
+        while self.experience >= self.get_experience_to_next_level() {
+            self.experience -= self.get_experience_to_next_level();
+            self.level += 1;
+            self.points += 1;
+        }
+    }
+
+    pub fn has_skill(&self, skill: Skill) -> bool {
+        self.skills.contains(&skill)
+    }
+
+    pub fn has_requirements(&self, skill: Skill) -> bool {
+        skill
+            .get_requirements()
+            .iter()
+            .all(|requirement| self.has_skill(*requirement))
+    }
+
+    pub fn can_learn(&self, skill: Skill) -> bool {
+        !self.has_skill(skill) && self.has_requirements(skill) && self.points >= skill.get_cost()
+    }
+
+    /// Spends the points on the skill. Returns false if it can't be learned yet
+    pub fn learn(&mut self, skill: Skill) -> bool {
+        if !self.can_learn(skill) {
+            return false;
+        }
+        self.points -= skill.get_cost();
+        self.skills.push(skill);
+        true
+    }
+}
+
+/// The skills that work in the current run
+#[derive(Resource, Clone, PartialEq, Eq, Debug, Default)]
+pub struct ActiveSkills {
+    skills: Vec<Skill>,
+}
+
+impl ActiveSkills {
+    pub fn has(&self, skill: Skill) -> bool {
+        self.skills.contains(&skill)
+    }
+
+    /// How much lower the resistance of customers is to the haggle
+    pub fn get_resistance_bonus(&self, haggle_type: &HaggleType) -> i32 {
+        match Skill::get_odds_skill(haggle_type) {
+            Some(skill) if self.has(skill) => ODDS_BONUS,
+            _ => 0,
+        }
+    }
+
+    /// How far a successful haggle of the kind moves the price, from the step of the difficulty
+    pub fn get_price_step(&self, haggle_type: &HaggleType, price_step: u32) -> u32 {
+        match Skill::get_swing_skill(haggle_type) {
+            Some(skill) if self.has(skill) => price_step * (100 + SWING_BONUS) / 100,
+            _ => price_step,
+        }
+    }
+
+    pub fn get_patience_bonus(&self) -> u32 {
+        if self.has(Skill::Patience) {
+            PATIENCE_BONUS
+        } else {
+            0
+        }
+    }
+}
+
+/// Everyone plays the daily challenge with the same rules, so it leaves out the skills
+fn activate_skills(mut commands: Commands, progression: Res<Progression>, run_mode: Res<RunMode>) {
+    let skills = match *run_mode {
+        RunMode::Daily => vec![],
+        _ => progression.skills.clone(),
+    };
+    commands.insert_resource(ActiveSkills { skills });
+}
+
+fn gain_haggle_experience(
+    mut events: EventReader<HaggleResultEvent>,
+    mut progression: ResMut<Progression>,
+) {
+    for event in events.iter() {
+        if event.result == HaggleResult::Success {
+            progression.add_experience(HAGGLE_EXPERIENCE);
+        }
+    }
+}
+
+fn gain_sale_experience(
+    mut events: EventReader<BarterResolved>,
+    mut progression: ResMut<Progression>,
+) {
+    for event in events.iter() {
+        if let BarterResolutionTypes::Approve { .. } = event.resolution_type {
+            progression.add_experience(SALE_EXPERIENCE);
+        }
+    }
+}
@@ -0,0 +1,232 @@
+use crate::achievements::{AchievementList, AchievementUnlocked, Achievements};
+use crate::loading::AchievementAssets;
+use crate::localization::{get_localized, Localization};
+use crate::ui::widgets::{spawn_screen_root, ButtonWidget, LabelWidget, PanelWidget};
+use crate::ui::{UiColors, UiState, UiStyle};
+use bevy::prelude::*;
+use iyes_loopless::prelude::{AppLooplessStateExt, ConditionSet, NextState};
+use std::time::Duration;
+
+pub struct AchievementsUiPlugin;
+
+/// This plugin is responsible for the toasts shown when an achievement is unlocked and for the
+/// achievement gallery opened from the main menu. The gallery is only drawn during the State
+/// `UiState::Achievements`
+impl Plugin for AchievementsUiPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_enter_system(UiState::Achievements, setup_gallery_ui)
+            .add_exit_system(UiState::Achievements, cleanup_gallery_ui)
+            .add_system(despawn_toasts)
+            .add_system_set(
+                ConditionSet::new()
+                    .run_on_event::<AchievementUnlocked>()
+                    .with_system(spawn_toasts)
+                    .into(),
+            )
+            .add_system_set(
+                ConditionSet::new()
+                    .run_in_state(UiState::Achievements)
+                    .with_system(click_gallery_button)
+                    .into(),
+            );
+    }
+}
+
+/// How long a toast stays on screen
+const TOAST_DURATION: f32 = 4.0;
+/// How far toasts shown at the same time are apart
+const TOAST_SPACING: f32 = 90.0;
+
+#[derive(Component, Default, PartialEq, Clone)]
+pub struct GalleryUi;
+
+#[derive(Component, Default, PartialEq, Clone)]
+pub struct GalleryBackButton;
+
+#[derive(Component)]
+pub struct AchievementToast {
+    timer: Timer,
+}
+
+#[allow(clippy::too_many_arguments)]
+fn spawn_toasts(
+    mut commands: Commands,
+    mut events: EventReader<AchievementUnlocked>,
+    toasts: Query<(), With<AchievementToast>>,
+    achievement_assets: Res<AchievementAssets>,
+    lists: Res<Assets<AchievementList>>,
+    ui_style: Res<UiStyle>,
+    colors: Res<UiColors>,
+    localization: Res<Localization>,
+) {
+    let list = match lists.get(&achievement_assets.achievements) {
+        Some(list) => list,
+        None => return,
+    };
+    // new toasts go below the ones that are still shown
+    let mut index = toasts.iter().count();
+    for event in events.iter() {
+        let achievement = match list.get(&event.id) {
+            Some(achievement) => achievement,
+            None => continue,
+        };
+        commands
+            .spawn(NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    position: UiRect {
+                        right: Val::Px(30.0),
+                        top: Val::Px(30.0 + TOAST_SPACING * index as f32),
+                        ..default()
+                    },
+                    ..default()
+                },
+                z_index: ZIndex::Global(10),
+                ..default()
+            })
+            .insert(AchievementToast {
+                timer: Timer::new(Duration::from_secs_f32(TOAST_DURATION), TimerMode::Once),
+            })
+            .with_children(|parent| {
+                PanelWidget::new(Val::Auto, Val::Auto)
+                    .with_padding(10.0)
+                    .spawn(parent, &colors)
+                    .with_children(|parent| {
+                        LabelWidget::new(localization.get("achievements-unlocked"))
+                            .small()
+                            .spawn(parent, &ui_style, &colors);
+                        LabelWidget::new(get_localized(
+                            &achievement.name,
+                            localization.get_language(),
+                        ))
+                        .spawn(parent, &ui_style, &colors);
+                    });
+            });
+        index += 1;
+    }
+}
+
+fn despawn_toasts(
+    mut commands: Commands,
+    mut toasts: Query<(Entity, &mut AchievementToast)>,
+    time: Res<Time>,
+) {
+    for (entity, mut toast) in toasts.iter_mut() {
+        toast.timer.tick(time.delta());
+        if toast.timer.finished() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+fn setup_gallery_ui(
+    mut commands: Commands,
+    achievements: Res<Achievements>,
+    achievement_assets: Res<AchievementAssets>,
+    lists: Res<Assets<AchievementList>>,
+    ui_style: Res<UiStyle>,
+    colors: Res<UiColors>,
+    localization: Res<Localization>,
+) {
+    let list = match lists.get(&achievement_assets.achievements) {
+        Some(list) => list,
+        None => return,
+    };
+    let unlocked = list
+        .achievements
+        .iter()
+        .filter(|achievement| achievements.is_unlocked(&achievement.id))
+        .count();
+    let language = localization.get_language();
+
+    spawn_screen_root(&mut commands)
+        .insert(GalleryUi)
+        .with_children(|parent| {
+            PanelWidget::new(Val::Percent(60.0), Val::Percent(85.0))
+                .absolute()
+                .with_justify(JustifyContent::FlexStart)
+                .with_padding(15.0)
+                .spawn(parent, &colors)
+                .with_children(|parent| {
+                    LabelWidget::title(localization.get("achievements-title"))
+                        .spawn(parent, &ui_style, &colors);
+                    LabelWidget::new(localization.get_with(
+                        "achievements-progress",
+                        &[
+                            ("unlocked", unlocked.into()),
+                            ("total", list.achievements.len().into()),
+                        ],
+                    ))
+                    .small()
+                    .spawn(parent, &ui_style, &colors);
+
+                    PanelWidget::new(Val::Percent(100.0), Val::Auto)
+                        .with_justify(JustifyContent::FlexStart)
+                        .with_padding(15.0)
+                        .with_margin(UiRect::vertical(Val::Px(10.0)))
+                        .with_color(colors.panel_dark)
+                        .spawn(parent, &colors)
+                        .with_children(|parent| {
+                            for achievement in list.achievements.iter() {
+                                let is_unlocked = achievements.is_unlocked(&achievement.id);
+                                let (name, description) = if is_unlocked || !achievement.hidden {
+                                    (
+                                        get_localized(&achievement.name, language).to_string(),
+                                        get_localized(&achievement.description, language)
+                                            .to_string(),
+                                    )
+                                } else {
+                                    (
+                                        localization.get("achievements-hidden-name"),
+                                        localization.get("achievements-hidden-description"),
+                                    )
+                                };
+                                let color = if is_unlocked {
+                                    colors.success
+                                } else {
+                                    colors.text_light
+                                };
+                                LabelWidget::new(name)
+                                    .with_color(color)
+                                    .spawn(parent, &ui_style, &colors);
+                                LabelWidget::new(description)
+                                    .small()
+                                    .with_color(colors.text_light)
+                                    .with_style(Style {
+                                        margin: UiRect {
+                                            bottom: Val::Px(8.0),
+                                            ..default()
+                                        },
+                                        ..default()
+                                    })
+                                    .spawn(parent, &ui_style, &colors);
+                            }
+                        });
+
+                    ButtonWidget::new(localization.get("ui-back"))
+                        .with_margin(UiRect {
+                            top: Val::Auto,
+                            ..default()
+                        })
+                        .spawn(parent, &ui_style, &colors)
+                        .insert(GalleryBackButton);
+                });
+        });
+}
+
+fn click_gallery_button(
+    mut commands: Commands,
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<GalleryBackButton>)>,
+) {
+    for interaction in &interaction_query {
+        if let Interaction::Clicked = interaction {
+            commands.insert_resource(NextState(UiState::Normal));
+        }
+    }
+}
+
+fn cleanup_gallery_ui(mut commands: Commands, ui: Query<Entity, With<GalleryUi>>) {
+    for ui in ui.iter() {
+        commands.entity(ui).despawn_recursive();
+    }
+}
@@ -0,0 +1,264 @@
+use crate::campaign::CampaignSave;
+use crate::difficulty::{Difficulty, DifficultyPreset, DifficultyValue};
+use crate::localization::Localization;
+use crate::run::RunMode;
+use crate::ui::widgets::{spawn_screen_root, ButtonWidget, LabelWidget, PanelWidget};
+use crate::ui::{UiColors, UiState, UiStyle};
+use crate::GameState;
+use bevy::prelude::*;
+use iyes_loopless::prelude::{AppLooplessStateExt, ConditionSet, NextState};
+
+pub struct DifficultyUiPlugin;
+
+/// This plugin is responsible for the difficulty screen shown between picking a mode in the main
+/// menu and starting the run, the screen is only drawn during the State `UiState::Difficulty`
+impl Plugin for DifficultyUiPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_enter_system(UiState::Difficulty, setup_difficulty_ui)
+            .add_exit_system(UiState::Difficulty, cleanup_difficulty_ui)
+            .add_system_set(
+                ConditionSet::new()
+                    .run_in_state(UiState::Difficulty)
+                    .with_system(click_difficulty_button)
+                    .with_system(update_difficulty_text)
+                    .into(),
+            );
+    }
+}
+
+#[derive(Component, Default, PartialEq, Clone)]
+pub struct DifficultyUi;
+
+/// A row of the difficulty screen
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DifficultyRow {
+    Preset,
+    Value(DifficultyValue),
+}
+
+#[derive(Component, PartialEq, Clone)]
+pub struct DifficultyValueText {
+    row: DifficultyRow,
+}
+
+#[derive(Component, PartialEq, Clone)]
+pub struct DifficultyButtonProps {
+    difficulty_button_type: DifficultyButtonType,
+}
+
+#[derive(PartialEq, Clone)]
+pub enum DifficultyButtonType {
+    Decrease(DifficultyRow),
+    Increase(DifficultyRow),
+    Start,
+    Back,
+}
+
+impl DifficultyButtonProps {
+    pub fn get_button_text(&self, localization: &Localization) -> String {
+        return match self.difficulty_button_type {
+            DifficultyButtonType::Decrease(DifficultyRow::Preset) => String::from("<"),
+            DifficultyButtonType::Increase(DifficultyRow::Preset) => String::from(">"),
+            DifficultyButtonType::Decrease(_) => String::from("-"),
+            DifficultyButtonType::Increase(_) => String::from("+"),
+            DifficultyButtonType::Start => localization.get("difficulty-start"),
+            DifficultyButtonType::Back => localization.get("ui-back"),
+        };
+    }
+}
+
+fn get_value_text(
+    row: DifficultyRow,
+    difficulty: &Difficulty,
+    localization: &Localization,
+) -> String {
+    return match row {
+        DifficultyRow::Preset => localization.get_with(
+            "difficulty-preset",
+            &[(
+                "preset",
+                localization.get(difficulty.preset.get_locale_key()).into(),
+            )],
+        ),
+        DifficultyRow::Value(value) => localization.get_with(
+            value.get_locale_key(),
+            &[("value", difficulty.values.get(value).into())],
+        ),
+    };
+}
+
+fn setup_difficulty_ui(
+    mut commands: Commands,
+    ui_style: Res<UiStyle>,
+    colors: Res<UiColors>,
+    difficulty: Res<Difficulty>,
+    localization: Res<Localization>,
+) {
+    // only the custom difficulty can be changed value by value
+    let custom = difficulty.preset == DifficultyPreset::Custom;
+    let mut rows = vec![DifficultyRow::Preset];
+    rows.extend(
+        DifficultyValue::ALL
+            .iter()
+            .map(|value| DifficultyRow::Value(*value)),
+    );
+
+    spawn_screen_root(&mut commands)
+        .insert(DifficultyUi)
+        .with_children(|parent| {
+            PanelWidget::new(Val::Percent(40.0), Val::Auto)
+                .absolute()
+                .with_padding(15.0)
+                .spawn(parent, &colors)
+                .with_children(|parent| {
+                    LabelWidget::title(localization.get("difficulty-title"))
+                        .spawn(parent, &ui_style, &colors);
+
+                    for row in rows {
+                        let adjustable = row == DifficultyRow::Preset || custom;
+                        PanelWidget::new(Val::Percent(100.0), Val::Auto)
+                            .row()
+                            .with_margin(UiRect::vertical(Val::Px(5.0)))
+                            .spawn(parent, &colors)
+                            .with_children(|parent| {
+                                if adjustable {
+                                    spawn_difficulty_button(
+                                        parent,
+                                        DifficultyButtonType::Decrease(row),
+                                        &ui_style,
+                                        &colors,
+                                        &localization,
+                                    );
+                                }
+                                LabelWidget::new(get_value_text(row, &difficulty, &localization))
+                                    .spawn(parent, &ui_style, &colors)
+                                    .insert(DifficultyValueText { row });
+                                if adjustable {
+                                    spawn_difficulty_button(
+                                        parent,
+                                        DifficultyButtonType::Increase(row),
+                                        &ui_style,
+                                        &colors,
+                                        &localization,
+                                    );
+                                }
+                            });
+                    }
+
+                    PanelWidget::new(Val::Percent(100.0), Val::Auto)
+                        .row()
+                        .with_margin(UiRect {
+                            top: Val::Px(10.0),
+                            ..default()
+                        })
+                        .spawn(parent, &colors)
+                        .with_children(|parent| {
+                            spawn_difficulty_button(
+                                parent,
+                                DifficultyButtonType::Back,
+                                &ui_style,
+                                &colors,
+                                &localization,
+                            );
+                            spawn_difficulty_button(
+                                parent,
+                                DifficultyButtonType::Start,
+                                &ui_style,
+                                &colors,
+                                &localization,
+                            );
+                        });
+                });
+        });
+}
+
+fn spawn_difficulty_button(
+    parent: &mut ChildBuilder,
+    difficulty_button_type: DifficultyButtonType,
+    ui_style: &Res<UiStyle>,
+    colors: &Res<UiColors>,
+    localization: &Res<Localization>,
+) -> Entity {
+    let props = DifficultyButtonProps {
+        difficulty_button_type,
+    };
+    ButtonWidget::new(props.get_button_text(localization))
+        .small()
+        .spawn(parent, ui_style, colors)
+        .insert(props)
+        .id()
+}
+
+fn update_difficulty_text(
+    mut texts: Query<(&mut Text, &DifficultyValueText)>,
+    difficulty: Res<Difficulty>,
+    localization: Res<Localization>,
+) {
+    if !difficulty.is_changed() {
+        return;
+    }
+    for (mut text, value_text) in texts.iter_mut() {
+        text.sections[0].value = get_value_text(value_text.row, &difficulty, &localization);
+    }
+}
+
+fn click_difficulty_button(
+    mut commands: Commands,
+    interaction_query: Query<
+        (&Interaction, &DifficultyButtonProps),
+        (Changed<Interaction>, With<Button>),
+    >,
+    mut difficulty: ResMut<Difficulty>,
+    run_mode: Res<RunMode>,
+    mut campaign_save: ResMut<CampaignSave>,
+) {
+    for (interaction, props) in &interaction_query {
+        if let Interaction::Clicked = interaction {
+            match props.difficulty_button_type {
+                DifficultyButtonType::Decrease(row) => {
+                    adjust_row(row, -1, &mut difficulty, &mut commands);
+                }
+                DifficultyButtonType::Increase(row) => {
+                    adjust_row(row, 1, &mut difficulty, &mut commands);
+                }
+                DifficultyButtonType::Start => {
+                    if *run_mode == RunMode::Campaign {
+                        campaign_save.start_new(&difficulty);
+                    }
+                    commands.insert_resource(NextState(UiState::Normal));
+                    commands.insert_resource(NextState(GameState::Playing));
+                }
+                DifficultyButtonType::Back => {
+                    commands.insert_resource(NextState(UiState::Normal));
+                }
+            }
+        }
+    }
+}
+
+/// Steps the preset or a value up or down
+fn adjust_row(
+    row: DifficultyRow,
+    direction: i32,
+    difficulty: &mut Difficulty,
+    commands: &mut Commands,
+) {
+    match row {
+        DifficultyRow::Preset => {
+            let preset = difficulty.preset.cycle(direction);
+            difficulty.set_preset(preset);
+            // the values can only be changed one by one with the custom preset, so the screen is
+            // drawn again with or without their buttons
+            commands.insert_resource(NextState(UiState::Difficulty));
+        }
+        DifficultyRow::Value(value) => {
+            difficulty.adjust(value, direction);
+        }
+    }
+}
+
+fn cleanup_difficulty_ui(mut commands: Commands, ui: Query<Entity, With<DifficultyUi>>) {
+    for ui in ui.iter() {
+        commands.entity(ui).despawn_recursive();
+    }
+}
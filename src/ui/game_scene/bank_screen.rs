@@ -0,0 +1,253 @@
+use crate::bank::{Bank, BankAction, DEPOSIT_INTEREST_RATE, DEPOSIT_STEP, LOAN_OFFERS};
+use crate::difficulty::Difficulty;
+use crate::localization::Localization;
+use crate::ui::widgets::{spawn_screen_root, ButtonWidget, LabelWidget, PanelWidget};
+use crate::ui::{UiColors, UiState, UiStyle};
+use crate::PausedState;
+use bevy::prelude::*;
+use iyes_loopless::prelude::{AppLooplessStateExt, ConditionSet, NextState};
+
+pub struct BankUiPlugin;
+
+/// This plugin is responsible for the bank screen where the player manages loans and deposits
+/// The screen is only drawn during the State `UiState::Bank` and is removed when that state is exited
+impl Plugin for BankUiPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_enter_system(UiState::Bank, setup_bank_ui)
+            .add_exit_system(UiState::Bank, cleanup_bank_ui)
+            .add_system_set(
+                ConditionSet::new()
+                    .run_in_state(UiState::Bank)
+                    .run_in_state(PausedState::Playing)
+                    .with_system(click_bank_button)
+                    .with_system(update_bank_text)
+                    .into(),
+            );
+    }
+}
+
+/// How many of the most recent transactions are shown in the history
+const SHOWN_HISTORY: usize = 8;
+
+#[derive(Component, Default, PartialEq, Clone)]
+pub struct BankUi;
+
+#[derive(Component, Default, PartialEq, Clone)]
+pub struct BankSummaryText;
+
+#[derive(Component, Default, PartialEq, Clone)]
+pub struct BankHistoryText;
+
+#[derive(Component, PartialEq, Clone)]
+pub struct BankButtonProps {
+    bank_button_type: BankButtonType,
+}
+
+#[derive(PartialEq, Clone)]
+pub enum BankButtonType {
+    Action(BankAction),
+    Close,
+}
+
+impl BankButtonProps {
+    pub fn get_button_text(&self, localization: &Localization, difficulty: &Difficulty) -> String {
+        return match self.bank_button_type {
+            BankButtonType::Action(BankAction::TakeLoan(index)) => {
+                let offer = difficulty.scale_loan(&LOAN_OFFERS[index]);
+                localization.get_with(
+                    "bank-borrow",
+                    &[
+                        ("principal", offer.principal.into()),
+                        ("interest", (offer.interest_rate * 100.0).round().into()),
+                        ("nights", offer.nights.into()),
+                    ],
+                )
+            }
+            BankButtonType::Action(BankAction::Deposit(amount)) => {
+                localization.get_with("bank-deposit", &[("amount", amount.into())])
+            }
+            BankButtonType::Action(BankAction::Withdraw(amount)) => {
+                localization.get_with("bank-withdraw", &[("amount", amount.into())])
+            }
+            BankButtonType::Action(BankAction::PayOverdue) => localization.get("bank-pay-overdue"),
+            BankButtonType::Close => localization.get("ui-close"),
+        };
+    }
+}
+
+fn get_summary_text(bank: &Bank, localization: &Localization) -> String {
+    localization.get_with(
+        "bank-summary",
+        &[
+            ("deposit", bank.deposit.into()),
+            ("interest", (DEPOSIT_INTEREST_RATE * 100.0).round().into()),
+            ("owed", bank.total_owed().into()),
+            ("overdue", bank.total_overdue().into()),
+        ],
+    )
+}
+
+fn get_history_text(bank: &Bank, localization: &Localization) -> String {
+    if bank.history.is_empty() {
+        return localization.get("bank-no-history");
+    }
+    bank.history
+        .iter()
+        .rev()
+        .take(SHOWN_HISTORY)
+        .map(|transaction| {
+            localization.get_with(
+                "bank-history-entry",
+                &[
+                    ("night", transaction.night.into()),
+                    ("kind", localization.get(transaction.kind.get_locale_key()).into()),
+                    ("amount", transaction.amount.into()),
+                ],
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+fn setup_bank_ui(
+    mut commands: Commands,
+    ui_style: Res<UiStyle>,
+    colors: Res<UiColors>,
+    bank: Res<Bank>,
+    difficulty: Res<Difficulty>,
+    localization: Res<Localization>,
+) {
+    spawn_screen_root(&mut commands)
+        .insert(BankUi)
+        .with_children(|parent| {
+            PanelWidget::new(Val::Percent(70.0), Val::Percent(80.0))
+                .row()
+                .absolute()
+                .spawn(parent, &colors)
+                .with_children(|parent| {
+                    // left side with the account and the actions
+                    PanelWidget::new(Val::Percent(50.0), Val::Percent(100.0))
+                        .with_padding(15.0)
+                        .spawn(parent, &colors)
+                        .with_children(|parent| {
+                            LabelWidget::new(get_summary_text(&bank, &localization))
+                                .spawn(parent, &ui_style, &colors)
+                                .insert(BankSummaryText);
+
+                            for index in 0..LOAN_OFFERS.len() {
+                                spawn_bank_button(
+                                    parent,
+                                    BankButtonType::Action(BankAction::TakeLoan(index)),
+                                    &ui_style,
+                                    &colors,
+                                    &localization,
+                                    &difficulty,
+                                );
+                            }
+                            spawn_bank_button(
+                                parent,
+                                BankButtonType::Action(BankAction::Deposit(DEPOSIT_STEP)),
+                                &ui_style,
+                                &colors,
+                                &localization,
+                                &difficulty,
+                            );
+                            spawn_bank_button(
+                                parent,
+                                BankButtonType::Action(BankAction::Withdraw(DEPOSIT_STEP)),
+                                &ui_style,
+                                &colors,
+                                &localization,
+                                &difficulty,
+                            );
+                            spawn_bank_button(
+                                parent,
+                                BankButtonType::Action(BankAction::PayOverdue),
+                                &ui_style,
+                                &colors,
+                                &localization,
+                                &difficulty,
+                            );
+                            spawn_bank_button(
+                                parent,
+                                BankButtonType::Close,
+                                &ui_style,
+                                &colors,
+                                &localization,
+                                &difficulty,
+                            );
+                        });
+
+                    // right side with the transaction history
+                    PanelWidget::new(Val::Percent(50.0), Val::Percent(100.0))
+                        .with_justify(JustifyContent::FlexStart)
+                        .with_padding(15.0)
+                        .with_color(colors.panel_dark)
+                        .spawn(parent, &colors)
+                        .with_children(|parent| {
+                            LabelWidget::new(get_history_text(&bank, &localization))
+                                .small()
+                                .with_color(colors.text_light)
+                                .spawn(parent, &ui_style, &colors)
+                                .insert(BankHistoryText);
+                        });
+                });
+        });
+}
+
+fn spawn_bank_button(
+    parent: &mut ChildBuilder,
+    bank_button_type: BankButtonType,
+    ui_style: &Res<UiStyle>,
+    colors: &Res<UiColors>,
+    localization: &Localization,
+    difficulty: &Difficulty,
+) -> Entity {
+    let props = BankButtonProps { bank_button_type };
+    ButtonWidget::new(props.get_button_text(localization, difficulty))
+        .small()
+        .with_width(Val::Percent(100.0))
+        .spawn(parent, ui_style, colors)
+        .insert(props)
+        .id()
+}
+
+fn update_bank_text(
+    mut summary: Query<&mut Text, (With<BankSummaryText>, Without<BankHistoryText>)>,
+    mut history: Query<&mut Text, (With<BankHistoryText>, Without<BankSummaryText>)>,
+    bank: Res<Bank>,
+    localization: Res<Localization>,
+) {
+    if !bank.is_changed() {
+        return;
+    }
+    for mut text in summary.iter_mut() {
+        text.sections[0].value = get_summary_text(&bank, &localization);
+    }
+    for mut text in history.iter_mut() {
+        text.sections[0].value = get_history_text(&bank, &localization);
+    }
+}
+
+fn click_bank_button(
+    mut commands: Commands,
+    interaction_query: Query<(&Interaction, &BankButtonProps), (Changed<Interaction>, With<Button>)>,
+    mut bank_actions: EventWriter<BankAction>,
+) {
+    for (interaction, props) in &interaction_query {
+        if let Interaction::Clicked = interaction {
+            match props.bank_button_type {
+                BankButtonType::Action(action) => bank_actions.send(action),
+                BankButtonType::Close => {
+                    commands.insert_resource(NextState(UiState::Normal));
+                }
+            }
+        }
+    }
+}
+
+fn cleanup_bank_ui(mut commands: Commands, ui: Query<Entity, With<BankUi>>) {
+    for ui in ui.iter() {
+        commands.entity(ui).despawn_recursive();
+    }
+}
@@ -1,13 +1,22 @@
-﻿use crate::barter::{HaggleResult, HaggleResultEvent, BarterResolutionTypes, HaggleType, HaggleAttemptEvent, BarterState, Barter};
-use crate::loading::FontAssets;
-use crate::ui::{UiColors, UiState};
+﻿use crate::barter::{HaggleResult, HaggleResultEvent, BarterResolutionTypes, HaggleType, HaggleAttemptEvent, BarterState, Barter, BarterResolved, CustomerOutOfPatience};
+use crate::loading::TextureAssets;
+use crate::localization::Localization;
+use crate::settings::Settings;
+use crate::skills::{ActiveSkills, Skill};
+use crate::ui::game_scene::speech_bubbles::spawn_barter_speech_bubble;
+use crate::ui::motion::slide_tween;
+use crate::ui::widgets::{
+    spawn_screen_root, ButtonWidget, CardWidget, LabelWidget, PanelWidget, ProgressBar,
+    ProgressBarWidget,
+};
+use crate::ui::{UiColors, UiState, UiStyle};
 use crate::PausedState;
 use bevy::prelude::*;
 use iyes_loopless::prelude::{AppLooplessStateExt, ConditionSet, NextState};
 
-use crate::barter::customers::{CustomerHandler, CustomerState};
-use bevy_tweening::lens::UiPositionLens;
-use bevy_tweening::{Animator, EaseFunction, Tween, TweenCompleted};
+use crate::barter::appraisal::{Appraisal, AppraiseEvent, APPRAISED_HAGGLES};
+use crate::barter::customers::{CustomerHandler, CustomerState, MaxPurchaseAmount};
+use bevy_tweening::{Animator, EaseFunction, TweenCompleted};
 use crate::player::Gold;
 
 pub struct BarterUiPlugin;
@@ -32,15 +41,27 @@ impl Plugin for BarterUiPlugin {
                     .run_in_state(PausedState::Playing)
                     .with_system(click_barter_control_button)
                     .with_system(click_barter_button)
+                    .with_system(click_appraise_button)
+                    .with_system(update_patience_bar)
+                    .with_system(update_appraisal_text)
                     .with_system(tween_out_barter_ui)
                     .with_system(cleanup_barter_ui)
                     .into(),
+            )
+            .add_system_set(
+                ConditionSet::new()
+                    .run_in_state(UiState::Barter)
+                    .run_on_event::<CustomerOutOfPatience>()
+                    .with_system(customer_walks_away)
+                    .into(),
             );
     }
 }
 
 const BARTER_UI_TRANSITION_DONE: u64 = 1;
 const BARTER_RESOLUTION_CARD_TRANSITION_DONE: u64 = 2;
+/// Below this much of their patience the patience bar turns red
+const PATIENCE_WARNING: f32 = 0.35;
 
 #[derive(Component, Clone, PartialEq, Default)]
 pub struct ResolutionUiParent;
@@ -59,10 +80,10 @@ pub struct BarterControlButtonProps {
 }
 
 impl BarterControlButtonProps {
-    pub fn get_button_text(&self) -> String {
+    pub fn get_button_text(&self, localization: &Localization) -> String {
         return match self.control_button_type {
-            BarterResolutionTypes::Approve { amount } => String::from("Approve"),
-            BarterResolutionTypes::Deny => String::from("Deny"),
+            BarterResolutionTypes::Approve { amount } => localization.get("barter-approve"),
+            BarterResolutionTypes::Deny => localization.get("barter-deny"),
         };
     }
 }
@@ -73,205 +94,138 @@ pub struct BarterUi;
 #[derive(Component, Default, PartialEq, Clone)]
 pub struct BarterResultCard;
 
+/// The bar showing how much patience the customer has left
+#[derive(Component, Default, PartialEq, Clone)]
+pub struct PatienceBar;
+
+#[derive(Component, Default, PartialEq, Clone)]
+pub struct AppraiseButton;
+
+/// What the player found out by appraising the customer, in the middle of the barter screen
+#[derive(Component, Default, PartialEq, Clone)]
+pub struct AppraisalText;
+
+/// How far below the window the barter screen has to be to be out of sight. Ui values are scaled
+/// by the [`UiScale`], the window height isn't
+fn get_offscreen_bottom(window: &Window, ui_scale: &UiScale) -> Val {
+    Val::Px(-window.height() / ui_scale.scale as f32)
+}
+
+#[allow(clippy::too_many_arguments)]
 fn setup_barter_ui(
     mut commands: Commands,
-    font_assets: Res<FontAssets>,
+    ui_style: Res<UiStyle>,
     colors: Res<UiColors>,
+    settings: Res<Settings>,
+    localization: Res<Localization>,
+    skills: Res<ActiveSkills>,
+    customer_handler: Res<CustomerHandler>,
+    budgets: Query<&MaxPurchaseAmount>,
     mut windows: ResMut<Windows>,
+    ui_scale: Res<UiScale>,
 ) {
+    // only shopkeepers with a keen eye can tell how much a customer has on them
+    let budget = customer_handler
+        .get_next_customer()
+        .and_then(|customer| budgets.get(customer).ok())
+        .filter(|_| skills.has(Skill::KeenEye))
+        .map(|budget| budget.max);
     let window = windows.primary();
-    let tween = Tween::new(
+    let tween = slide_tween(
+        &settings,
         EaseFunction::QuadraticIn,
-        std::time::Duration::from_secs_f32(0.7),
-        UiPositionLens {
-            start: UiRect {
-                left: Val::Auto,
-                top: Val::Auto,
-                right: Val::Auto,
-                bottom: Val::Px(-window.height()),
-            },
-            end: UiRect {
-                left: Val::Auto,
-                top: Val::Auto,
-                right: Val::Auto,
-                bottom: Val::Px(0.0),
-            },
+        0.7,
+        UiRect {
+            left: Val::Auto,
+            top: Val::Auto,
+            right: Val::Auto,
+            bottom: get_offscreen_bottom(window, &ui_scale),
+        },
+        UiRect {
+            left: Val::Auto,
+            top: Val::Auto,
+            right: Val::Auto,
+            bottom: Val::Px(0.0),
         },
     );
 
     // root bundle
-    commands
-        .spawn(NodeBundle {
-            style: Style {
-                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
-                justify_content: JustifyContent::Center,
-                align_items: AlignItems::Center,
-                position_type: PositionType::Absolute,
-                ..default()
-            },
-            ..default()
-        })
+    spawn_screen_root(&mut commands)
         .insert(Animator::new(tween))
         .insert(BarterUi)
         .with_children(|parent| {
             // main background and holder for barter ui
-
-            parent
-                .spawn(NodeBundle {
-                    style: Style {
-                        size: Size::new(Val::Percent(75.0), Val::Percent(80.0)),
-                        justify_content: JustifyContent::SpaceBetween,
-                        position_type: PositionType::Absolute,
-                        ..default()
-                    },
-                    background_color: Color::rgb(0.65, 0.65, 0.65).into(),
-                    ..default()
-                })
+            PanelWidget::new(Val::Percent(75.0), Val::Percent(80.0))
+                .row()
+                .absolute()
+                .spawn(parent, &colors)
                 .with_children(|parent| {
                     // left side of barter screen
-                    setup_left_barter_screen(parent, &font_assets, &colors);
+                    setup_left_barter_screen(parent, &ui_style, &colors, &localization, &skills, budget);
 
                     // center of barter screen
-                    setup_middle_barter_screen(parent);
+                    setup_middle_barter_screen(parent, &ui_style, &colors);
 
                     // right side of barter screen
-                    setup_right_barter_screen(parent, &font_assets, &colors);
+                    setup_right_barter_screen(parent, &ui_style, &colors, &localization);
                 });
         });
 }
 
 fn setup_left_barter_screen(
     parent: &mut ChildBuilder,
-    font_assets: &Res<FontAssets>,
+    ui_style: &Res<UiStyle>,
     colors: &Res<UiColors>,
+    localization: &Localization,
+    skills: &ActiveSkills,
+    budget: Option<u32>,
 ) -> Entity {
-    parent
-        .spawn(NodeBundle {
-            style: Style {
-                size: Size::new(Val::Percent(20.0), Val::Percent(100.0)),
-                justify_content: JustifyContent::SpaceBetween,
-                position_type: PositionType::Relative,
-                ..default()
-            },
-            background_color: Color::rgb(0.65, 0.65, 0.65).into(),
-            ..default()
-        })
+    let mut barter_button_types = vec![HaggleType::Bully, HaggleType::Plea, HaggleType::Persuade];
+    if skills.has(Skill::Charm) {
+        barter_button_types.push(HaggleType::Charm);
+    }
+
+    PanelWidget::new(Val::Percent(20.0), Val::Percent(100.0))
+        .spawn(parent, colors)
         .with_children(|parent| {
             // Bottom meta options
-            parent
-                .spawn(NodeBundle {
-                    style: Style {
-                        size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
-                        justify_content: JustifyContent::FlexEnd,
-                        position_type: PositionType::Relative,
-                        flex_direction: FlexDirection::Column,
-                        ..default()
-                    },
-                    background_color: Color::rgb(0.65, 0.65, 0.65).into(),
-                    ..default()
-                })
+            PanelWidget::new(Val::Percent(100.0), Val::Percent(100.0))
+                .with_justify(JustifyContent::FlexEnd)
+                .spawn(parent, colors)
                 .with_children(|parent| {
-                    parent
-                        .spawn(ButtonBundle {
-                            style: Style {
-                                size: Size::new(Val::Percent(100.0), Val::Auto),
-                                padding: UiRect::all(Val::Px(15.0)),
-                                justify_content: JustifyContent::Center,
-                                align_items: AlignItems::Center,
-                                ..Default::default()
-                            },
-                            background_color: colors.button_standard.into(),
-                            ..Default::default()
-                        })
-                        .insert(BarterButtonProps {
-                            barter_button_type: HaggleType::Bully,
-                        })
-                        .with_children(|parent| {
-                            parent.spawn(TextBundle {
-                                text: Text {
-                                    sections: vec![TextSection {
-                                        value: HaggleType::get_string_name(HaggleType::Bully),
-                                        style: TextStyle {
-                                            font: font_assets.fira_sans.clone(),
-                                            font_size: 40.0,
-                                            color: Color::rgb(0.9, 0.9, 0.9),
-                                        },
-                                    }],
-                                    alignment: Default::default(),
-                                },
-                                ..Default::default()
-                            });
-                        });
-
-                    parent
-                        .spawn(ButtonBundle {
-                            style: Style {
-                                size: Size::new(Val::Percent(100.0), Val::Auto),
-                                padding: UiRect::all(Val::Px(15.0)),
-                                justify_content: JustifyContent::Center,
-                                align_items: AlignItems::Center,
-                                ..Default::default()
-                            },
-                            background_color: colors.button_standard.into(),
-                            ..Default::default()
-                        })
-                        .insert(BarterButtonProps {
-                            barter_button_type: HaggleType::Plea,
-                        })
-                        .with_children(|parent| {
-                            parent.spawn(TextBundle {
-                                text: Text {
-                                    sections: vec![TextSection {
-                                        value: HaggleType::get_string_name(HaggleType::Plea),
-                                        style: TextStyle {
-                                            font: font_assets.fira_sans.clone(),
-                                            font_size: 40.0,
-                                            color: Color::rgb(0.9, 0.9, 0.9),
-                                        },
-                                    }],
-                                    alignment: Default::default(),
-                                },
-                                ..Default::default()
-                            });
-                        });
-
-                    parent
-                        .spawn(ButtonBundle {
-                            style: Style {
-                                size: Size::new(Val::Percent(100.0), Val::Auto),
-                                padding: UiRect::all(Val::Px(15.0)),
-                                justify_content: JustifyContent::Center,
-                                align_items: AlignItems::Center,
-                                ..Default::default()
-                            },
-                            background_color: colors.button_standard.into(),
-                            ..Default::default()
-                        })
-                        .insert(BarterButtonProps {
-                            barter_button_type: HaggleType::Persuade,
-                        })
-                        .with_children(|parent| {
-                            parent.spawn(TextBundle {
-                                text: Text {
-                                    sections: vec![TextSection {
-                                        value: HaggleType::get_string_name(HaggleType::Persuade),
-                                        style: TextStyle {
-                                            font: font_assets.fira_sans.clone(),
-                                            font_size: 40.0,
-                                            color: Color::rgb(0.9, 0.9, 0.9),
-                                        },
-                                    }],
-                                    alignment: Default::default(),
-                                },
-                                ..Default::default()
-                            });
-                        });
+                    if let Some(budget) = budget {
+                        LabelWidget::new(localization.get_with("barter-budget", &[("budget", budget.into())]))
+                            .small()
+                            .spawn(parent, ui_style, colors);
+                    }
+                    LabelWidget::new(localization.get("barter-patience")).spawn(parent, ui_style, colors);
+                    ProgressBarWidget::new(1.0)
+                        .with_warning_below(PATIENCE_WARNING)
+                        .spawn(parent, colors)
+                        .insert(PatienceBar);
+
+                    for barter_button_type in barter_button_types
+                    {
+                        ButtonWidget::new(localization.get(barter_button_type.get_locale_key()))
+                            .with_width(Val::Percent(100.0))
+                            .spawn(parent, ui_style, colors)
+                            .insert(BarterButtonProps { barter_button_type });
+                    }
+
+                    ButtonWidget::new(localization.get("barter-appraise"))
+                        .with_width(Val::Percent(100.0))
+                        .spawn(parent, ui_style, colors)
+                        .insert(AppraiseButton);
                 });
         })
         .id()
 }
 
-fn setup_middle_barter_screen(parent: &mut ChildBuilder) -> Entity {
+fn setup_middle_barter_screen(
+    parent: &mut ChildBuilder,
+    ui_style: &Res<UiStyle>,
+    colors: &Res<UiColors>,
+) -> Entity {
     parent
         .spawn(NodeBundle {
             style: Style {
@@ -283,186 +237,53 @@ fn setup_middle_barter_screen(parent: &mut ChildBuilder) -> Entity {
                 flex_shrink: 0.0,
                 ..default()
             },
-            background_color: Color::rgb(0.1, 0.1, 0.1).into(),
+            background_color: colors.panel_dark.into(),
             ..default()
         })
         .insert(ResolutionUiParent)
+        .with_children(|parent| {
+            // empty until the customer is appraised
+            LabelWidget::new("")
+                .small()
+                .with_color(colors.text_light)
+                .with_style(Style {
+                    position_type: PositionType::Absolute,
+                    position: UiRect {
+                        left: Val::Px(10.0),
+                        top: Val::Px(10.0),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .spawn(parent, ui_style, colors)
+                .insert(AppraisalText);
+            spawn_barter_speech_bubble(parent, ui_style, colors);
+        })
         .id()
 }
 
 fn setup_right_barter_screen(
     parent: &mut ChildBuilder,
-    font_assets: &Res<FontAssets>,
+    ui_style: &Res<UiStyle>,
     colors: &Res<UiColors>,
+    localization: &Localization,
 ) -> Entity {
-    let entity = parent
-        .spawn(NodeBundle {
-            style: Style {
-                size: Size::new(Val::Percent(20.0), Val::Percent(100.0)),
-                justify_content: JustifyContent::SpaceBetween,
-                position_type: PositionType::Relative,
-                flex_direction: FlexDirection::Column,
-                ..default()
-            },
-            background_color: Color::rgb(0.65, 0.65, 0.65).into(),
-            ..default()
-        })
+    let entity = PanelWidget::new(Val::Percent(20.0), Val::Percent(100.0))
+        .spawn(parent, colors)
         .with_children(|parent| {
             // top right side stuff
-            parent
-                .spawn(NodeBundle {
-                    style: Style {
-                        size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
-                        justify_content: JustifyContent::FlexStart,
-                        position_type: PositionType::Relative,
-                        flex_direction: FlexDirection::Column,
-                        ..default()
-                    },
-                    background_color: Color::rgb(0.65, 0.65, 0.65).into(),
-                    ..default()
-                })
+            PanelWidget::new(Val::Percent(100.0), Val::Percent(100.0))
+                .with_justify(JustifyContent::FlexStart)
+                .spawn(parent, colors)
                 .with_children(|parent| {
-                    parent
-                        .spawn(ButtonBundle {
-                            style: Style {
-                                size: Size::new(Val::Percent(100.0), Val::Auto),
-                                padding: UiRect::all(Val::Px(15.0)),
-                                justify_content: JustifyContent::Center,
-                                align_items: AlignItems::Center,
-                                ..Default::default()
-                            },
-                            background_color: colors.button_standard.into(),
-                            ..Default::default()
-                        })
-                        .insert(BarterControlButtonProps {
-                            control_button_type: BarterResolutionTypes::Approve { amount: 0 },
-                        })
-                        .with_children(|parent| {
-                            parent.spawn(TextBundle {
-                                text: Text {
-                                    sections: vec![TextSection {
-                                        value: "Approve".to_string(),
-                                        style: TextStyle {
-                                            font: font_assets.fira_sans.clone(),
-                                            font_size: 40.0,
-                                            color: Color::rgb(0.9, 0.9, 0.9),
-                                        },
-                                    }],
-                                    alignment: Default::default(),
-                                },
-                                ..Default::default()
-                            });
-                        });
-
-                    parent
-                        .spawn(ButtonBundle {
-                            style: Style {
-                                size: Size::new(Val::Percent(100.0), Val::Auto),
-                                padding: UiRect::all(Val::Px(15.0)),
-                                justify_content: JustifyContent::Center,
-                                align_items: AlignItems::Center,
-                                ..Default::default()
-                            },
-                            background_color: colors.button_standard.into(),
-                            ..Default::default()
-                        })
-                        .insert(BarterControlButtonProps {
-                            control_button_type: BarterResolutionTypes::Deny,
-                        })
-                        .with_children(|parent| {
-                            parent.spawn(TextBundle {
-                                text: Text {
-                                    sections: vec![TextSection {
-                                        value: "Deny".to_string(),
-                                        style: TextStyle {
-                                            font: font_assets.fira_sans.clone(),
-                                            font_size: 40.0,
-                                            color: Color::rgb(0.9, 0.9, 0.9),
-                                        },
-                                    }],
-                                    alignment: Default::default(),
-                                },
-                                ..Default::default()
-                            });
-                        });
+                    spawn_barter_control_buttons(parent, ui_style, colors, localization);
                 });
             // Bottom meta options
-            parent
-                .spawn(NodeBundle {
-                    style: Style {
-                        size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
-                        justify_content: JustifyContent::FlexEnd,
-                        position_type: PositionType::Relative,
-                        flex_direction: FlexDirection::Column,
-                        ..default()
-                    },
-                    background_color: Color::rgb(0.65, 0.65, 0.65).into(),
-                    ..default()
-                })
+            PanelWidget::new(Val::Percent(100.0), Val::Percent(100.0))
+                .with_justify(JustifyContent::FlexEnd)
+                .spawn(parent, colors)
                 .with_children(|parent| {
-                    parent
-                        .spawn(ButtonBundle {
-                            style: Style {
-                                size: Size::new(Val::Percent(100.0), Val::Auto),
-                                padding: UiRect::all(Val::Px(15.0)),
-                                justify_content: JustifyContent::Center,
-                                align_items: AlignItems::Center,
-                                ..Default::default()
-                            },
-                            background_color: colors.success.into(),
-                            ..Default::default()
-                        })
-                        .insert(BarterControlButtonProps {
-                            control_button_type: BarterResolutionTypes::Approve { amount: 0 },
-                        })
-                        .with_children(|parent| {
-                            parent.spawn(TextBundle {
-                                text: Text {
-                                    sections: vec![TextSection {
-                                        value: "Approve".to_string(),
-                                        style: TextStyle {
-                                            font: font_assets.fira_sans.clone(),
-                                            font_size: 40.0,
-                                            color: Color::rgb(0.9, 0.9, 0.9),
-                                        },
-                                    }],
-                                    alignment: Default::default(),
-                                },
-                                ..Default::default()
-                            });
-                        });
-
-                    parent
-                        .spawn(ButtonBundle {
-                            style: Style {
-                                size: Size::new(Val::Percent(100.0), Val::Auto),
-                                padding: UiRect::all(Val::Px(15.0)),
-                                justify_content: JustifyContent::Center,
-                                align_items: AlignItems::Center,
-                                ..Default::default()
-                            },
-                            background_color: colors.failure.into(),
-                            ..Default::default()
-                        })
-                        .insert(BarterControlButtonProps {
-                            control_button_type: BarterResolutionTypes::Deny,
-                        })
-                        .with_children(|parent| {
-                            parent.spawn(TextBundle {
-                                text: Text {
-                                    sections: vec![TextSection {
-                                        value: "Deny".to_string(),
-                                        style: TextStyle {
-                                            font: font_assets.fira_sans.clone(),
-                                            font_size: 40.0,
-                                            color: Color::rgb(0.9, 0.9, 0.9),
-                                        },
-                                    }],
-                                    alignment: Default::default(),
-                                },
-                                ..Default::default()
-                            });
-                        });
+                    spawn_barter_control_buttons(parent, ui_style, colors, localization);
                 });
         })
         .id();
@@ -470,34 +291,57 @@ fn setup_right_barter_screen(
     entity
 }
 
+fn spawn_barter_control_buttons(
+    parent: &mut ChildBuilder,
+    ui_style: &Res<UiStyle>,
+    colors: &Res<UiColors>,
+    localization: &Localization,
+) {
+    for control_button_type in [
+        BarterResolutionTypes::Approve { amount: 0 },
+        BarterResolutionTypes::Deny,
+    ] {
+        let props = BarterControlButtonProps {
+            control_button_type,
+        };
+        ButtonWidget::new(props.get_button_text(localization))
+            .with_width(Val::Percent(100.0))
+            .spawn(parent, ui_style, colors)
+            .insert(props);
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 fn spawn_barter_result(
     parent: Query<Entity, With<ResolutionUiParent>>,
     mut commands: Commands,
     mut result: EventReader<HaggleResultEvent>,
-    font_assets: Res<FontAssets>,
+    ui_style: Res<UiStyle>,
     colors: Res<UiColors>,
+    texture_assets: Res<TextureAssets>,
+    settings: Res<Settings>,
+    localization: Res<Localization>,
     mut query: Query<(Entity, &mut Style), With<BarterResultCard>>,
 ) {
     let parent = parent.single();
 
     for event in result.iter() {
         for (entity, mut style) in query.iter_mut() {
-            let tween = Tween::new(
+            let tween = slide_tween(
+                &settings,
                 EaseFunction::QuadraticIn,
-                std::time::Duration::from_secs_f32(0.3),
-                UiPositionLens {
-                    start: UiRect {
-                        left: Val::Auto,
-                        top: Val::Auto,
-                        right: Val::Auto,
-                        bottom: Val::Px(-170.0),
-                    },
-                    end: UiRect {
-                        left: Val::Auto,
-                        top: Val::Auto,
-                        right: Val::Auto,
-                        bottom: Val::Px(0.0),
-                    },
+                0.3,
+                UiRect {
+                    left: Val::Auto,
+                    top: Val::Auto,
+                    right: Val::Auto,
+                    bottom: Val::Px(-170.0),
+                },
+                UiRect {
+                    left: Val::Auto,
+                    top: Val::Auto,
+                    right: Val::Auto,
+                    bottom: Val::Px(0.0),
                 },
             );
 
@@ -512,38 +356,39 @@ fn spawn_barter_result(
         }
 
         commands.entity(parent).with_children(|parent| {
-            let attempt_type_text = event.attempt_type.get_string_name_from_instance();
-            let price_text = event.new_price;
+            let attempt_type_text = localization.get(event.attempt_type.get_locale_key());
+            let price_text = localization
+                .get_with("barter-new-price", &[("price", event.new_price.into())]);
             let mut color = colors.success;
-            let mut result_text = String::from("Success");
+            let mut icon = texture_assets.icon_success.clone();
+            let mut result_key = "barter-result-success";
             let mut justify_content_type: JustifyContent = JustifyContent::FlexStart;
 
             if let HaggleResult::Failure = event.result {
                 color = colors.failure;
-                result_text = String::from("Failure");
+                icon = texture_assets.icon_failure.clone();
+                result_key = "barter-result-failure";
                 justify_content_type = JustifyContent::FlexEnd;
             }
 
-            let tween = Tween::new(
+            let tween = slide_tween(
+                &settings,
                 EaseFunction::QuadraticIn,
-                std::time::Duration::from_secs_f32(0.3),
-                UiPositionLens {
-                    start: UiRect {
-                        left: Val::Auto,
-                        top: Val::Auto,
-                        right: Val::Auto,
-                        bottom: Val::Px(-170.0),
-                    },
-                    end: UiRect {
-                        left: Val::Auto,
-                        top: Val::Auto,
-                        right: Val::Auto,
-                        bottom: Val::Px(0.0),
-                    },
+                0.3,
+                UiRect {
+                    left: Val::Auto,
+                    top: Val::Auto,
+                    right: Val::Auto,
+                    bottom: Val::Px(-170.0),
+                },
+                UiRect {
+                    left: Val::Auto,
+                    top: Val::Auto,
+                    right: Val::Auto,
+                    bottom: Val::Px(0.0),
                 },
             );
 
-            // spawn in two parts so that its split up and we have a border
             parent
                 // main holder node
                 .spawn(NodeBundle {
@@ -567,109 +412,14 @@ fn spawn_barter_result(
                 .insert(BarterResultCard)
                 .insert(Animator::new(tween))
                 .with_children(|parent| {
-                    parent
-                        // big holder that is used as our border
-                        .spawn(NodeBundle {
-                            style: Style {
-                                size: Size::new(Val::Percent(65.0), Val::Percent(100.0)),
-                                justify_content: JustifyContent::Center,
-                                align_items: AlignItems::Center,
-                                position_type: PositionType::Relative,
-                                ..default()
-                            },
-                            background_color: color.into(),
-                            ..default()
-                        })
-                        .with_children(|parent| {
-                            // actual interior that we want to use
-                            parent
-                                .spawn(NodeBundle {
-                                    style: Style {
-                                        size: Size::new(Val::Percent(95.0), Val::Percent(80.0)),
-                                        justify_content: JustifyContent::Center,
-                                        align_items: AlignItems::Center,
-                                        flex_direction: FlexDirection::Column,
-                                        position_type: PositionType::Relative,
-                                        ..default()
-                                    },
-                                    background_color: Color::rgb(1.0, 1.0, 1.0).into(),
-                                    ..default()
-                                })
-                                .with_children(|parent| {
-                                    // Result text
-                                    parent
-                                        .spawn(NodeBundle {
-                                            style: Style {
-                                                size: Size::new(Val::Percent(100.0), Val::Auto),
-                                                padding: UiRect {
-                                                    left: Val::Px(20.0),
-                                                    right: Val::Px(20.0),
-                                                    top: Val::Px(10.0),
-                                                    bottom: Val::Px(20.0),
-                                                },
-                                                justify_content: JustifyContent::FlexStart,
-                                                align_items: AlignItems::Center,
-                                                position_type: PositionType::Relative,
-                                                ..default()
-                                            },
-                                            background_color: color.into(),
-                                            ..default()
-                                        })
-                                        .with_children(|parent| {
-                                            parent.spawn(TextBundle {
-                                                text: Text {
-                                                    sections: vec![TextSection {
-                                                        value: String::from(format!(
-                                                            "{} {}!",
-                                                            attempt_type_text, result_text
-                                                        )),
-                                                        style: TextStyle {
-                                                            font: font_assets.fira_sans.clone(),
-                                                            font_size: 40.0,
-                                                            color: Color::rgb(1.0, 1.0, 1.0),
-                                                        },
-                                                    }],
-                                                    alignment: Default::default(),
-                                                },
-                                                ..Default::default()
-                                            });
-                                        });
-
-                                    // amount
-                                    parent
-                                        .spawn(NodeBundle {
-                                            style: Style {
-                                                size: Size::new(Val::Percent(100.0), Val::Auto),
-                                                padding: UiRect::all(Val::Px(20.0)),
-                                                align_items: AlignItems::Center,
-                                                justify_content: JustifyContent::FlexStart,
-                                                position_type: PositionType::Relative,
-                                                ..default()
-                                            },
-                                            background_color: Color::rgb(1.0, 1.0, 1.0).into(),
-                                            ..default()
-                                        })
-                                        .with_children(|parent| {
-                                            parent.spawn(TextBundle {
-                                                text: Text {
-                                                    sections: vec![TextSection {
-                                                        value: String::from(format!(
-                                                            "New Price: {} gold",
-                                                            price_text
-                                                        )),
-                                                        style: TextStyle {
-                                                            font: font_assets.fira_sans.clone(),
-                                                            font_size: 40.0,
-                                                            color: Color::rgb(0.0, 0.0, 0.0),
-                                                        },
-                                                    }],
-                                                    alignment: Default::default(),
-                                                },
-                                                ..Default::default()
-                                            });
-                                        });
-                                });
-                        });
+                    CardWidget::new(
+                        localization
+                            .get_with(result_key, &[("haggle", attempt_type_text.into())]),
+                        price_text,
+                        color,
+                    )
+                    .with_icon(icon)
+                    .spawn(parent, &ui_style, &colors);
                 });
         });
     }
@@ -677,48 +427,42 @@ fn spawn_barter_result(
 
 fn click_barter_control_button(
     mut commands: Commands,
-    button_colors: Res<UiColors>,
-    mut interaction_query: Query<
-        (
-            &Interaction,
-            &mut BackgroundColor,
-            &BarterControlButtonProps,
-        ),
+    interaction_query: Query<
+        (&Interaction, &BarterControlButtonProps),
         (Changed<Interaction>, With<Button>),
     >,
     mut close_ui: EventWriter<CloseBarterUi>,
+    mut barter_resolved: EventWriter<BarterResolved>,
     mut gold: ResMut<Gold>,
     mut barter: ResMut<Barter>
 ) {
-    for (interaction, mut color, props) in &mut interaction_query {
-        match *interaction {
-            Interaction::Hovered => match props.control_button_type {
-                BarterResolutionTypes::Approve { .. } => {
-                    *color = button_colors.success_hovered.into();
-                }
-                BarterResolutionTypes::Deny => {
-                    *color = button_colors.failure_hovered.into();
-                }
-            },
-            Interaction::None => match props.control_button_type {
-                BarterResolutionTypes::Approve { .. } => {
-                    *color = button_colors.success.into();
-                }
-                BarterResolutionTypes::Deny => {
-                    *color = button_colors.failure.into();
-                }
-            },
-            _ => {}
-        }
+    for (interaction, props) in &interaction_query {
         match props.control_button_type {
             BarterResolutionTypes::Approve { .. } => {
                 if let Interaction::Clicked = interaction {
+                    // the screen is still sliding out after the first click
+                    if !barter.resolve() {
+                        continue;
+                    }
                     gold.amount = gold.amount.saturating_add(barter.get_price());
+                    barter_resolved.send(BarterResolved {
+                        resolution_type: BarterResolutionTypes::Approve {
+                            amount: barter.get_price(),
+                        },
+                        item: barter.get_item(),
+                    });
                     close_ui.send_default();
                 }
             }
             BarterResolutionTypes::Deny => {
                 if let Interaction::Clicked = interaction {
+                    if !barter.resolve() {
+                        continue;
+                    }
+                    barter_resolved.send(BarterResolved {
+                        resolution_type: BarterResolutionTypes::Deny,
+                        item: barter.get_item(),
+                    });
                     close_ui.send_default();
                 }
             }
@@ -728,23 +472,18 @@ fn click_barter_control_button(
 
 fn click_barter_button(
     mut commands: Commands,
-    button_colors: Res<UiColors>,
     mut barter_attempt: EventWriter<HaggleAttemptEvent>,
-    mut interaction_query: Query<
-        (&Interaction, &mut BackgroundColor, &BarterButtonProps),
+    interaction_query: Query<
+        (&Interaction, &BarterButtonProps),
         (Changed<Interaction>, With<Button>),
     >,
+    barter: Res<Barter>,
 ) {
-    for (interaction, mut color, props) in &mut interaction_query {
-        match *interaction {
-            Interaction::Hovered => {
-                *color = button_colors.button_hovered.into();
-            }
-            Interaction::None => {
-                *color = button_colors.button_standard.into();
-            }
-            _ => {}
-        }
+    // the screen is still sliding out, the barter is over
+    if barter.is_resolved() {
+        return;
+    }
+    for (interaction, props) in &interaction_query {
         match props.barter_button_type {
             HaggleType::Bully => {
                 if let Interaction::Clicked = interaction {
@@ -767,36 +506,142 @@ fn click_barter_button(
                     })
                 }
             }
+            HaggleType::Charm => {
+                if let Interaction::Clicked = interaction {
+                    barter_attempt.send(HaggleAttemptEvent {
+                        attempt_type: HaggleType::Charm,
+                    })
+                }
+            }
+        }
+    }
+}
+
+fn click_appraise_button(
+    mut appraise: EventWriter<AppraiseEvent>,
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<AppraiseButton>)>,
+    barter: Res<Barter>,
+) {
+    if barter.is_resolved() {
+        return;
+    }
+    for interaction in &interaction_query {
+        if let Interaction::Clicked = interaction {
+            appraise.send(AppraiseEvent);
         }
     }
 }
 
+fn get_appraisal_text(appraisal: &Appraisal, localization: &Localization) -> String {
+    let mut lines: Vec<String> = APPRAISED_HAGGLES
+        .iter()
+        .zip(appraisal.resistances.iter())
+        .map(|(haggle_type, resistance)| {
+            localization.get_with(
+                "barter-appraisal-resistance",
+                &[
+                    ("haggle", localization.get(haggle_type.get_locale_key()).into()),
+                    ("low", (*resistance.start()).into()),
+                    ("high", (*resistance.end()).into()),
+                ],
+            )
+        })
+        .collect();
+    lines.push(localization.get_with(
+        "barter-appraisal-budget",
+        &[
+            ("low", (*appraisal.budget.start()).into()),
+            ("high", (*appraisal.budget.end()).into()),
+        ],
+    ));
+    lines.join("\n")
+}
+
+fn update_appraisal_text(
+    barter: Option<Res<Barter>>,
+    mut texts: Query<&mut Text, With<AppraisalText>>,
+    localization: Res<Localization>,
+) {
+    let barter = match barter {
+        Some(barter) => barter,
+        None => return,
+    };
+    if !barter.is_changed() {
+        return;
+    }
+    let appraisal = match barter.get_appraisal() {
+        Some(appraisal) => appraisal,
+        None => return,
+    };
+    for mut text in texts.iter_mut() {
+        text.sections[0].value = get_appraisal_text(appraisal, &localization);
+    }
+}
+
+fn update_patience_bar(
+    barter: Option<Res<Barter>>,
+    mut bars: Query<&mut ProgressBar, With<PatienceBar>>,
+) {
+    let barter = match barter {
+        Some(barter) => barter,
+        None => return,
+    };
+    if !barter.is_changed() {
+        return;
+    }
+    for mut bar in bars.iter_mut() {
+        bar.value = barter.get_patience_fraction();
+    }
+}
+
+/// The customer had enough of the haggling and leaves without buying anything
+fn customer_walks_away(
+    mut events: EventReader<CustomerOutOfPatience>,
+    mut close_ui: EventWriter<CloseBarterUi>,
+    mut barter_resolved: EventWriter<BarterResolved>,
+    mut barter: ResMut<Barter>,
+) {
+    if events.iter().count() == 0 {
+        return;
+    }
+    // the player already approved or denied the barter before the customer got to leave
+    if !barter.resolve() {
+        return;
+    }
+    barter_resolved.send(BarterResolved {
+        resolution_type: BarterResolutionTypes::Deny,
+        item: barter.get_item(),
+    });
+    close_ui.send_default();
+}
+
 fn tween_out_barter_ui(
     mut commands: Commands,
     button: Query<Entity, With<BarterUi>>,
     mut close_ui: EventReader<CloseBarterUi>,
+    settings: Res<Settings>,
     mut windows: ResMut<Windows>,
+    ui_scale: Res<UiScale>,
 ) {
     let window = windows.primary();
 
     for event in close_ui.iter() {
         for button in button.iter() {
-            let tween = Tween::new(
+            let tween = slide_tween(
+                &settings,
                 EaseFunction::QuadraticIn,
-                std::time::Duration::from_secs_f32(0.7),
-                UiPositionLens {
-                    start: UiRect {
-                        left: Val::Auto,
-                        top: Val::Auto,
-                        right: Val::Auto,
-                        bottom: Val::Px(0.0),
-                    },
-                    end: UiRect {
-                        left: Val::Auto,
-                        top: Val::Auto,
-                        right: Val::Auto,
-                        bottom: Val::Px(-window.height()),
-                    },
+                0.7,
+                UiRect {
+                    left: Val::Auto,
+                    top: Val::Auto,
+                    right: Val::Auto,
+                    bottom: Val::Px(0.0),
+                },
+                UiRect {
+                    left: Val::Auto,
+                    top: Val::Auto,
+                    right: Val::Auto,
+                    bottom: get_offscreen_bottom(window, &ui_scale),
                 },
             )
             .with_completed_event(BARTER_UI_TRANSITION_DONE);
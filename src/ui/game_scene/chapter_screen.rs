@@ -0,0 +1,249 @@
+//! The screens between the chapters of the campaign, and the list of objectives shown in the shop
+//! while a chapter is being played
+
+use crate::campaign::{Campaign, CampaignProgress, ChapterScreen, ContinueCampaign};
+use crate::dialogue::tree::StoryFlags;
+use crate::loading::CampaignAssets;
+use crate::localization::{get_localized, LanguageChanged, Localization};
+use crate::night::{Night, NightEnded};
+use crate::player::Reputation;
+use crate::ui::widgets::{spawn_screen_root, ButtonWidget, LabelWidget, PanelWidget};
+use crate::ui::{UiColors, UiState, UiStyle};
+use crate::GameState;
+use bevy::prelude::*;
+use iyes_loopless::prelude::{AppLooplessStateExt, ConditionSet};
+
+pub struct ChapterUiPlugin;
+
+/// This plugin is responsible for the chapter screen and the objective tracker
+/// The chapter screen is only drawn during the State `UiState::Chapter` and is removed when that
+/// state is exited
+impl Plugin for ChapterUiPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_enter_system(UiState::Chapter, setup_chapter_ui)
+            .add_exit_system(UiState::Chapter, cleanup_chapter_ui)
+            .add_exit_system(GameState::Playing, cleanup_objective_tracker)
+            .add_system_set(
+                ConditionSet::new()
+                    .run_in_state(UiState::Chapter)
+                    .with_system(click_chapter_button)
+                    .into(),
+            )
+            .add_system_set(
+                ConditionSet::new()
+                    .run_in_state(GameState::Playing)
+                    .with_system(refresh_objective_tracker)
+                    .into(),
+            );
+    }
+}
+
+/// Text wider than this wraps onto the next line
+const CHAPTER_TEXT_WIDTH: f32 = 1080.0;
+
+#[derive(Component, Default, PartialEq, Clone)]
+pub struct ChapterUi;
+
+#[derive(Component, Default, PartialEq, Clone)]
+pub struct ChapterContinueButton;
+
+/// The objectives of the current chapter, below the gold in the top bar
+#[derive(Component, Default, PartialEq, Clone)]
+pub struct ObjectiveTracker;
+
+#[allow(clippy::too_many_arguments)]
+fn setup_chapter_ui(
+    mut commands: Commands,
+    screen: Option<Res<ChapterScreen>>,
+    progress: Option<Res<CampaignProgress>>,
+    campaign_assets: Res<CampaignAssets>,
+    campaigns: Res<Assets<Campaign>>,
+    reputation: Res<Reputation>,
+    night: Res<Night>,
+    ui_style: Res<UiStyle>,
+    colors: Res<UiColors>,
+    localization: Res<Localization>,
+) {
+    let (screen, progress, campaign) =
+        match (screen, progress, campaigns.get(&campaign_assets.campaign)) {
+            (Some(screen), Some(progress), Some(campaign)) => (*screen, progress, campaign),
+            _ => return,
+        };
+    let (title, text) = match campaign.get_screen_text(screen) {
+        Some(text) => text,
+        None => return,
+    };
+    let language = localization.get_language();
+
+    spawn_screen_root(&mut commands)
+        .insert(ChapterUi)
+        .with_children(|parent| {
+            PanelWidget::new(Val::Percent(60.0), Val::Percent(70.0))
+                .absolute()
+                .with_justify(JustifyContent::FlexStart)
+                .with_padding(15.0)
+                .spawn(parent, &colors)
+                .with_children(|parent| {
+                    LabelWidget::title(get_localized(title, language))
+                        .spawn(parent, &ui_style, &colors);
+                    LabelWidget::new(get_localized(text, language))
+                        .with_style(Style {
+                            margin: UiRect::vertical(Val::Px(20.0)),
+                            max_size: Size::new(Val::Px(CHAPTER_TEXT_WIDTH), Val::Undefined),
+                            ..default()
+                        })
+                        .spawn(parent, &ui_style, &colors);
+
+                    // the intro tells the player what the chapter is about
+                    if let ChapterScreen::Intro(chapter) = screen {
+                        if let Some(chapter) = campaign.chapters.get(chapter) {
+                            LabelWidget::new(localization.get("campaign-objectives"))
+                                .spawn(parent, &ui_style, &colors);
+                            for objective in chapter.objectives.iter() {
+                                LabelWidget::new(objective.get_text(
+                                    &progress,
+                                    &reputation,
+                                    &localization,
+                                ))
+                                .small()
+                                .spawn(parent, &ui_style, &colors);
+                            }
+                            LabelWidget::new(localization.get_with(
+                                "campaign-nights",
+                                &[("nights", progress.get_nights_left(chapter, &night).into())],
+                            ))
+                            .small()
+                            .with_style(Style {
+                                margin: UiRect::vertical(Val::Px(10.0)),
+                                ..default()
+                            })
+                            .spawn(parent, &ui_style, &colors);
+                        }
+                    }
+
+                    let button_text = match screen {
+                        ChapterScreen::Intro(_) | ChapterScreen::Outro(_) => {
+                            localization.get("campaign-continue")
+                        }
+                        ChapterScreen::Ending | ChapterScreen::Failed(_) => {
+                            localization.get("ui-main-menu")
+                        }
+                    };
+                    ButtonWidget::new(button_text)
+                        .with_margin(UiRect {
+                            top: Val::Auto,
+                            ..default()
+                        })
+                        .spawn(parent, &ui_style, &colors)
+                        .insert(ChapterContinueButton);
+                });
+        });
+}
+
+fn click_chapter_button(
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<ChapterContinueButton>)>,
+    mut continue_campaign: EventWriter<ContinueCampaign>,
+) {
+    for interaction in &interaction_query {
+        if let Interaction::Clicked = interaction {
+            continue_campaign.send_default();
+        }
+    }
+}
+
+fn cleanup_chapter_ui(mut commands: Commands, ui: Query<Entity, With<ChapterUi>>) {
+    for ui in ui.iter() {
+        commands.entity(ui).despawn_recursive();
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn refresh_objective_tracker(
+    mut commands: Commands,
+    trackers: Query<Entity, With<ObjectiveTracker>>,
+    progress: Option<Res<CampaignProgress>>,
+    campaign_assets: Res<CampaignAssets>,
+    campaigns: Res<Assets<Campaign>>,
+    reputation: Res<Reputation>,
+    flags: Res<StoryFlags>,
+    night: Res<Night>,
+    mut night_ended: EventReader<NightEnded>,
+    mut language_changed: EventReader<LanguageChanged>,
+    ui_style: Res<UiStyle>,
+    colors: Res<UiColors>,
+    localization: Res<Localization>,
+) {
+    let progress = match progress {
+        Some(progress) => progress,
+        None => return,
+    };
+    let night_ended = night_ended.iter().count() > 0;
+    let language_changed = language_changed.iter().count() > 0;
+    if !progress.is_changed()
+        && !reputation.is_changed()
+        && !flags.is_changed()
+        && !night_ended
+        && !language_changed
+    {
+        return;
+    }
+    let chapter = match campaigns
+        .get(&campaign_assets.campaign)
+        .and_then(|campaign| campaign.chapters.get(progress.chapter))
+    {
+        Some(chapter) => chapter,
+        None => return,
+    };
+
+    let tracker = match trackers.get_single() {
+        Ok(tracker) => {
+            commands.entity(tracker).despawn_descendants();
+            tracker
+        }
+        Err(_) => commands
+            .spawn(NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    position: UiRect {
+                        left: Val::Px(30.0),
+                        top: Val::Px(80.0),
+                        ..default()
+                    },
+                    flex_direction: FlexDirection::Column,
+                    ..default()
+                },
+                ..default()
+            })
+            .insert(ObjectiveTracker)
+            .id(),
+    };
+    commands.entity(tracker).with_children(|parent| {
+        LabelWidget::new(localization.get_with(
+            "campaign-nights-left",
+            &[("nights", progress.get_nights_left(chapter, &night).into())],
+        ))
+        .small()
+        .with_color(colors.text_light)
+        .spawn(parent, &ui_style, &colors);
+        for objective in chapter.objectives.iter() {
+            let color = if objective.is_complete(&progress, &reputation, &flags) {
+                colors.success
+            } else {
+                colors.text_light
+            };
+            LabelWidget::new(objective.get_text(&progress, &reputation, &localization))
+                .small()
+                .with_color(color)
+                .spawn(parent, &ui_style, &colors);
+        }
+    });
+}
+
+fn cleanup_objective_tracker(
+    mut commands: Commands,
+    trackers: Query<Entity, With<ObjectiveTracker>>,
+) {
+    for tracker in trackers.iter() {
+        commands.entity(tracker).despawn_recursive();
+    }
+}
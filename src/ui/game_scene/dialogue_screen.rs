@@ -0,0 +1,165 @@
+use crate::dialogue::tree::{
+    ActiveDialogue, ChooseDialogueOption, DialogueContext, DialogueTree, LeaveDialogue, StoryFlags,
+};
+use crate::localization::{get_localized, Localization};
+use crate::market::items::Inventory;
+use crate::player::{Gold, Reputation};
+use crate::settings::Settings;
+use crate::ui::game_scene::speech_bubbles::Typewriter;
+use crate::ui::widgets::{spawn_screen_root, ButtonWidget, LabelWidget, PanelWidget};
+use crate::ui::{UiColors, UiState, UiStyle};
+use crate::PausedState;
+use bevy::prelude::*;
+use iyes_loopless::prelude::{AppLooplessStateExt, ConditionSet};
+
+pub struct DialogueUiPlugin;
+
+/// This plugin is responsible for the dialogue screen shown while talking to a story character
+/// The screen is only drawn during the State `UiState::Dialogue` and is removed when that state is exited
+impl Plugin for DialogueUiPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_enter_system(UiState::Dialogue, setup_dialogue_ui)
+            .add_exit_system(UiState::Dialogue, cleanup_dialogue_ui)
+            .add_system_set(
+                ConditionSet::new()
+                    .run_in_state(UiState::Dialogue)
+                    .run_in_state(PausedState::Playing)
+                    .with_system(refresh_dialogue_ui)
+                    .with_system(click_dialogue_button)
+                    .into(),
+            );
+    }
+}
+
+/// Text wider than this wraps onto the next line
+const DIALOGUE_TEXT_WIDTH: f32 = 1080.0;
+
+#[derive(Component, Default, PartialEq, Clone)]
+pub struct DialogueUi;
+
+/// Holds the text and choices of the current node, filled in by refresh_dialogue_ui
+#[derive(Component, Default, PartialEq, Clone)]
+pub struct DialogueContent;
+
+#[derive(Component, PartialEq, Clone)]
+pub struct DialogueButtonProps {
+    dialogue_button_type: DialogueButtonType,
+}
+
+#[derive(PartialEq, Clone)]
+pub enum DialogueButtonType {
+    /// Index of the choice in the current node
+    Choice(usize),
+    Leave,
+}
+
+fn setup_dialogue_ui(mut commands: Commands, colors: Res<UiColors>) {
+    spawn_screen_root(&mut commands)
+        .insert(DialogueUi)
+        .with_children(|parent| {
+            PanelWidget::new(Val::Percent(60.0), Val::Percent(70.0))
+                .absolute()
+                .with_justify(JustifyContent::FlexStart)
+                .with_padding(15.0)
+                .spawn(parent, &colors)
+                .insert(DialogueContent);
+        });
+}
+
+#[allow(clippy::too_many_arguments)]
+fn refresh_dialogue_ui(
+    mut commands: Commands,
+    content: Query<Entity, With<DialogueContent>>,
+    dialogue: Option<Res<ActiveDialogue>>,
+    trees: Res<Assets<DialogueTree>>,
+    gold: Res<Gold>,
+    reputation: Res<Reputation>,
+    inventory: Res<Inventory>,
+    flags: Res<StoryFlags>,
+    ui_style: Res<UiStyle>,
+    colors: Res<UiColors>,
+    settings: Res<Settings>,
+    localization: Res<Localization>,
+) {
+    let dialogue = match dialogue {
+        Some(dialogue) if dialogue.is_changed() => dialogue,
+        _ => return,
+    };
+    let tree = match trees.get(&dialogue.tree) {
+        Some(tree) => tree,
+        None => return,
+    };
+    let node = match tree.nodes.get(&dialogue.node) {
+        Some(node) => node,
+        None => return,
+    };
+    let context = DialogueContext {
+        gold: &gold,
+        reputation: &reputation,
+        inventory: &inventory,
+        flags: &flags,
+    };
+    let choices = node.get_available_choices(&context);
+    let language = localization.get_language();
+
+    for content in content.iter() {
+        commands.entity(content).despawn_descendants();
+        commands.entity(content).with_children(|parent| {
+            LabelWidget::title(get_localized(&tree.name, language))
+                .spawn(parent, &ui_style, &colors);
+            LabelWidget::new(String::new())
+                .with_style(Style {
+                    margin: UiRect::vertical(Val::Px(20.0)),
+                    max_size: Size::new(Val::Px(DIALOGUE_TEXT_WIDTH), Val::Undefined),
+                    ..default()
+                })
+                .spawn(parent, &ui_style, &colors)
+                .insert(Typewriter::new(
+                    get_localized(&node.text, language).to_string(),
+                    &settings,
+                ));
+
+            for (index, choice) in choices.iter() {
+                ButtonWidget::new(get_localized(&choice.text, language))
+                    .with_width(Val::Percent(100.0))
+                    .spawn(parent, &ui_style, &colors)
+                    .insert(DialogueButtonProps {
+                        dialogue_button_type: DialogueButtonType::Choice(*index),
+                    });
+            }
+            // without anything left to say the player can only say goodbye
+            if choices.is_empty() {
+                ButtonWidget::new(localization.get("dialogue-leave"))
+                    .with_width(Val::Percent(100.0))
+                    .spawn(parent, &ui_style, &colors)
+                    .insert(DialogueButtonProps {
+                        dialogue_button_type: DialogueButtonType::Leave,
+                    });
+            }
+        });
+    }
+}
+
+fn click_dialogue_button(
+    interaction_query: Query<
+        (&Interaction, &DialogueButtonProps),
+        (Changed<Interaction>, With<Button>),
+    >,
+    mut choose: EventWriter<ChooseDialogueOption>,
+    mut leave: EventWriter<LeaveDialogue>,
+) {
+    for (interaction, props) in &interaction_query {
+        if let Interaction::Clicked = interaction {
+            match props.dialogue_button_type {
+                DialogueButtonType::Choice(index) => choose.send(ChooseDialogueOption { index }),
+                DialogueButtonType::Leave => leave.send_default(),
+            }
+        }
+    }
+}
+
+fn cleanup_dialogue_ui(mut commands: Commands, ui: Query<Entity, With<DialogueUi>>) {
+    for ui in ui.iter() {
+        commands.entity(ui).despawn_recursive();
+    }
+}
@@ -0,0 +1,180 @@
+//! The rent shown in the shop during endless runs, and the screen shown once the shop went
+//! bankrupt
+
+use crate::daily::{format_day, today};
+use crate::difficulty::Difficulty;
+use crate::endless::{get_rent, EndlessRun};
+use crate::ledger::Ledger;
+use crate::localization::{LanguageChanged, Localization};
+use crate::night::{Night, NightEnded};
+use crate::run::RunMode;
+use crate::ui::widgets::{spawn_screen_root, ButtonWidget, LabelWidget, PanelWidget};
+use crate::ui::{UiColors, UiState, UiStyle};
+use crate::GameState;
+use bevy::prelude::*;
+use iyes_loopless::prelude::{AppLooplessStateExt, ConditionSet, NextState};
+
+pub struct EndlessUiPlugin;
+
+/// This plugin is responsible for the rent label and the run over screen
+/// The run over screen is only drawn during the State `UiState::RunOver` and is removed when that
+/// state is exited
+impl Plugin for EndlessUiPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_enter_system(UiState::RunOver, setup_run_over_ui)
+            .add_exit_system(UiState::RunOver, cleanup_run_over_ui)
+            .add_exit_system(GameState::Playing, cleanup_rent_label)
+            .add_system_set(
+                ConditionSet::new()
+                    .run_in_state(UiState::RunOver)
+                    .with_system(click_run_over_button)
+                    .into(),
+            )
+            .add_system_set(
+                ConditionSet::new()
+                    .run_in_state(GameState::Playing)
+                    .with_system(refresh_rent_label)
+                    .into(),
+            );
+    }
+}
+
+#[derive(Component, Default, PartialEq, Clone)]
+pub struct RunOverUi;
+
+#[derive(Component, Default, PartialEq, Clone)]
+pub struct RunOverMenuButton;
+
+/// Holds the rent due at the end of the night, below the gold in the top bar
+#[derive(Component, Default, PartialEq, Clone)]
+pub struct RentPanel;
+
+#[derive(Component, Default, PartialEq, Clone)]
+pub struct RentLabel;
+
+fn setup_run_over_ui(
+    mut commands: Commands,
+    run: Option<Res<EndlessRun>>,
+    ledger: Res<Ledger>,
+    run_mode: Res<RunMode>,
+    ui_style: Res<UiStyle>,
+    colors: Res<UiColors>,
+    localization: Res<Localization>,
+) {
+    let run = match run {
+        Some(run) => run,
+        None => return,
+    };
+    let gold_earned = ledger.stats_all_time().revenue;
+
+    spawn_screen_root(&mut commands)
+        .insert(RunOverUi)
+        .with_children(|parent| {
+            PanelWidget::new(Val::Percent(40.0), Val::Percent(50.0))
+                .absolute()
+                .with_padding(15.0)
+                .spawn(parent, &colors)
+                .with_children(|parent| {
+                    LabelWidget::title(localization.get("endless-bankrupt"))
+                        .spawn(parent, &ui_style, &colors);
+                    if *run_mode == RunMode::Daily {
+                        LabelWidget::new(localization.get_with(
+                            "daily-challenge-of",
+                            &[("date", format_day(today()).into())],
+                        ))
+                        .small()
+                        .spawn(parent, &ui_style, &colors);
+                    }
+                    LabelWidget::new(localization.get_with(
+                        "endless-result",
+                        &[
+                            ("nights", run.nights_survived.into()),
+                            ("gold", gold_earned.into()),
+                            ("score", run.get_score(gold_earned).into()),
+                        ],
+                    ))
+                    .spawn(parent, &ui_style, &colors);
+                    ButtonWidget::new(localization.get("ui-main-menu"))
+                        .spawn(parent, &ui_style, &colors)
+                        .insert(RunOverMenuButton);
+                });
+        });
+}
+
+fn click_run_over_button(
+    mut commands: Commands,
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<RunOverMenuButton>)>,
+) {
+    for interaction in &interaction_query {
+        if let Interaction::Clicked = interaction {
+            commands.insert_resource(NextState(GameState::MainMenu));
+        }
+    }
+}
+
+fn cleanup_run_over_ui(mut commands: Commands, ui: Query<Entity, With<RunOverUi>>) {
+    for ui in ui.iter() {
+        commands.entity(ui).despawn_recursive();
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn refresh_rent_label(
+    mut commands: Commands,
+    panels: Query<(), With<RentPanel>>,
+    mut labels: Query<&mut Text, With<RentLabel>>,
+    run: Option<Res<EndlessRun>>,
+    night: Res<Night>,
+    difficulty: Res<Difficulty>,
+    mut night_ended: EventReader<NightEnded>,
+    mut language_changed: EventReader<LanguageChanged>,
+    ui_style: Res<UiStyle>,
+    colors: Res<UiColors>,
+    localization: Res<Localization>,
+) {
+    if run.is_none() {
+        return;
+    }
+    let night_ended = night_ended.iter().count() > 0;
+    let language_changed = language_changed.iter().count() > 0;
+    let spawned = !panels.is_empty();
+    if spawned && !night_ended && !language_changed {
+        return;
+    }
+    let rent = get_rent(night.number, &difficulty);
+    let text = localization.get_with("endless-rent", &[("amount", rent.into())]);
+
+    if !spawned {
+        commands
+            .spawn(NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    position: UiRect {
+                        left: Val::Px(30.0),
+                        top: Val::Px(80.0),
+                        ..default()
+                    },
+                    ..default()
+                },
+                ..default()
+            })
+            .insert(RentPanel)
+            .with_children(|parent| {
+                LabelWidget::new(text)
+                    .small()
+                    .with_color(colors.text_light)
+                    .spawn(parent, &ui_style, &colors)
+                    .insert(RentLabel);
+            });
+        return;
+    }
+    for mut label in labels.iter_mut() {
+        label.sections[0].value = text.clone();
+    }
+}
+
+fn cleanup_rent_label(mut commands: Commands, panels: Query<Entity, With<RentPanel>>) {
+    for panel in panels.iter() {
+        commands.entity(panel).despawn_recursive();
+    }
+}
@@ -0,0 +1,329 @@
+use crate::ledger::export::ExportLedger;
+use crate::ledger::{Ledger, LedgerStats};
+use crate::localization::Localization;
+use crate::night::Night;
+use crate::ui::widgets::{spawn_screen_root, ButtonWidget, LabelWidget, PanelWidget};
+use crate::ui::{UiColors, UiState, UiStyle};
+use crate::PausedState;
+use bevy::prelude::*;
+use iyes_loopless::prelude::{AppLooplessStateExt, ConditionSet, NextState};
+
+pub struct LedgerUiPlugin;
+
+/// This plugin is responsible for the ledger screen showing the shops finances
+/// The screen is only drawn during the State `UiState::Ledger` and is removed when that state is exited
+impl Plugin for LedgerUiPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_enter_system(UiState::Ledger, setup_ledger_ui)
+            .add_exit_system(UiState::Ledger, cleanup_ledger_ui)
+            .add_system_set(
+                ConditionSet::new()
+                    .run_in_state(UiState::Ledger)
+                    .run_in_state(PausedState::Playing)
+                    .run_if_resource_exists::<LedgerView>()
+                    .with_system(click_ledger_button)
+                    .with_system(refresh_ledger_ui)
+                    .into(),
+            );
+    }
+}
+
+/// How many nights are shown in the charts
+const CHART_NIGHTS: u32 = 10;
+const CHART_HEIGHT: f32 = 150.0;
+
+/// Which entries of the ledger the screen is summarizing
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Resource)]
+pub enum LedgerView {
+    Night(u32),
+    AllTime,
+}
+
+#[derive(Component, Default, PartialEq, Clone)]
+pub struct LedgerUi;
+
+#[derive(Component, Default, PartialEq, Clone)]
+pub struct LedgerStatsText;
+
+#[derive(Component, Default, PartialEq, Clone)]
+pub struct LedgerCharts;
+
+#[derive(Component, PartialEq, Clone)]
+pub struct LedgerButtonProps {
+    ledger_button_type: LedgerButtonType,
+}
+
+#[derive(PartialEq, Clone)]
+pub enum LedgerButtonType {
+    PreviousNight,
+    NextNight,
+    AllTime,
+    Export,
+    Close,
+}
+
+impl LedgerButtonProps {
+    pub fn get_button_text(&self, localization: &Localization) -> String {
+        return match self.ledger_button_type {
+            LedgerButtonType::PreviousNight => localization.get("ledger-previous-night"),
+            LedgerButtonType::NextNight => localization.get("ledger-next-night"),
+            LedgerButtonType::AllTime => localization.get("ledger-all-time"),
+            LedgerButtonType::Export => localization.get("ledger-export"),
+            LedgerButtonType::Close => localization.get("ui-close"),
+        };
+    }
+}
+
+fn get_stats_text(view: &LedgerView, ledger: &Ledger, localization: &Localization) -> String {
+    let (title, stats) = match view {
+        LedgerView::Night(night) => (
+            localization.get_with("ledger-night", &[("night", (*night).into())]),
+            ledger.stats_for_night(*night),
+        ),
+        LedgerView::AllTime => (localization.get("ledger-all-time"), ledger.stats_all_time()),
+    };
+    localization.get_with(
+        "ledger-stats",
+        &[
+            ("title", title.into()),
+            ("revenue", stats.revenue.into()),
+            ("costs", stats.costs.into()),
+            ("margin", stats.margin().into()),
+            ("sales", stats.sales.into()),
+            ("barters", stats.barters.into()),
+            ("won", stats.haggles_won.into()),
+            ("haggles", stats.haggles.into()),
+            ("rate", (stats.haggle_success_rate() * 100.0).round().into()),
+        ],
+    )
+}
+
+fn setup_ledger_ui(
+    mut commands: Commands,
+    ui_style: Res<UiStyle>,
+    colors: Res<UiColors>,
+    night: Res<Night>,
+    localization: Res<Localization>,
+) {
+    commands.insert_resource(LedgerView::Night(night.number));
+
+    spawn_screen_root(&mut commands)
+        .insert(LedgerUi)
+        .with_children(|parent| {
+            PanelWidget::new(Val::Percent(80.0), Val::Percent(85.0))
+                .absolute()
+                .with_padding(15.0)
+                .spawn(parent, &colors)
+                .with_children(|parent| {
+                    // view controls
+                    PanelWidget::new(Val::Percent(100.0), Val::Auto)
+                        .row()
+                        .spawn(parent, &colors)
+                        .with_children(|parent| {
+                            for ledger_button_type in [
+                                LedgerButtonType::PreviousNight,
+                                LedgerButtonType::NextNight,
+                                LedgerButtonType::AllTime,
+                                LedgerButtonType::Export,
+                                LedgerButtonType::Close,
+                            ] {
+                                let props = LedgerButtonProps { ledger_button_type };
+                                ButtonWidget::new(props.get_button_text(&localization))
+                                    .small()
+                                    .spawn(parent, &ui_style, &colors)
+                                    .insert(props);
+                            }
+                        });
+
+                    LabelWidget::new(String::new())
+                        .spawn(parent, &ui_style, &colors)
+                        .insert(LedgerStatsText);
+
+                    // the charts are filled in by refresh_ledger_ui
+                    PanelWidget::new(Val::Percent(100.0), Val::Auto)
+                        .row()
+                        .with_color(colors.panel_dark)
+                        .spawn(parent, &colors)
+                        .insert(LedgerCharts);
+                });
+        });
+}
+
+/// Spawns a bar chart with one bar per night. Values are scaled against the largest absolute
+/// value and bars for negative values use the failure color
+fn spawn_chart(
+    parent: &mut ChildBuilder,
+    title: &str,
+    values: &[f32],
+    color: Color,
+    negative_color: Color,
+    ui_style: &Res<UiStyle>,
+    colors: &Res<UiColors>,
+) {
+    let max = values
+        .iter()
+        .fold(0.0_f32, |max, value| max.max(value.abs()))
+        .max(1.0);
+    let bar_width = 100.0 / values.len().max(1) as f32;
+
+    parent
+        .spawn(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(32.0), Val::Auto),
+                flex_direction: FlexDirection::Column,
+                padding: UiRect::all(Val::Px(10.0)),
+                position_type: PositionType::Relative,
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|parent| {
+            LabelWidget::new(title)
+                .small()
+                .with_color(colors.text_light)
+                .spawn(parent, ui_style, colors);
+
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        size: Size::new(Val::Percent(100.0), Val::Px(CHART_HEIGHT)),
+                        align_items: AlignItems::FlexEnd,
+                        position_type: PositionType::Relative,
+                        ..default()
+                    },
+                    background_color: Color::rgb(0.2, 0.2, 0.2).into(),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    for value in values {
+                        let bar_color = if *value < 0.0 { negative_color } else { color };
+                        parent.spawn(NodeBundle {
+                            style: Style {
+                                size: Size::new(
+                                    Val::Percent(bar_width),
+                                    Val::Percent(value.abs() / max * 100.0),
+                                ),
+                                margin: UiRect::horizontal(Val::Px(2.0)),
+                                ..default()
+                            },
+                            background_color: bar_color.into(),
+                            ..default()
+                        });
+                    }
+                });
+        });
+}
+
+#[allow(clippy::too_many_arguments)]
+fn refresh_ledger_ui(
+    mut commands: Commands,
+    mut stats_text: Query<&mut Text, With<LedgerStatsText>>,
+    charts: Query<Entity, With<LedgerCharts>>,
+    view: Res<LedgerView>,
+    ledger: Res<Ledger>,
+    night: Res<Night>,
+    ui_style: Res<UiStyle>,
+    colors: Res<UiColors>,
+    localization: Res<Localization>,
+) {
+    if !view.is_changed() && !ledger.is_changed() {
+        return;
+    }
+
+    for mut text in stats_text.iter_mut() {
+        text.sections[0].value = get_stats_text(&view, &ledger, &localization);
+    }
+
+    let first_night = night.number.saturating_sub(CHART_NIGHTS - 1).max(1);
+    let nightly_stats: Vec<LedgerStats> = (first_night..=night.number)
+        .map(|night| ledger.stats_for_night(night))
+        .collect();
+    let revenue: Vec<f32> = nightly_stats.iter().map(|stats| stats.revenue as f32).collect();
+    let margin: Vec<f32> = nightly_stats.iter().map(|stats| stats.margin() as f32).collect();
+    let haggle_success: Vec<f32> = nightly_stats
+        .iter()
+        .map(|stats| stats.haggle_success_rate() * 100.0)
+        .collect();
+
+    for charts in charts.iter() {
+        commands.entity(charts).despawn_descendants();
+        commands.entity(charts).with_children(|parent| {
+            spawn_chart(
+                parent,
+                &localization.get("ledger-chart-revenue"),
+                &revenue,
+                colors.success,
+                colors.failure,
+                &ui_style,
+                &colors,
+            );
+            spawn_chart(
+                parent,
+                &localization.get("ledger-chart-margin"),
+                &margin,
+                colors.success,
+                colors.failure,
+                &ui_style,
+                &colors,
+            );
+            spawn_chart(
+                parent,
+                &localization.get("ledger-chart-haggle-success"),
+                &haggle_success,
+                colors.button_standard,
+                colors.failure,
+                &ui_style,
+                &colors,
+            );
+        });
+    }
+}
+
+fn click_ledger_button(
+    mut commands: Commands,
+    interaction_query: Query<
+        (&Interaction, &LedgerButtonProps),
+        (Changed<Interaction>, With<Button>),
+    >,
+    mut view: ResMut<LedgerView>,
+    night: Res<Night>,
+    mut export: EventWriter<ExportLedger>,
+) {
+    for (interaction, props) in &interaction_query {
+        if let Interaction::Clicked = interaction {
+            match props.ledger_button_type {
+                LedgerButtonType::PreviousNight => {
+                    *view = match *view {
+                        LedgerView::Night(selected) => {
+                            LedgerView::Night(selected.saturating_sub(1).max(1))
+                        }
+                        LedgerView::AllTime => LedgerView::Night(night.number),
+                    };
+                }
+                LedgerButtonType::NextNight => {
+                    *view = match *view {
+                        LedgerView::Night(selected) => {
+                            LedgerView::Night((selected + 1).min(night.number))
+                        }
+                        LedgerView::AllTime => LedgerView::Night(night.number),
+                    };
+                }
+                LedgerButtonType::AllTime => {
+                    *view = LedgerView::AllTime;
+                }
+                LedgerButtonType::Export => {
+                    export.send_default();
+                }
+                LedgerButtonType::Close => {
+                    commands.insert_resource(NextState(UiState::Normal));
+                }
+            }
+        }
+    }
+}
+
+fn cleanup_ledger_ui(mut commands: Commands, ui: Query<Entity, With<LedgerUi>>) {
+    for ui in ui.iter() {
+        commands.entity(ui).despawn_recursive();
+    }
+}
@@ -1,11 +1,29 @@
-﻿use crate::ui::game_scene::barter_screen::BarterUiPlugin;
+﻿use crate::ui::game_scene::bank_screen::BankUiPlugin;
+use crate::ui::game_scene::barter_screen::BarterUiPlugin;
+use crate::ui::game_scene::chapter_screen::ChapterUiPlugin;
+use crate::ui::game_scene::dialogue_screen::DialogueUiPlugin;
+use crate::ui::game_scene::endless_screen::EndlessUiPlugin;
+use crate::ui::game_scene::ledger_screen::LedgerUiPlugin;
+use crate::ui::game_scene::pause_screen::PauseUiPlugin;
+use crate::ui::game_scene::purchase_screen::PurchaseUiPlugin;
 use crate::ui::game_scene::scene_ui::{GameSceneUiPlugin};
+use crate::ui::game_scene::speech_bubbles::SpeechBubblePlugin;
+use crate::ui::game_scene::upgrades_screen::UpgradesUiPlugin;
 use crate::GameState;
 use bevy::prelude::{App, Plugin, Resource};
 use iyes_loopless::prelude::{AppLooplessStateExt, ConditionSet};
 
+pub mod bank_screen;
 pub mod barter_screen;
+pub mod chapter_screen;
+pub mod dialogue_screen;
+pub mod endless_screen;
+pub mod ledger_screen;
+pub mod pause_screen;
+pub mod purchase_screen;
 pub mod scene_ui;
+pub mod speech_bubbles;
+pub mod upgrades_screen;
 
 pub struct SceneUiPlugin;
 
@@ -15,6 +33,15 @@ impl Plugin for SceneUiPlugin {
     fn build(&self, app: &mut App) {
 
         app.add_plugin(BarterUiPlugin)
-            .add_plugin(GameSceneUiPlugin);
+            .add_plugin(PurchaseUiPlugin)
+            .add_plugin(UpgradesUiPlugin)
+            .add_plugin(BankUiPlugin)
+            .add_plugin(LedgerUiPlugin)
+            .add_plugin(DialogueUiPlugin)
+            .add_plugin(ChapterUiPlugin)
+            .add_plugin(EndlessUiPlugin)
+            .add_plugin(PauseUiPlugin)
+            .add_plugin(GameSceneUiPlugin)
+            .add_plugin(SpeechBubblePlugin);
     }
 }
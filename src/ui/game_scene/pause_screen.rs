@@ -0,0 +1,114 @@
+use crate::localization::Localization;
+use crate::ui::widgets::{spawn_screen_root, ButtonWidget, LabelWidget, PanelWidget};
+use crate::ui::{UiColors, UiState, UiStyle};
+use crate::{GameState, PausedState};
+use bevy::prelude::*;
+use iyes_loopless::prelude::{AppLooplessStateExt, ConditionSet, NextState};
+
+pub struct PauseUiPlugin;
+
+/// This plugin is responsible for the pause screen where the player can leave the run for the main
+/// menu. The screen is only drawn during the State `UiState::Pause` and is removed when that state
+/// is exited
+impl Plugin for PauseUiPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_enter_system(UiState::Pause, setup_pause_ui)
+            .add_enter_system(UiState::Pause, pause_game)
+            .add_exit_system(UiState::Pause, cleanup_pause_ui)
+            .add_exit_system(UiState::Pause, resume_game)
+            .add_system_set(
+                ConditionSet::new()
+                    .run_in_state(UiState::Pause)
+                    .with_system(click_pause_button)
+                    .into(),
+            );
+    }
+}
+
+#[derive(Component, Default, PartialEq, Clone)]
+pub struct PauseUi;
+
+#[derive(Component, PartialEq, Clone)]
+pub struct PauseButtonProps {
+    pause_button_type: PauseButtonType,
+}
+
+#[derive(PartialEq, Clone)]
+pub enum PauseButtonType {
+    Resume,
+    /// Ends the run, which adds it to the records
+    Quit,
+}
+
+impl PauseButtonProps {
+    pub fn get_button_text(&self, localization: &Localization) -> String {
+        return match self.pause_button_type {
+            PauseButtonType::Resume => localization.get("pause-resume"),
+            PauseButtonType::Quit => localization.get("pause-quit"),
+        };
+    }
+}
+
+fn setup_pause_ui(
+    mut commands: Commands,
+    ui_style: Res<UiStyle>,
+    colors: Res<UiColors>,
+    localization: Res<Localization>,
+) {
+    spawn_screen_root(&mut commands)
+        .insert(PauseUi)
+        .with_children(|parent| {
+            PanelWidget::new(Val::Percent(30.0), Val::Percent(40.0))
+                .absolute()
+                .with_padding(15.0)
+                .spawn(parent, &colors)
+                .with_children(|parent| {
+                    LabelWidget::title(localization.get("pause-title"))
+                        .spawn(parent, &ui_style, &colors);
+
+                    for pause_button_type in [PauseButtonType::Resume, PauseButtonType::Quit] {
+                        let props = PauseButtonProps { pause_button_type };
+                        ButtonWidget::new(props.get_button_text(&localization))
+                            .with_width(Val::Percent(100.0))
+                            .spawn(parent, &ui_style, &colors)
+                            .insert(props);
+                    }
+                });
+        });
+}
+
+/// The night doesn't go on while the game is paused
+fn pause_game(mut commands: Commands) {
+    commands.insert_resource(NextState(PausedState::Paused));
+}
+
+fn resume_game(mut commands: Commands) {
+    commands.insert_resource(NextState(PausedState::Playing));
+}
+
+fn click_pause_button(
+    mut commands: Commands,
+    interaction_query: Query<
+        (&Interaction, &PauseButtonProps),
+        (Changed<Interaction>, With<Button>),
+    >,
+) {
+    for (interaction, props) in &interaction_query {
+        if let Interaction::Clicked = interaction {
+            match props.pause_button_type {
+                PauseButtonType::Resume => {
+                    commands.insert_resource(NextState(UiState::Normal));
+                }
+                PauseButtonType::Quit => {
+                    commands.insert_resource(NextState(GameState::MainMenu));
+                }
+            }
+        }
+    }
+}
+
+fn cleanup_pause_ui(mut commands: Commands, ui: Query<Entity, With<PauseUi>>) {
+    for ui in ui.iter() {
+        commands.entity(ui).despawn_recursive();
+    }
+}
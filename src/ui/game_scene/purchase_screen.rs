@@ -0,0 +1,230 @@
+use crate::localization::Localization;
+use crate::market::items::{Inventory, ItemCategory, ItemPurchased};
+use crate::market::Market;
+use crate::player::Gold;
+use crate::ui::widgets::{spawn_screen_root, ButtonWidget, LabelWidget, PanelWidget};
+use crate::ui::{UiColors, UiState, UiStyle};
+use crate::upgrades::ShopUpgrades;
+use crate::PausedState;
+use bevy::prelude::*;
+use iyes_loopless::prelude::{AppLooplessStateExt, ConditionSet, NextState};
+
+pub struct PurchaseUiPlugin;
+
+/// This plugin is responsible for the supplier screen where the player restocks the shop
+/// The screen is only drawn during the State `UiState::Purchase` and is removed when that state is exited
+impl Plugin for PurchaseUiPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_enter_system(UiState::Purchase, setup_purchase_ui)
+            .add_exit_system(UiState::Purchase, cleanup_purchase_ui)
+            .add_system_set(
+                ConditionSet::new()
+                    .run_in_state(UiState::Purchase)
+                    .run_in_state(PausedState::Playing)
+                    .with_system(click_purchase_button)
+                    .with_system(update_purchase_rows)
+                    .into(),
+            );
+    }
+}
+
+#[derive(Component, Default, PartialEq, Clone)]
+pub struct PurchaseUi;
+
+#[derive(Component, PartialEq, Clone)]
+pub struct PurchaseRowText {
+    item: ItemCategory,
+}
+
+/// How much of the room for stock is used
+#[derive(Component, Default, PartialEq, Clone)]
+pub struct PurchaseCapacityText;
+
+#[derive(Component, PartialEq, Clone)]
+pub struct PurchaseButtonProps {
+    purchase_button_type: PurchaseButtonType,
+}
+
+#[derive(PartialEq, Clone)]
+pub enum PurchaseButtonType {
+    Buy(ItemCategory),
+    Close,
+}
+
+impl PurchaseButtonProps {
+    pub fn get_button_text(&self, localization: &Localization) -> String {
+        return match self.purchase_button_type {
+            PurchaseButtonType::Buy(_) => localization.get("purchase-buy"),
+            PurchaseButtonType::Close => localization.get("ui-close"),
+        };
+    }
+}
+
+fn get_row_text(
+    item: ItemCategory,
+    inventory: &Inventory,
+    market: &Market,
+    localization: &Localization,
+) -> String {
+    let trend = (market.get_trend(item) * 100.0).round() as i32;
+    localization.get_with(
+        "purchase-row",
+        &[
+            ("item", localization.get(item.get_locale_key()).into()),
+            ("stock", inventory.get_stock(item).into()),
+            ("cost", market.get_supplier_cost(item).into()),
+            ("trend", format!("{:+}", trend).into()),
+        ],
+    )
+}
+
+fn get_capacity_text(
+    inventory: &Inventory,
+    upgrades: &ShopUpgrades,
+    localization: &Localization,
+) -> String {
+    localization.get_with(
+        "purchase-capacity",
+        &[
+            ("stock", inventory.get_total_stock().into()),
+            ("capacity", upgrades.get_stock_capacity().into()),
+        ],
+    )
+}
+
+fn setup_purchase_ui(
+    mut commands: Commands,
+    ui_style: Res<UiStyle>,
+    colors: Res<UiColors>,
+    inventory: Res<Inventory>,
+    market: Res<Market>,
+    upgrades: Res<ShopUpgrades>,
+    localization: Res<Localization>,
+) {
+    spawn_screen_root(&mut commands)
+        .insert(PurchaseUi)
+        .with_children(|parent| {
+            PanelWidget::new(Val::Percent(50.0), Val::Percent(70.0))
+                .absolute()
+                .with_padding(15.0)
+                .spawn(parent, &colors)
+                .with_children(|parent| {
+                    LabelWidget::title(localization.get("purchase-title"))
+                        .spawn(parent, &ui_style, &colors);
+                    LabelWidget::new(get_capacity_text(&inventory, &upgrades, &localization))
+                        .small()
+                        .spawn(parent, &ui_style, &colors)
+                        .insert(PurchaseCapacityText);
+
+                    for item in ItemCategory::ALL {
+                        spawn_purchase_row(
+                            parent,
+                            item,
+                            &ui_style,
+                            &colors,
+                            &inventory,
+                            &market,
+                            &localization,
+                        );
+                    }
+
+                    let props = PurchaseButtonProps {
+                        purchase_button_type: PurchaseButtonType::Close,
+                    };
+                    ButtonWidget::new(props.get_button_text(&localization))
+                        .with_width(Val::Percent(100.0))
+                        .spawn(parent, &ui_style, &colors)
+                        .insert(props);
+                });
+        });
+}
+
+fn spawn_purchase_row(
+    parent: &mut ChildBuilder,
+    item: ItemCategory,
+    ui_style: &Res<UiStyle>,
+    colors: &Res<UiColors>,
+    inventory: &Res<Inventory>,
+    market: &Res<Market>,
+    localization: &Localization,
+) -> Entity {
+    PanelWidget::new(Val::Percent(100.0), Val::Auto)
+        .row()
+        .spawn(parent, colors)
+        .with_children(|parent| {
+            LabelWidget::new(get_row_text(item, inventory, market, localization))
+                .spawn(parent, ui_style, colors)
+                .insert(PurchaseRowText { item });
+
+            let props = PurchaseButtonProps {
+                purchase_button_type: PurchaseButtonType::Buy(item),
+            };
+            ButtonWidget::new(props.get_button_text(localization))
+                .small()
+                .spawn(parent, ui_style, colors)
+                .insert(props);
+        })
+        .id()
+}
+
+fn update_purchase_rows(
+    mut rows: Query<(&mut Text, &PurchaseRowText)>,
+    mut capacity_texts: Query<&mut Text, (With<PurchaseCapacityText>, Without<PurchaseRowText>)>,
+    inventory: Res<Inventory>,
+    market: Res<Market>,
+    upgrades: Res<ShopUpgrades>,
+    localization: Res<Localization>,
+) {
+    if !inventory.is_changed() && !market.is_changed() && !upgrades.is_changed() {
+        return;
+    }
+    for (mut text, row) in rows.iter_mut() {
+        text.sections[0].value = get_row_text(row.item, &inventory, &market, &localization);
+    }
+    for mut text in capacity_texts.iter_mut() {
+        text.sections[0].value = get_capacity_text(&inventory, &upgrades, &localization);
+    }
+}
+
+fn click_purchase_button(
+    mut commands: Commands,
+    interaction_query: Query<
+        (&Interaction, &PurchaseButtonProps),
+        (Changed<Interaction>, With<Button>),
+    >,
+    mut gold: ResMut<Gold>,
+    mut inventory: ResMut<Inventory>,
+    market: Res<Market>,
+    upgrades: Res<ShopUpgrades>,
+    mut purchased: EventWriter<ItemPurchased>,
+) {
+    for (interaction, props) in &interaction_query {
+        match props.purchase_button_type {
+            PurchaseButtonType::Buy(item) => {
+                if let Interaction::Clicked = interaction {
+                    let cost = market.get_supplier_cost(item);
+                    // the shop has no room for more stock
+                    if inventory.get_total_stock() >= upgrades.get_stock_capacity() {
+                        continue;
+                    }
+                    if gold.amount >= cost {
+                        gold.amount -= cost;
+                        inventory.add(item, 1);
+                        purchased.send(ItemPurchased { item, cost });
+                    }
+                }
+            }
+            PurchaseButtonType::Close => {
+                if let Interaction::Clicked = interaction {
+                    commands.insert_resource(NextState(UiState::Normal));
+                }
+            }
+        }
+    }
+}
+
+fn cleanup_purchase_ui(mut commands: Commands, ui: Query<Entity, With<PurchaseUi>>) {
+    for ui in ui.iter() {
+        commands.entity(ui).despawn_recursive();
+    }
+}
@@ -1,12 +1,20 @@
 ﻿use crate::barter::customers::{CustomerHandler, CustomerState, IsActiveCustomer};
-use crate::loading::FontAssets;
-use crate::player::Gold;
-use crate::ui::{UiColors, UiState};
+use crate::dialogue::tree::StoryCharacter;
+use crate::localization::{LanguageChanged, Localization};
+use crate::player::{Gold, Reputation};
+use crate::settings::Settings;
+use crate::ui::motion::slide_tween;
+use crate::ui::widgets::{ButtonWidget, LabelWidget};
+use crate::ui::{UiColors, UiState, UiStyle};
 use crate::{GameState, PausedState};
 use bevy::prelude::*;
+use bevy_tweening::lens::TextColorLens;
+use fluent_bundle::FluentValue;
+use bevy_tweening::{component_animator_system, Animator, EaseFunction, Lens, Tween, TweenCompleted};
 use iyes_loopless::prelude::{AppLooplessStateExt, ConditionSet};
-use iyes_loopless::state::NextState;
+use iyes_loopless::state::{CurrentState, NextState};
 use crate::barter::BarterState;
+use crate::market::items::Inventory;
 
 pub struct GameSceneUiPlugin;
 
@@ -16,12 +24,29 @@ impl Plugin for GameSceneUiPlugin {
     fn build(&self, app: &mut App) {
         app.add_enter_system(GameState::Playing, setup_scene_ui)
             .add_exit_system(GameState::Playing, cleanup_game_ui)
+            .add_system(component_animator_system::<GoldAmount>)
             .add_system_set(
                 ConditionSet::new()
                     .run_in_state(GameState::Playing)
                     .run_in_state(PausedState::Playing)
                     .with_system(handle_game_buttons)
                     .with_system(update_gold_count)
+                    .with_system(update_gold_text)
+                    .with_system(update_reputation_count)
+                    .into(),
+            )
+            .add_system_set(
+                ConditionSet::new()
+                    .run_in_state(GameState::Playing)
+                    .run_on_event::<LanguageChanged>()
+                    .with_system(relabel_game_buttons)
+                    .into(),
+            )
+            .add_system_set(
+                ConditionSet::new()
+                    .run_in_state(GameState::Playing)
+                    .run_on_event::<TweenCompleted>()
+                    .with_system(despawn_gold_popups)
                     .into(),
             );
     }
@@ -36,11 +61,15 @@ pub struct GameMainButtonProps {
 }
 
 impl GameMainButtonProps {
-    pub fn get_button_text(&self) -> String {
+    pub fn get_button_text(&self, localization: &Localization) -> String {
         return match self.game_button_type {
-            GameStateButtons::Barter => String::from("Barter"),
-            GameStateButtons::Purchase => String::from("Purchase"),
-            GameStateButtons::Options => String::from("Options"),
+            GameStateButtons::Barter => localization.get("hud-barter"),
+            GameStateButtons::Purchase => localization.get("hud-purchase"),
+            GameStateButtons::Upgrades => localization.get("hud-upgrades"),
+            GameStateButtons::Bank => localization.get("hud-bank"),
+            GameStateButtons::Ledger => localization.get("hud-ledger"),
+            GameStateButtons::Options => localization.get("hud-options"),
+            GameStateButtons::Pause => localization.get("hud-pause"),
         };
     }
 }
@@ -50,18 +79,72 @@ pub enum GameStateButtons {
     #[default]
     Barter,
     Purchase,
+    Upgrades,
+    Bank,
+    Ledger,
     Options,
+    Pause,
+}
+
+const GOLD_POPUP_TRANSITION_DONE: u64 = 10;
+/// How long the counter takes to count up or down to a new amount of gold
+const GOLD_COUNTER_DURATION: f32 = 0.6;
+/// How long a gain or loss popup floats before it disappears
+const GOLD_POPUP_DURATION: f32 = 1.2;
+
+/// The gold counter in the top bar. `shown` is counted towards `to` by a tween, so that a new
+/// change can pick up from wherever the counter currently is
+#[derive(PartialEq, Clone, Copy, Debug, Default, Component)]
+struct GoldAmount {
+    shown: u32,
+    to: u32,
+}
+
+/// A floating "+45" or "-20" shown below the gold counter when the gold changes
+#[derive(PartialEq, Clone, Copy, Debug, Default, Component)]
+struct GoldPopup;
+
+/// Counts the gold counter from one amount to another
+struct GoldCounterLens {
+    start: u32,
+    end: u32,
+}
+
+impl Lens<GoldAmount> for GoldCounterLens {
+    fn lerp(&mut self, target: &mut GoldAmount, ratio: f32) {
+        target.shown = lerp_gold(self.start, self.end, ratio);
+    }
+}
+
+fn lerp_gold(start: u32, end: u32, ratio: f32) -> u32 {
+    (start as f32 + (end as f32 - start as f32) * ratio).round() as u32
+}
+
+fn get_gold_text(amount: u32, localization: &Localization) -> String {
+    localization.get_with("hud-gold", &[("amount", amount.into())])
+}
+
+fn get_reputation_text(amount: i32, localization: &Localization) -> String {
+    localization.get_with("hud-reputation", &[("amount", amount.into())])
 }
 
 #[derive(PartialEq, Clone, Copy, Debug, Default, Component)]
-struct GoldAmount;
+struct ReputationAmount;
 
 fn setup_scene_ui(
     mut commands: Commands,
-    font_assets: Res<FontAssets>,
+    ui_style: Res<UiStyle>,
     colors: Res<UiColors>,
     gold: Res<Gold>,
+    reputation: Res<Reputation>,
+    localization: Res<Localization>,
 ) {
+    let top_bar_style = Style {
+        padding: UiRect::all(Val::Px(15.0)),
+        margin: UiRect::all(Val::Px(15.0)),
+        ..Default::default()
+    };
+
     commands
         .spawn(NodeBundle {
             style: Style {
@@ -74,30 +157,20 @@ fn setup_scene_ui(
         })
         .insert(GameUi)
         .with_children(|parent| {
-            parent
-                .spawn(TextBundle {
-                    style: Style {
-                        size: Size::new(Val::Auto, Val::Auto),
-                        padding: UiRect::all(Val::Px(15.0)),
-                        margin: UiRect::all(Val::Px(15.0)),
-                        justify_content: JustifyContent::FlexStart,
-                        align_items: AlignItems::FlexStart,
-                        ..Default::default()
-                    },
-                    text: Text {
-                        sections: vec![TextSection {
-                            value: format!("Gold: {:?}", gold.amount).to_string(),
-                            style: TextStyle {
-                                font: font_assets.fira_sans.clone(),
-                                font_size: 40.0,
-                                color: Color::rgb(0.9, 0.9, 0.9),
-                            },
-                        }],
-                        alignment: Default::default(),
-                    },
-                    ..Default::default()
-                })
-                .insert(GoldAmount);
+            LabelWidget::title(get_gold_text(gold.amount, &localization))
+                .with_color(colors.text_light)
+                .with_style(top_bar_style.clone())
+                .spawn(parent, &ui_style, &colors)
+                .insert(GoldAmount {
+                    shown: gold.amount,
+                    to: gold.amount,
+                });
+
+            LabelWidget::title(get_reputation_text(reputation.amount, &localization))
+                .with_color(colors.text_light)
+                .with_style(top_bar_style.clone())
+                .spawn(parent, &ui_style, &colors)
+                .insert(ReputationAmount);
         });
 
     commands
@@ -112,77 +185,258 @@ fn setup_scene_ui(
         })
         .insert(GameUi)
         .with_children(|parent| {
-            parent
-                .spawn(ButtonBundle {
-                    style: Style {
-                        size: Size::new(Val::Auto, Val::Auto),
-                        padding: UiRect::all(Val::Px(15.0)),
-                        margin: UiRect::all(Val::Auto),
-                        justify_content: JustifyContent::Center,
-                        align_items: AlignItems::Center,
-                        ..Default::default()
-                    },
-                    background_color: colors.button_standard.into(),
-                    ..Default::default()
-                })
-                .insert(GameMainButtonProps {
-                    game_button_type: GameStateButtons::Barter,
-                })
-                .with_children(|parent| {
-                    parent.spawn(TextBundle {
-                        text: Text {
-                            sections: vec![TextSection {
-                                value: "Barter".to_string(),
-                                style: TextStyle {
-                                    font: font_assets.fira_sans.clone(),
-                                    font_size: 40.0,
-                                    color: Color::rgb(0.9, 0.9, 0.9),
-                                },
-                            }],
-                            alignment: Default::default(),
-                        },
-                        ..Default::default()
-                    });
-                });
+            for game_button_type in [
+                GameStateButtons::Barter,
+                GameStateButtons::Purchase,
+                GameStateButtons::Upgrades,
+                GameStateButtons::Bank,
+                GameStateButtons::Ledger,
+                GameStateButtons::Options,
+                GameStateButtons::Pause,
+            ] {
+                let props = GameMainButtonProps { game_button_type };
+                ButtonWidget::new(props.get_button_text(&localization))
+                    .with_margin(UiRect::all(Val::Auto))
+                    .spawn(parent, &ui_style, &colors)
+                    .insert(props);
+            }
         });
 }
 
-fn update_gold_count(mut gold_amount_query: Query<&mut Text, With<GoldAmount>>, gold: Res<Gold>) {
-    for mut text in gold_amount_query.iter_mut() {
-        text.sections[0].value = format!("Gold: {:?}", gold.amount).to_string();
+fn update_gold_count(
+    mut commands: Commands,
+    mut gold_amount_query: Query<(Entity, &mut GoldAmount)>,
+    gold: Res<Gold>,
+    ui_style: Res<UiStyle>,
+    colors: Res<UiColors>,
+    settings: Res<Settings>,
+    localization: Res<Localization>,
+) {
+    if !gold.is_changed() {
+        return;
+    }
+    for (entity, mut gold_amount) in gold_amount_query.iter_mut() {
+        if gold_amount.to == gold.amount {
+            continue;
+        }
+        spawn_gold_popup(
+            &mut commands,
+            gold.amount as i64 - gold_amount.to as i64,
+            &ui_style,
+            &colors,
+            &settings,
+            &localization,
+        );
+
+        // if the counter is still counting towards the last change we start from where it is now
+        let shown = gold_amount.shown;
+        gold_amount.to = gold.amount;
+        let tween = Tween::new(
+            EaseFunction::QuadraticOut,
+            std::time::Duration::from_secs_f32(GOLD_COUNTER_DURATION),
+            GoldCounterLens {
+                start: shown,
+                end: gold.amount,
+            },
+        );
+        commands.entity(entity).insert(Animator::new(tween));
+    }
+}
+
+fn update_gold_text(
+    mut gold_amount_query: Query<(&mut Text, &GoldAmount, ChangeTrackers<GoldAmount>)>,
+    mut language_changed: EventReader<LanguageChanged>,
+    localization: Res<Localization>,
+) {
+    let relabel = language_changed.iter().count() > 0;
+    for (mut text, gold_amount, tracker) in gold_amount_query.iter_mut() {
+        if relabel || tracker.is_changed() {
+            text.sections[0].value = get_gold_text(gold_amount.shown, &localization);
+        }
+    }
+}
+
+fn spawn_gold_popup(
+    commands: &mut Commands,
+    change: i64,
+    ui_style: &Res<UiStyle>,
+    colors: &Res<UiColors>,
+    settings: &Res<Settings>,
+    localization: &Localization,
+) {
+    let args = [("amount", FluentValue::from(change.unsigned_abs()))];
+    let (text, color) = if change > 0 {
+        (localization.get_with("hud-gold-gained", &args), colors.success)
+    } else {
+        (localization.get_with("hud-gold-lost", &args), colors.failure)
+    };
+    let mut faded = color;
+    faded.set_a(0.0);
+    let position = |top: f32| UiRect {
+        left: Val::Px(30.0),
+        top: Val::Px(top),
+        right: Val::Auto,
+        bottom: Val::Auto,
+    };
+    let float_up = slide_tween(
+        settings,
+        EaseFunction::QuadraticOut,
+        GOLD_POPUP_DURATION,
+        position(80.0),
+        position(50.0),
+    );
+    // the fade is kept with reduced motion, so it decides when the popup is done
+    let fade_out = Tween::new(
+        EaseFunction::QuadraticIn,
+        std::time::Duration::from_secs_f32(GOLD_POPUP_DURATION),
+        TextColorLens {
+            start: color,
+            end: faded,
+            section: 0,
+        },
+    )
+    .with_completed_event(GOLD_POPUP_TRANSITION_DONE);
+
+    commands
+        .spawn(TextBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                position: position(80.0),
+                ..Default::default()
+            },
+            text: Text {
+                sections: vec![TextSection {
+                    value: text,
+                    style: TextStyle {
+                        font: ui_style.font.clone(),
+                        font_size: ui_style.text_size,
+                        color,
+                    },
+                }],
+                alignment: Default::default(),
+            },
+            ..Default::default()
+        })
+        .insert(Animator::new(float_up))
+        .insert(Animator::new(fade_out))
+        .insert(GoldPopup)
+        .insert(GameUi);
+}
+
+fn despawn_gold_popups(
+    mut commands: Commands,
+    mut reader: EventReader<TweenCompleted>,
+    popups: Query<Entity, With<GoldPopup>>,
+) {
+    for event in reader.iter() {
+        if event.user_data == GOLD_POPUP_TRANSITION_DONE && popups.contains(event.entity) {
+            commands.entity(event.entity).despawn_recursive();
+        }
+    }
+}
+
+fn update_reputation_count(
+    mut reputation_amount_query: Query<&mut Text, With<ReputationAmount>>,
+    reputation: Res<Reputation>,
+    localization: Res<Localization>,
+) {
+    if !reputation.is_changed() && !localization.is_changed() {
+        return;
+    }
+    for mut text in reputation_amount_query.iter_mut() {
+        text.sections[0].value = get_reputation_text(reputation.amount, &localization);
+    }
+}
+
+/// The top bar stays open while the language is changed in the options, so its buttons are
+/// relabeled in place instead of rebuilding it
+fn relabel_game_buttons(
+    buttons: Query<(&GameMainButtonProps, &Children)>,
+    mut texts: Query<&mut Text>,
+    localization: Res<Localization>,
+) {
+    for (props, children) in buttons.iter() {
+        for child in children.iter() {
+            if let Ok(mut text) = texts.get_mut(*child) {
+                text.sections[0].value = props.get_button_text(&localization);
+            }
+        }
     }
 }
 
 fn handle_game_buttons(
     mut commands: Commands,
-    button_colors: Res<UiColors>,
-    mut interaction_query: Query<
-        (&Interaction, &mut BackgroundColor, &GameMainButtonProps),
+    interaction_query: Query<
+        (&Interaction, &GameMainButtonProps),
         (Changed<Interaction>, With<Button>),
     >,
     customer_handler: Res<CustomerHandler>,
+    story_characters: Query<(), With<StoryCharacter>>,
+    inventory: Res<Inventory>,
+    ui_state: Res<CurrentState<UiState>>,
 ) {
-    for (interaction, mut color, props) in &mut interaction_query {
-        match *interaction {
-            Interaction::Hovered => {
-                *color = button_colors.button_hovered.into();
-            }
-            Interaction::None => {
-                *color = button_colors.button_standard.into();
-            }
-            _ => {}
-        }
+    for (interaction, props) in &interaction_query {
         match props.game_button_type {
             GameStateButtons::Barter => {
                 if let Interaction::Clicked = interaction {
+                    if ui_state.0 != UiState::Normal {
+                        continue;
+                    }
                     if let Some(customer) = customer_handler.get_next_customer() {
-                        commands.insert_resource(NextState(BarterState::Bartering));
+                        // story characters come to talk, not to buy
+                        if story_characters.contains(customer) {
+                            commands.insert_resource(NextState(UiState::Dialogue));
+                        } else if inventory.has_any_stock() {
+                            commands.insert_resource(NextState(BarterState::Bartering));
+                        } else {
+                            continue;
+                        }
                         commands.entity(customer).insert(IsActiveCustomer);
                     }
                 }
             }
-            GameStateButtons::Purchase => {}
-            GameStateButtons::Options => {}
+            GameStateButtons::Purchase => {
+                if let Interaction::Clicked = interaction {
+                    if ui_state.0 == UiState::Normal {
+                        commands.insert_resource(NextState(UiState::Purchase));
+                    }
+                }
+            }
+            GameStateButtons::Upgrades => {
+                if let Interaction::Clicked = interaction {
+                    if ui_state.0 == UiState::Normal {
+                        commands.insert_resource(NextState(UiState::Upgrades));
+                    }
+                }
+            }
+            GameStateButtons::Bank => {
+                if let Interaction::Clicked = interaction {
+                    if ui_state.0 == UiState::Normal {
+                        commands.insert_resource(NextState(UiState::Bank));
+                    }
+                }
+            }
+            GameStateButtons::Ledger => {
+                if let Interaction::Clicked = interaction {
+                    if ui_state.0 == UiState::Normal {
+                        commands.insert_resource(NextState(UiState::Ledger));
+                    }
+                }
+            }
+            GameStateButtons::Options => {
+                if let Interaction::Clicked = interaction {
+                    if ui_state.0 == UiState::Normal {
+                        commands.insert_resource(NextState(UiState::Options));
+                    }
+                }
+            }
+            GameStateButtons::Pause => {
+                if let Interaction::Clicked = interaction {
+                    if ui_state.0 == UiState::Normal {
+                        commands.insert_resource(NextState(UiState::Pause));
+                    }
+                }
+            }
         }
     }
 }
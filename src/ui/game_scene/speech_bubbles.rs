@@ -0,0 +1,271 @@
+//! Shows what customers say. Every line gets a speech bubble above the customer in the shop, and
+//! while the barter screen is open it is also shown at the top of the barter screen, since the
+//! screen covers the customer. The text is typed out one character at a time, with reduced motion
+//! the whole line shows up right away
+
+use crate::barter::customers::Customer;
+use crate::barter::BeginBarter;
+use crate::dialogue::CustomerSpoke;
+use crate::settings::Settings;
+use crate::ui::widgets::{LabelWidget, PanelWidget};
+use crate::ui::{UiColors, UiStyle};
+use crate::{GameState, PausedState};
+use bevy::prelude::*;
+use bevy::text::Text2dBounds;
+use iyes_loopless::prelude::ConditionSet;
+
+pub struct SpeechBubblePlugin;
+
+impl Plugin for SpeechBubblePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_set(
+            ConditionSet::new()
+                .run_in_state(GameState::Playing)
+                .with_system(spawn_world_bubbles)
+                .with_system(update_barter_speech)
+                .into(),
+        )
+        .add_system_set(
+            ConditionSet::new()
+                .run_in_state(GameState::Playing)
+                .run_in_state(PausedState::Playing)
+                .with_system(reveal_typewriter_text)
+                .with_system(despawn_world_bubbles)
+                .with_system(keep_bubbles_on_screen)
+                .into(),
+        );
+    }
+}
+
+/// How many characters of a line are typed out per second
+const CHARACTERS_PER_SECOND: f32 = 40.0;
+/// How long a bubble in the shop stays up after its line was typed out
+const BUBBLE_LINGER: f32 = 2.5;
+/// Size of the bubbles in the shop, in pixels of the pixel camera
+const WORLD_BUBBLE_SIZE: Vec2 = Vec2::new(110.0, 28.0);
+/// How far above the middle of the customer their bubble floats
+const WORLD_BUBBLE_OFFSET: f32 = 34.0;
+const WORLD_BUBBLE_FONT_SIZE: f32 = 8.0;
+/// Text in the bubble of the barter screen wider than this wraps onto the next line
+const BARTER_BUBBLE_TEXT_WIDTH: f32 = 740.0;
+/// Half the width of the view of the pixel camera, bubbles are kept inside of it
+const WORLD_HALF_WIDTH: f32 = 160.0;
+
+/// Reveals `line` in the text of the entity one character at a time
+#[derive(Component, Clone, PartialEq, Debug)]
+pub struct Typewriter {
+    line: String,
+    elapsed: f32,
+}
+
+impl Typewriter {
+    pub fn new(line: String, settings: &Settings) -> Typewriter {
+        Typewriter {
+            elapsed: if settings.reduced_motion {
+                get_typing_duration(&line)
+            } else {
+                0.0
+            },
+            line,
+        }
+    }
+
+    fn is_done(&self) -> bool {
+        self.elapsed >= get_typing_duration(&self.line)
+    }
+
+    fn get_revealed_text(&self) -> String {
+        if self.is_done() {
+            return self.line.clone();
+        }
+        let characters = (self.elapsed * CHARACTERS_PER_SECOND) as usize;
+        self.line.chars().take(characters).collect()
+    }
+}
+
+fn get_typing_duration(line: &str) -> f32 {
+    line.chars().count() as f32 / CHARACTERS_PER_SECOND
+}
+
+/// A bubble above a customer in the shop, removed when `timer` finishes
+#[derive(Component)]
+pub struct WorldSpeechBubble {
+    timer: Timer,
+}
+
+/// The bubble at the top of the barter screen, hidden until the customer says something
+#[derive(Component)]
+pub struct BarterSpeechBubble;
+
+#[derive(Component)]
+pub struct BarterSpeechText;
+
+/// Spawns the speech bubble of the barter screen, it shows the last line of the customer
+pub fn spawn_barter_speech_bubble(
+    parent: &mut ChildBuilder,
+    ui_style: &UiStyle,
+    colors: &UiColors,
+) {
+    PanelWidget::new(Val::Percent(90.0), Val::Auto)
+        .absolute()
+        .with_padding(10.0)
+        .with_margin(UiRect {
+            left: Val::Percent(5.0),
+            top: Val::Px(20.0),
+            ..default()
+        })
+        .with_color(colors.card_background)
+        .spawn(parent, colors)
+        .insert(Visibility { is_visible: false })
+        .insert(BarterSpeechBubble)
+        .with_children(|parent| {
+            LabelWidget::new(String::new())
+                .with_style(Style {
+                    max_size: Size::new(Val::Px(BARTER_BUBBLE_TEXT_WIDTH), Val::Undefined),
+                    ..default()
+                })
+                .spawn(parent, ui_style, colors)
+                .insert(BarterSpeechText);
+        });
+}
+
+fn spawn_world_bubbles(
+    mut commands: Commands,
+    mut spoke: EventReader<CustomerSpoke>,
+    customers: Query<Option<&Children>, With<Customer>>,
+    bubbles: Query<(), With<WorldSpeechBubble>>,
+    ui_style: Res<UiStyle>,
+    colors: Res<UiColors>,
+    settings: Res<Settings>,
+) {
+    for event in spoke.iter() {
+        // the customer might have left the shop in the meantime
+        let children = match customers.get(event.customer) {
+            Ok(children) => children,
+            Err(_) => continue,
+        };
+        // a new line replaces whatever the customer was saying before
+        if let Some(children) = children {
+            for child in children.iter() {
+                if bubbles.contains(*child) {
+                    commands.entity(*child).despawn_recursive();
+                }
+            }
+        }
+
+        let bubble = commands
+            .spawn(SpriteBundle {
+                sprite: Sprite {
+                    color: colors.card_background,
+                    custom_size: Some(WORLD_BUBBLE_SIZE),
+                    ..default()
+                },
+                transform: Transform::from_xyz(0.0, WORLD_BUBBLE_OFFSET, 10.0),
+                ..default()
+            })
+            .insert(WorldSpeechBubble {
+                timer: Timer::from_seconds(
+                    get_typing_duration(&event.line) + BUBBLE_LINGER,
+                    TimerMode::Once,
+                ),
+            })
+            .with_children(|parent| {
+                parent
+                    .spawn(Text2dBundle {
+                        text: Text::from_section(
+                            String::new(),
+                            TextStyle {
+                                font: ui_style.font.clone(),
+                                font_size: WORLD_BUBBLE_FONT_SIZE,
+                                color: colors.text_dark,
+                            },
+                        )
+                        .with_alignment(TextAlignment::CENTER),
+                        text_2d_bounds: Text2dBounds {
+                            size: WORLD_BUBBLE_SIZE - Vec2::splat(6.0),
+                        },
+                        transform: Transform::from_xyz(0.0, 0.0, 1.0),
+                        ..default()
+                    })
+                    .insert(Typewriter::new(event.line.clone(), &settings));
+            })
+            .id();
+        commands.entity(event.customer).add_child(bubble);
+    }
+}
+
+fn update_barter_speech(
+    mut commands: Commands,
+    mut begin: EventReader<BeginBarter>,
+    mut spoke: EventReader<CustomerSpoke>,
+    mut last_line: Local<Option<String>>,
+    mut bubbles: Query<&mut Visibility, With<BarterSpeechBubble>>,
+    texts: Query<Entity, With<BarterSpeechText>>,
+    added: Query<(), Added<BarterSpeechText>>,
+    settings: Res<Settings>,
+) {
+    // lines from the last barter don't carry over to the next one
+    if begin.iter().count() > 0 {
+        *last_line = None;
+    }
+    let mut changed = false;
+    for event in spoke.iter() {
+        *last_line = Some(event.line.clone());
+        changed = true;
+    }
+    // the greeting is said before the barter screen is spawned
+    if !changed && added.is_empty() {
+        return;
+    }
+    let line = match &*last_line {
+        Some(line) => line.clone(),
+        None => return,
+    };
+
+    for mut visibility in bubbles.iter_mut() {
+        visibility.is_visible = true;
+    }
+    for text in texts.iter() {
+        commands
+            .entity(text)
+            .insert(Typewriter::new(line.clone(), &settings));
+    }
+}
+
+fn reveal_typewriter_text(mut texts: Query<(&mut Text, &mut Typewriter)>, time: Res<Time>) {
+    for (mut text, mut typewriter) in texts.iter_mut() {
+        // replacing the line of a typewriter counts as a change, so it is shown even when done
+        if typewriter.is_done() && !typewriter.is_changed() {
+            continue;
+        }
+        typewriter.elapsed += time.delta_seconds();
+        text.sections[0].value = typewriter.get_revealed_text();
+    }
+}
+
+fn despawn_world_bubbles(
+    mut commands: Commands,
+    mut bubbles: Query<(Entity, &mut WorldSpeechBubble)>,
+    time: Res<Time>,
+) {
+    for (entity, mut bubble) in bubbles.iter_mut() {
+        bubble.timer.tick(time.delta());
+        if bubble.timer.finished() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+/// Customers walk right up to the edges of the shop, so their bubbles are pushed back into view
+fn keep_bubbles_on_screen(
+    mut bubbles: Query<(&Parent, &mut Transform), (With<WorldSpeechBubble>, Without<Customer>)>,
+    customers: Query<&Transform, With<Customer>>,
+) {
+    let max_x = WORLD_HALF_WIDTH - WORLD_BUBBLE_SIZE.x / 2.0;
+    for (parent, mut transform) in bubbles.iter_mut() {
+        if let Ok(customer) = customers.get(parent.get()) {
+            let x = customer.translation.x.clamp(-max_x, max_x);
+            transform.translation.x = x - customer.translation.x;
+        }
+    }
+}
@@ -0,0 +1,212 @@
+use crate::ledger::{ExpenseKind, ExpensePaid};
+use crate::localization::Localization;
+use crate::night::Night;
+use crate::player::Gold;
+use crate::ui::widgets::{spawn_screen_root, ButtonWidget, LabelWidget, PanelWidget};
+use crate::ui::{UiColors, UiState, UiStyle};
+use crate::upgrades::{ShopUpgrade, ShopUpgrades, MAX_UPGRADE_LEVEL};
+use crate::PausedState;
+use bevy::prelude::*;
+use iyes_loopless::prelude::{AppLooplessStateExt, ConditionSet, NextState};
+
+pub struct UpgradesUiPlugin;
+
+/// This plugin is responsible for the upgrades screen where the player improves the shop
+/// The screen is only drawn during the State `UiState::Upgrades` and is removed when that state is exited
+impl Plugin for UpgradesUiPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_enter_system(UiState::Upgrades, setup_upgrades_ui)
+            .add_exit_system(UiState::Upgrades, cleanup_upgrades_ui)
+            .add_system_set(
+                ConditionSet::new()
+                    .run_in_state(UiState::Upgrades)
+                    .run_in_state(PausedState::Playing)
+                    .with_system(click_upgrade_button)
+                    .with_system(update_upgrade_rows)
+                    .into(),
+            );
+    }
+}
+
+#[derive(Component, Default, PartialEq, Clone)]
+pub struct UpgradesUi;
+
+#[derive(Component, PartialEq, Clone)]
+pub struct UpgradeRowText {
+    upgrade: ShopUpgrade,
+}
+
+#[derive(Component, PartialEq, Clone)]
+pub struct UpgradeButtonProps {
+    upgrade_button_type: UpgradeButtonType,
+}
+
+#[derive(PartialEq, Clone)]
+pub enum UpgradeButtonType {
+    Buy(ShopUpgrade),
+    Close,
+}
+
+impl UpgradeButtonProps {
+    pub fn get_button_text(&self, localization: &Localization) -> String {
+        return match self.upgrade_button_type {
+            UpgradeButtonType::Buy(_) => localization.get("upgrades-buy"),
+            UpgradeButtonType::Close => localization.get("ui-close"),
+        };
+    }
+}
+
+fn get_row_text(
+    upgrade: ShopUpgrade,
+    upgrades: &ShopUpgrades,
+    localization: &Localization,
+) -> String {
+    let name = localization.get(upgrade.get_locale_key());
+    let level = upgrades.get_level(upgrade);
+    let description = localization.get(&format!("{}-description", upgrade.get_locale_key()));
+    return match upgrades.get_next_cost(upgrade) {
+        Some(cost) => localization.get_with(
+            "upgrades-row",
+            &[
+                ("upgrade", name.into()),
+                ("level", level.into()),
+                ("max", MAX_UPGRADE_LEVEL.into()),
+                ("cost", cost.into()),
+                ("description", description.into()),
+            ],
+        ),
+        None => localization.get_with(
+            "upgrades-row-maxed",
+            &[
+                ("upgrade", name.into()),
+                ("description", description.into()),
+            ],
+        ),
+    };
+}
+
+fn setup_upgrades_ui(
+    mut commands: Commands,
+    ui_style: Res<UiStyle>,
+    colors: Res<UiColors>,
+    upgrades: Res<ShopUpgrades>,
+    localization: Res<Localization>,
+) {
+    spawn_screen_root(&mut commands)
+        .insert(UpgradesUi)
+        .with_children(|parent| {
+            PanelWidget::new(Val::Percent(60.0), Val::Percent(70.0))
+                .absolute()
+                .with_padding(15.0)
+                .spawn(parent, &colors)
+                .with_children(|parent| {
+                    LabelWidget::title(localization.get("upgrades-title"))
+                        .spawn(parent, &ui_style, &colors);
+
+                    for upgrade in ShopUpgrade::ALL {
+                        spawn_upgrade_row(
+                            parent,
+                            upgrade,
+                            &ui_style,
+                            &colors,
+                            &upgrades,
+                            &localization,
+                        );
+                    }
+
+                    let props = UpgradeButtonProps {
+                        upgrade_button_type: UpgradeButtonType::Close,
+                    };
+                    ButtonWidget::new(props.get_button_text(&localization))
+                        .with_width(Val::Percent(100.0))
+                        .spawn(parent, &ui_style, &colors)
+                        .insert(props);
+                });
+        });
+}
+
+fn spawn_upgrade_row(
+    parent: &mut ChildBuilder,
+    upgrade: ShopUpgrade,
+    ui_style: &Res<UiStyle>,
+    colors: &Res<UiColors>,
+    upgrades: &Res<ShopUpgrades>,
+    localization: &Localization,
+) -> Entity {
+    PanelWidget::new(Val::Percent(100.0), Val::Auto)
+        .row()
+        .spawn(parent, colors)
+        .with_children(|parent| {
+            LabelWidget::new(get_row_text(upgrade, upgrades, localization))
+                .small()
+                .spawn(parent, ui_style, colors)
+                .insert(UpgradeRowText { upgrade });
+
+            let props = UpgradeButtonProps {
+                upgrade_button_type: UpgradeButtonType::Buy(upgrade),
+            };
+            ButtonWidget::new(props.get_button_text(localization))
+                .small()
+                .spawn(parent, ui_style, colors)
+                .insert(props);
+        })
+        .id()
+}
+
+fn update_upgrade_rows(
+    mut rows: Query<(&mut Text, &UpgradeRowText)>,
+    upgrades: Res<ShopUpgrades>,
+    localization: Res<Localization>,
+) {
+    if !upgrades.is_changed() {
+        return;
+    }
+    for (mut text, row) in rows.iter_mut() {
+        text.sections[0].value = get_row_text(row.upgrade, &upgrades, &localization);
+    }
+}
+
+fn click_upgrade_button(
+    mut commands: Commands,
+    interaction_query: Query<
+        (&Interaction, &UpgradeButtonProps),
+        (Changed<Interaction>, With<Button>),
+    >,
+    mut gold: ResMut<Gold>,
+    mut upgrades: ResMut<ShopUpgrades>,
+    night: Res<Night>,
+    mut expenses: EventWriter<ExpensePaid>,
+) {
+    for (interaction, props) in &interaction_query {
+        match props.upgrade_button_type {
+            UpgradeButtonType::Buy(upgrade) => {
+                if let Interaction::Clicked = interaction {
+                    let cost = match upgrades.get_next_cost(upgrade) {
+                        Some(cost) => cost,
+                        None => continue,
+                    };
+                    if gold.amount >= cost {
+                        gold.amount -= cost;
+                        upgrades.upgrade(upgrade);
+                        expenses.send(ExpensePaid {
+                            kind: ExpenseKind::ShopUpgrade,
+                            amount: cost,
+                            night: night.number,
+                        });
+                    }
+                }
+            }
+            UpgradeButtonType::Close => {
+                if let Interaction::Clicked = interaction {
+                    commands.insert_resource(NextState(UiState::Normal));
+                }
+            }
+        }
+    }
+}
+
+fn cleanup_upgrades_ui(mut commands: Commands, ui: Query<Entity, With<UpgradesUi>>) {
+    for ui in ui.iter() {
+        commands.entity(ui).despawn_recursive();
+    }
+}
@@ -1,7 +1,13 @@
 use crate::barter::BarterResolutionTypes;
-use crate::loading::FontAssets;
+use crate::campaign::CampaignSave;
+use crate::daily::{get_daily_seed, today, DailyScores};
+use crate::difficulty::Difficulty;
+use crate::localization::{LanguageChanged, Localization};
+use crate::rng::RequestedSeed;
+use crate::run::RunMode;
 use crate::ui::game_scene::barter_screen::{BarterControlButtonProps, BarterUi};
-use crate::ui::UiColors;
+use crate::ui::widgets::{spawn_screen_root, ButtonWidget, PanelWidget};
+use crate::ui::{UiColors, UiState, UiStyle};
 use crate::GameState;
 use bevy::app::AppExit;
 use bevy::prelude::*;
@@ -22,6 +28,13 @@ impl Plugin for MenuPlugin {
                     .run_in_state(GameState::MainMenu)
                     .with_system(click_play_button)
                     .into(),
+            )
+            .add_system_set(
+                ConditionSet::new()
+                    .run_in_state(GameState::MainMenu)
+                    .run_on_event::<LanguageChanged>()
+                    .with_system(rebuild_menu)
+                    .into(),
             );
     }
 }
@@ -38,100 +51,132 @@ pub struct MenuButtonProps {
 pub enum MenuButtonType {
     #[default]
     NewGame,
+    NewCampaign,
+    /// Only shown while there is an unfinished campaign
+    ContinueCampaign,
+    Endless,
+    /// Holds the score of today if the challenge was already attempted
+    DailyChallenge(Option<u32>),
+    Records,
+    Achievements,
+    Skills,
     Options,
     Exit,
 }
 
 impl MenuButtonProps {
-    pub fn get_button_text(&self) -> String {
+    pub fn get_button_text(&self, localization: &Localization) -> String {
         return match self.menu_button_type {
-            MenuButtonType::NewGame => String::from("New Game"),
-            MenuButtonType::Options => String::from("Options"),
-            MenuButtonType::Exit => String::from("Exit"),
+            MenuButtonType::NewGame => localization.get("menu-new-game"),
+            MenuButtonType::NewCampaign => localization.get("menu-new-campaign"),
+            MenuButtonType::ContinueCampaign => localization.get("menu-continue-campaign"),
+            MenuButtonType::Endless => localization.get("menu-endless"),
+            MenuButtonType::DailyChallenge(None) => localization.get("menu-daily"),
+            MenuButtonType::DailyChallenge(Some(score)) => {
+                localization.get_with("menu-daily-done", &[("score", score.into())])
+            }
+            MenuButtonType::Records => localization.get("menu-records"),
+            MenuButtonType::Achievements => localization.get("menu-achievements"),
+            MenuButtonType::Skills => localization.get("menu-skills"),
+            MenuButtonType::Options => localization.get("menu-options"),
+            MenuButtonType::Exit => localization.get("menu-exit"),
         };
     }
 }
 
-fn setup_menu(mut commands: Commands, font_assets: Res<FontAssets>, colors: Res<UiColors>) {
-    commands
-        .spawn(NodeBundle {
-            style: Style {
-                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
-                justify_content: JustifyContent::Center,
-                align_items: AlignItems::Center,
-                position_type: PositionType::Absolute,
-                ..default()
-            },
-            ..default()
-        })
+fn setup_menu(
+    mut commands: Commands,
+    ui_style: Res<UiStyle>,
+    colors: Res<UiColors>,
+    localization: Res<Localization>,
+    campaign_save: Res<CampaignSave>,
+    daily_scores: Res<DailyScores>,
+) {
+    let mut menu_button_types = vec![MenuButtonType::NewGame, MenuButtonType::NewCampaign];
+    if campaign_save.can_continue() {
+        menu_button_types.push(MenuButtonType::ContinueCampaign);
+    }
+    menu_button_types.push(MenuButtonType::Endless);
+    menu_button_types.push(MenuButtonType::DailyChallenge(
+        daily_scores.get_attempt(today()).map(|attempt| attempt.score),
+    ));
+    menu_button_types.push(MenuButtonType::Records);
+    menu_button_types.push(MenuButtonType::Achievements);
+    menu_button_types.push(MenuButtonType::Skills);
+    menu_button_types.push(MenuButtonType::Options);
+    menu_button_types.push(MenuButtonType::Exit);
+
+    spawn_screen_root(&mut commands)
         .insert(MenuUi)
         .with_children(|parent| {
-            parent
-                .spawn(NodeBundle {
-                    style: Style {
-                        size: Size::new(Val::Percent(30.0), Val::Percent(50.0)),
-                        justify_content: JustifyContent::SpaceBetween,
-                        position_type: PositionType::Absolute,
-                        ..default()
-                    },
-                    background_color: Color::rgb(0.65, 0.65, 0.65).into(),
-                    ..default()
-                })
+            PanelWidget::new(Val::Percent(30.0), Val::Percent(90.0))
+                .absolute()
+                .spawn(parent, &colors)
                 .with_children(|parent| {
-                    parent
-                        .spawn(ButtonBundle {
-                            style: Style {
-                                size: Size::new(Val::Auto, Val::Auto),
-                                padding: UiRect::all(Val::Px(15.0)),
-                                margin: UiRect::all(Val::Auto),
-                                justify_content: JustifyContent::Center,
-                                align_items: AlignItems::Center,
-                                ..Default::default()
-                            },
-                            background_color: colors.button_standard.into(),
-                            ..Default::default()
-                        })
-                        .insert(MenuButtonProps {
-                            menu_button_type: MenuButtonType::NewGame,
-                        })
-                        .with_children(|parent| {
-                            parent.spawn(TextBundle {
-                                text: Text {
-                                    sections: vec![TextSection {
-                                        value: "Play".to_string(),
-                                        style: TextStyle {
-                                            font: font_assets.fira_sans.clone(),
-                                            font_size: 40.0,
-                                            color: Color::rgb(0.9, 0.9, 0.9),
-                                        },
-                                    }],
-                                    alignment: Default::default(),
-                                },
-                                ..Default::default()
-                            });
-                        });
+                    for menu_button_type in menu_button_types {
+                        let props = MenuButtonProps { menu_button_type };
+                        ButtonWidget::new(props.get_button_text(&localization))
+                            .with_margin(UiRect::all(Val::Auto))
+                            .spawn(parent, &ui_style, &colors)
+                            .insert(props);
+                    }
                 });
         });
 }
 
 fn click_play_button(
     mut commands: Commands,
-    button_colors: Res<UiColors>,
-    mut interaction_query: Query<
-        (&Interaction, &mut BackgroundColor),
-        (Changed<Interaction>, With<Button>),
-    >,
+    interaction_query: Query<(&Interaction, &MenuButtonProps), (Changed<Interaction>, With<Button>)>,
+    ui_state: Res<CurrentState<UiState>>,
+    campaign_save: Res<CampaignSave>,
+    mut exit: EventWriter<AppExit>,
 ) {
-    for (interaction, mut color) in &mut interaction_query {
-        match *interaction {
-            Interaction::Clicked => {
-                commands.insert_resource(NextState(GameState::Playing));
+    for (interaction, props) in &interaction_query {
+        if let Interaction::Clicked = interaction {
+            if ui_state.0 != UiState::Normal {
+                continue;
             }
-            Interaction::Hovered => {
-                *color = button_colors.button_hovered.into();
-            }
-            Interaction::None => {
-                *color = button_colors.button_standard.into();
+            match props.menu_button_type {
+                MenuButtonType::NewGame => {
+                    commands.insert_resource(RunMode::FreePlay);
+                    commands.insert_resource(NextState(UiState::Difficulty));
+                }
+                MenuButtonType::NewCampaign => {
+                    // the campaign is only started over once the difficulty was picked
+                    commands.insert_resource(RunMode::Campaign);
+                    commands.insert_resource(NextState(UiState::Difficulty));
+                }
+                MenuButtonType::ContinueCampaign => {
+                    commands.insert_resource(campaign_save.difficulty.clone());
+                    commands.insert_resource(RunMode::Campaign);
+                    commands.insert_resource(NextState(GameState::Playing));
+                }
+                MenuButtonType::Endless => {
+                    commands.insert_resource(RunMode::Endless);
+                    commands.insert_resource(NextState(UiState::Difficulty));
+                }
+                MenuButtonType::DailyChallenge(None) => {
+                    // everyone plays the challenge with the same rules
+                    commands.insert_resource(Difficulty::default());
+                    commands.insert_resource(RequestedSeed(Some(get_daily_seed(today()))));
+                    commands.insert_resource(RunMode::Daily);
+                    commands.insert_resource(NextState(GameState::Playing));
+                }
+                // one attempt per day
+                MenuButtonType::DailyChallenge(Some(_)) => {}
+                MenuButtonType::Records => {
+                    commands.insert_resource(NextState(UiState::Records));
+                }
+                MenuButtonType::Achievements => {
+                    commands.insert_resource(NextState(UiState::Achievements));
+                }
+                MenuButtonType::Skills => {
+                    commands.insert_resource(NextState(UiState::Skills));
+                }
+                MenuButtonType::Options => {
+                    commands.insert_resource(NextState(UiState::Options));
+                }
+                MenuButtonType::Exit => exit.send(AppExit),
             }
         }
     }
@@ -142,3 +187,8 @@ fn cleanup_menu(mut commands: Commands, button: Query<Entity, With<MenuUi>>) {
         commands.entity(button).despawn_recursive();
     }
 }
+
+/// Reopens the menu so its buttons are drawn in the language that was just picked
+fn rebuild_menu(mut commands: Commands) {
+    commands.insert_resource(NextState(GameState::MainMenu));
+}
@@ -1,13 +1,33 @@
-﻿use crate::ui::game_scene::SceneUiPlugin;
+﻿use crate::ui::achievements::AchievementsUiPlugin;
+use crate::ui::difficulty::DifficultyUiPlugin;
+use crate::ui::game_scene::SceneUiPlugin;
 use crate::ui::menu::MenuPlugin;
+use crate::ui::options::OptionsPlugin;
+use crate::ui::records::RecordsUiPlugin;
+use crate::ui::scale::UiScalePlugin;
+use crate::ui::skills::SkillsUiPlugin;
+use crate::ui::theme::{deserialize_hex_color, ThemePlugin};
+use crate::ui::widgets::WidgetsPlugin;
 use crate::GameState;
 use bevy::prelude::{
-    App, AssetServer, Bundle, Color, Commands, Component, Plugin, Res, ResMut, Resource,
+    App, AssetServer, Bundle, Color, Commands, Component, Font, Handle, Plugin, Res, ResMut,
+    Resource,
 };
 use iyes_loopless::prelude::AppLooplessStateExt;
+use serde::Deserialize;
 
+pub mod achievements;
+pub mod difficulty;
 pub mod game_scene;
 pub mod menu;
+pub mod motion;
+pub mod options;
+pub mod palette;
+pub mod records;
+pub mod scale;
+pub mod skills;
+pub mod theme;
+pub mod widgets;
 
 pub struct UiPlugin;
 
@@ -16,10 +36,20 @@ pub struct UiPlugin;
 impl Plugin for UiPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<UiColors>()
+            .init_resource::<UiStyle>()
             .add_enter_system(GameState::Setup, ui_setup);
         app.insert_resource(SceneUiState::default())
             .add_loopless_state(UiState::Normal);
-        app.add_plugin(MenuPlugin).add_plugin(SceneUiPlugin);
+        app.add_plugin(ThemePlugin)
+            .add_plugin(UiScalePlugin)
+            .add_plugin(WidgetsPlugin)
+            .add_plugin(MenuPlugin)
+            .add_plugin(OptionsPlugin)
+            .add_plugin(DifficultyUiPlugin)
+            .add_plugin(RecordsUiPlugin)
+            .add_plugin(AchievementsUiPlugin)
+            .add_plugin(SkillsUiPlugin)
+            .add_plugin(SceneUiPlugin);
     }
 }
 
@@ -34,25 +64,57 @@ pub enum UiState {
     Normal,
     Barter,
     Purchase,
+    Bank,
+    Ledger,
+    Options,
     Pause,
+    Dialogue,
+    Chapter,
+    RunOver,
+    Difficulty,
+    Records,
+    Achievements,
+    Skills,
+    Upgrades,
 }
 
 pub fn ui_setup(mut commands: Commands, asset_server: Res<AssetServer>) {
     println!("{}", ((104 - 0) as f32 / (255 - 0) as f32));
 }
 
-#[derive(Resource, Clone, PartialEq)]
+/// The colors of the interface. Replaced by the colors of the current theme once it's loaded,
+/// in theme files they are written as hex codes like `"#BE4A2F"`
+#[derive(Resource, Clone, PartialEq, Debug, Deserialize)]
 pub struct UiColors {
+    #[serde(deserialize_with = "deserialize_hex_color")]
     background_standard: Color,
 
+    #[serde(deserialize_with = "deserialize_hex_color")]
     button_standard: Color,
+    #[serde(deserialize_with = "deserialize_hex_color")]
     button_hovered: Color,
-    
+
+    #[serde(deserialize_with = "deserialize_hex_color")]
     success: Color,
+    #[serde(deserialize_with = "deserialize_hex_color")]
     success_hovered: Color,
-    
+
+    #[serde(deserialize_with = "deserialize_hex_color")]
     failure: Color,
+    #[serde(deserialize_with = "deserialize_hex_color")]
     failure_hovered: Color,
+
+    #[serde(deserialize_with = "deserialize_hex_color")]
+    panel: Color,
+    #[serde(deserialize_with = "deserialize_hex_color")]
+    panel_dark: Color,
+    #[serde(deserialize_with = "deserialize_hex_color")]
+    card_background: Color,
+
+    #[serde(deserialize_with = "deserialize_hex_color")]
+    text_dark: Color,
+    #[serde(deserialize_with = "deserialize_hex_color")]
+    text_light: Color,
 }
 
 impl Default for UiColors {
@@ -93,6 +155,42 @@ impl Default for UiColors {
                 (38 - 0) as f32 / (255 - 0) as f32,
                 (51 - 0) as f32 / (255 - 0) as f32,
             ),
+            panel: Color::rgb(0.65, 0.65, 0.65),
+            panel_dark: Color::rgb(0.1, 0.1, 0.1),
+            card_background: Color::rgb(1.0, 1.0, 1.0),
+            text_dark: Color::rgb(0.1, 0.1, 0.1),
+            text_light: Color::rgb(0.9, 0.9, 0.9),
+        }
+    }
+}
+
+/// The font, text sizes and borders of the interface, set by the current theme
+#[derive(Resource, Clone, PartialEq, Debug, Deserialize)]
+pub struct UiStyle {
+    /// Path of the font relative to the assets folder, loaded along with the theme
+    #[serde(rename = "font")]
+    pub font_path: String,
+    #[serde(skip)]
+    pub font: Handle<Font>,
+    pub title_size: f32,
+    pub text_size: f32,
+    pub small_size: f32,
+    /// Thickness of the colored border around cards
+    pub border_width: f32,
+    /// Space between the edge of a button and its text. Small buttons use two thirds of it
+    pub button_padding: f32,
+}
+
+impl Default for UiStyle {
+    fn default() -> Self {
+        UiStyle {
+            font_path: String::from("fonts/FiraSans-Bold.ttf"),
+            font: Handle::default(),
+            title_size: 40.0,
+            text_size: 30.0,
+            small_size: 25.0,
+            border_width: 6.0,
+            button_padding: 15.0,
         }
     }
 }
@@ -0,0 +1,35 @@
+//! Tweens for moving the interface around that respect the reduced motion setting. With reduced
+//! motion nothing slides across the screen anymore, but every transition still runs as a tween so
+//! the completion events other systems wait on keep firing
+
+use crate::settings::Settings;
+use bevy::prelude::{Style, UiRect};
+use bevy_tweening::lens::UiPositionLens;
+use bevy_tweening::{EaseFunction, Tween};
+use std::time::Duration;
+
+/// How long a transition takes with reduced motion
+pub const REDUCED_MOTION_DURATION: f32 = 0.1;
+
+/// Slides a node from `start` to `end` over `seconds`. With reduced motion the node is placed at
+/// `end` right away instead
+pub fn slide_tween(
+    settings: &Settings,
+    ease_function: EaseFunction,
+    seconds: f32,
+    start: UiRect,
+    end: UiRect,
+) -> Tween<Style> {
+    if settings.reduced_motion {
+        return Tween::new(
+            ease_function,
+            Duration::from_secs_f32(REDUCED_MOTION_DURATION),
+            UiPositionLens { start: end, end },
+        );
+    }
+    Tween::new(
+        ease_function,
+        Duration::from_secs_f32(seconds),
+        UiPositionLens { start, end },
+    )
+}
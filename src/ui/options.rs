@@ -0,0 +1,316 @@
+use crate::loading::{LocaleAssets, ThemeAssets};
+use crate::localization::{get_languages, LanguageChanged, Localization, Translations};
+use crate::settings::{Settings, VolumeSetting, UI_SCALE_STEP, VOLUME_STEP};
+use crate::ui::theme::{get_theme_names, ThemeChanged, UiTheme};
+use crate::ui::widgets::{spawn_screen_root, ButtonWidget, LabelWidget, PanelWidget};
+use crate::ui::{UiColors, UiState, UiStyle};
+use bevy::prelude::*;
+use iyes_loopless::prelude::{AppLooplessStateExt, ConditionSet, NextState};
+
+pub struct OptionsPlugin;
+
+/// This plugin is responsible for the options screen. It can be opened from the main menu and
+/// while playing, the screen is only drawn during the State `UiState::Options`
+impl Plugin for OptionsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_enter_system(UiState::Options, setup_options_ui)
+            .add_exit_system(UiState::Options, cleanup_options_ui)
+            .add_system_set(
+                ConditionSet::new()
+                    .run_in_state(UiState::Options)
+                    .with_system(click_options_button)
+                    .with_system(update_options_text)
+                    .into(),
+            )
+            .add_system_set(
+                ConditionSet::new()
+                    .run_in_state(UiState::Options)
+                    .run_on_event::<ThemeChanged>()
+                    .with_system(rebuild_options_ui)
+                    .into(),
+            )
+            .add_system_set(
+                ConditionSet::new()
+                    .run_in_state(UiState::Options)
+                    .run_on_event::<LanguageChanged>()
+                    .with_system(rebuild_options_ui)
+                    .into(),
+            );
+    }
+}
+
+#[derive(Component, Default, PartialEq, Clone)]
+pub struct OptionsUi;
+
+/// A setting that has its own row in the options
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum OptionsRow {
+    Volume(VolumeSetting),
+    Theme,
+    Palette,
+    ReducedMotion,
+    UiScale,
+    Language,
+}
+
+impl OptionsRow {
+    pub fn all() -> Vec<OptionsRow> {
+        let mut rows: Vec<OptionsRow> = VolumeSetting::ALL
+            .iter()
+            .map(|setting| OptionsRow::Volume(*setting))
+            .collect();
+        rows.push(OptionsRow::Theme);
+        rows.push(OptionsRow::Palette);
+        rows.push(OptionsRow::ReducedMotion);
+        rows.push(OptionsRow::UiScale);
+        rows.push(OptionsRow::Language);
+        rows
+    }
+}
+
+#[derive(Component, PartialEq, Clone)]
+pub struct OptionsValueText {
+    row: OptionsRow,
+}
+
+#[derive(Component, PartialEq, Clone)]
+pub struct OptionsButtonProps {
+    options_button_type: OptionsButtonType,
+}
+
+#[derive(PartialEq, Clone)]
+pub enum OptionsButtonType {
+    Decrease(OptionsRow),
+    Increase(OptionsRow),
+    Close,
+}
+
+impl OptionsButtonProps {
+    pub fn get_button_text(&self, localization: &Localization) -> String {
+        return match self.options_button_type {
+            OptionsButtonType::Decrease(OptionsRow::Volume(_) | OptionsRow::UiScale) => {
+                String::from("-")
+            }
+            OptionsButtonType::Increase(OptionsRow::Volume(_) | OptionsRow::UiScale) => {
+                String::from("+")
+            }
+            OptionsButtonType::Decrease(_) => String::from("<"),
+            OptionsButtonType::Increase(_) => String::from(">"),
+            OptionsButtonType::Close => localization.get("ui-close"),
+        };
+    }
+}
+
+fn get_value_text(row: OptionsRow, settings: &Settings, localization: &Localization) -> String {
+    return match row {
+        OptionsRow::Volume(setting) => localization.get_with(
+            "options-volume",
+            &[
+                ("setting", localization.get(setting.get_locale_key()).into()),
+                (
+                    "percent",
+                    (settings.get_volume(setting) * 100.0).round().into(),
+                ),
+            ],
+        ),
+        OptionsRow::Theme => {
+            localization.get_with("options-theme", &[("theme", settings.theme.clone().into())])
+        }
+        OptionsRow::Palette => localization.get_with(
+            "options-colors",
+            &[(
+                "palette",
+                localization.get(settings.palette.get_locale_key()).into(),
+            )],
+        ),
+        OptionsRow::ReducedMotion => localization.get_with(
+            "options-reduced-motion",
+            &[(
+                "enabled",
+                if settings.reduced_motion { "yes" } else { "no" }.into(),
+            )],
+        ),
+        OptionsRow::UiScale => localization.get_with(
+            "options-interface-size",
+            &[("percent", (settings.ui_scale * 100.0).round().into())],
+        ),
+        OptionsRow::Language => localization.get_with(
+            "options-language",
+            &[("language", localization.get("language-name").into())],
+        ),
+    };
+}
+
+/// The name `step` places away from `current`, wrapping around at both ends
+fn cycle_name(current: &str, names: &[String], step: i32) -> Option<String> {
+    if names.is_empty() {
+        return None;
+    }
+    let index = names.iter().position(|name| name == current).unwrap_or(0) as i32;
+    let next = (index + step).rem_euclid(names.len() as i32) as usize;
+    Some(names[next].clone())
+}
+
+fn setup_options_ui(
+    mut commands: Commands,
+    ui_style: Res<UiStyle>,
+    colors: Res<UiColors>,
+    settings: Res<Settings>,
+    localization: Res<Localization>,
+) {
+    spawn_screen_root(&mut commands)
+        .insert(OptionsUi)
+        .with_children(|parent| {
+            PanelWidget::new(Val::Percent(40.0), Val::Auto)
+                .absolute()
+                .with_padding(15.0)
+                .spawn(parent, &colors)
+                .with_children(|parent| {
+                    LabelWidget::title(localization.get("options-title"))
+                        .spawn(parent, &ui_style, &colors);
+
+                    for row in OptionsRow::all() {
+                        PanelWidget::new(Val::Percent(100.0), Val::Auto)
+                            .row()
+                            .with_margin(UiRect::vertical(Val::Px(5.0)))
+                            .spawn(parent, &colors)
+                            .with_children(|parent| {
+                                spawn_options_button(
+                                    parent,
+                                    OptionsButtonType::Decrease(row),
+                                    &ui_style,
+                                    &colors,
+                                    &localization,
+                                );
+                                LabelWidget::new(get_value_text(row, &settings, &localization))
+                                    .spawn(parent, &ui_style, &colors)
+                                    .insert(OptionsValueText { row });
+                                spawn_options_button(
+                                    parent,
+                                    OptionsButtonType::Increase(row),
+                                    &ui_style,
+                                    &colors,
+                                    &localization,
+                                );
+                            });
+                    }
+
+                    spawn_options_button(
+                        parent,
+                        OptionsButtonType::Close,
+                        &ui_style,
+                        &colors,
+                        &localization,
+                    );
+                });
+        });
+}
+
+fn spawn_options_button(
+    parent: &mut ChildBuilder,
+    options_button_type: OptionsButtonType,
+    ui_style: &Res<UiStyle>,
+    colors: &Res<UiColors>,
+    localization: &Res<Localization>,
+) -> Entity {
+    let props = OptionsButtonProps {
+        options_button_type,
+    };
+    ButtonWidget::new(props.get_button_text(localization))
+        .small()
+        .spawn(parent, ui_style, colors)
+        .insert(props)
+        .id()
+}
+
+fn update_options_text(
+    mut texts: Query<(&mut Text, &OptionsValueText)>,
+    settings: Res<Settings>,
+    localization: Res<Localization>,
+) {
+    if !settings.is_changed() {
+        return;
+    }
+    for (mut text, value_text) in texts.iter_mut() {
+        text.sections[0].value = get_value_text(value_text.row, &settings, &localization);
+    }
+}
+
+fn click_options_button(
+    mut commands: Commands,
+    interaction_query: Query<
+        (&Interaction, &OptionsButtonProps),
+        (Changed<Interaction>, With<Button>),
+    >,
+    mut settings: ResMut<Settings>,
+    theme_assets: Res<ThemeAssets>,
+    themes: Res<Assets<UiTheme>>,
+    locale_assets: Res<LocaleAssets>,
+    translations: Res<Assets<Translations>>,
+) {
+    for (interaction, props) in &interaction_query {
+        if let Interaction::Clicked = interaction {
+            let choices = OptionsChoices {
+                themes: get_theme_names(&theme_assets, &themes),
+                languages: get_languages(&locale_assets, &translations),
+            };
+            match props.options_button_type {
+                OptionsButtonType::Decrease(row) => {
+                    adjust_row(row, -1, &mut settings, &choices);
+                }
+                OptionsButtonType::Increase(row) => {
+                    adjust_row(row, 1, &mut settings, &choices);
+                }
+                OptionsButtonType::Close => {
+                    commands.insert_resource(NextState(UiState::Normal));
+                }
+            }
+        }
+    }
+}
+
+/// The loaded themes and languages the rows can cycle through
+struct OptionsChoices {
+    themes: Vec<String>,
+    languages: Vec<String>,
+}
+
+/// Steps the setting of a row up or down
+fn adjust_row(row: OptionsRow, direction: i32, settings: &mut Settings, choices: &OptionsChoices) {
+    match row {
+        OptionsRow::Volume(setting) => {
+            settings.adjust_volume(setting, VOLUME_STEP * direction as f32);
+        }
+        OptionsRow::Theme => {
+            if let Some(theme) = cycle_name(&settings.theme, &choices.themes, direction) {
+                settings.theme = theme;
+            }
+        }
+        OptionsRow::Palette => {
+            settings.palette = settings.palette.cycle(direction);
+        }
+        OptionsRow::ReducedMotion => {
+            settings.reduced_motion = !settings.reduced_motion;
+        }
+        OptionsRow::UiScale => {
+            settings.adjust_ui_scale(UI_SCALE_STEP * direction as f32);
+        }
+        OptionsRow::Language => {
+            if let Some(language) = cycle_name(&settings.language, &choices.languages, direction) {
+                settings.language = language;
+            }
+        }
+    }
+}
+
+/// Reopens the options so the screen is drawn with the theme, palette or language that was just
+/// picked
+fn rebuild_options_ui(mut commands: Commands) {
+    commands.insert_resource(NextState(UiState::Options));
+}
+
+fn cleanup_options_ui(mut commands: Commands, ui: Query<Entity, With<OptionsUi>>) {
+    for ui in ui.iter() {
+        commands.entity(ui).despawn_recursive();
+    }
+}
@@ -0,0 +1,92 @@
+//! Accessibility palettes that are laid over the colors of the current theme. The barter screen
+//! tells success and failure apart by color, so the colorblind palettes swap those colors for
+//! pairs that stay distinct with that kind of color blindness. High contrast replaces the whole
+//! theme with black, white and strong accent colors
+
+use crate::ui::UiColors;
+use bevy::prelude::Color;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, Serialize, Deserialize)]
+pub enum Palette {
+    #[default]
+    Standard,
+    Deuteranopia,
+    Protanopia,
+    Tritanopia,
+    HighContrast,
+}
+
+impl Palette {
+    pub const ALL: [Palette; 5] = [
+        Palette::Standard,
+        Palette::Deuteranopia,
+        Palette::Protanopia,
+        Palette::Tritanopia,
+        Palette::HighContrast,
+    ];
+
+    /// Id of the name of the palette in the translations
+    pub fn get_locale_key(&self) -> &'static str {
+        return match self {
+            Palette::Standard => "palette-standard",
+            Palette::Deuteranopia => "palette-deuteranopia",
+            Palette::Protanopia => "palette-protanopia",
+            Palette::Tritanopia => "palette-tritanopia",
+            Palette::HighContrast => "palette-high-contrast",
+        };
+    }
+
+    /// The palette `step` places away from this one, wrapping around at both ends
+    pub fn cycle(&self, step: i32) -> Palette {
+        let index = Palette::ALL
+            .iter()
+            .position(|palette| palette == self)
+            .unwrap_or(0) as i32;
+        Palette::ALL[(index + step).rem_euclid(Palette::ALL.len() as i32) as usize]
+    }
+
+    /// Replaces the theme colors this palette overrides
+    pub fn apply(&self, colors: &mut UiColors) {
+        match self {
+            Palette::Standard => {}
+            // blue and vermillion, green and red look alike without working green cones
+            Palette::Deuteranopia => {
+                colors.success = Color::hex("0072B2").unwrap();
+                colors.success_hovered = Color::hex("005A8C").unwrap();
+                colors.failure = Color::hex("D55E00").unwrap();
+                colors.failure_hovered = Color::hex("A84A00").unwrap();
+            }
+            // reds look dark without working red cones, so failure uses a lighter orange
+            Palette::Protanopia => {
+                colors.success = Color::hex("0072B2").unwrap();
+                colors.success_hovered = Color::hex("005A8C").unwrap();
+                colors.failure = Color::hex("E69F00").unwrap();
+                colors.failure_hovered = Color::hex("B87F00").unwrap();
+            }
+            // teal and magenta, blues and greens look alike without working blue cones
+            Palette::Tritanopia => {
+                colors.success = Color::hex("00858A").unwrap();
+                colors.success_hovered = Color::hex("006A6E").unwrap();
+                colors.failure = Color::hex("D81B60").unwrap();
+                colors.failure_hovered = Color::hex("AD1457").unwrap();
+            }
+            Palette::HighContrast => {
+                *colors = UiColors {
+                    background_standard: Color::BLACK,
+                    button_standard: Color::hex("1F3FBF").unwrap(),
+                    button_hovered: Color::hex("0F2A8F").unwrap(),
+                    success: Color::hex("006B2D").unwrap(),
+                    success_hovered: Color::hex("004D20").unwrap(),
+                    failure: Color::hex("B00020").unwrap(),
+                    failure_hovered: Color::hex("7F0017").unwrap(),
+                    panel: Color::WHITE,
+                    panel_dark: Color::BLACK,
+                    card_background: Color::WHITE,
+                    text_dark: Color::BLACK,
+                    text_light: Color::WHITE,
+                };
+            }
+        }
+    }
+}
@@ -0,0 +1,299 @@
+use crate::localization::Localization;
+use crate::records::{RecordStat, Records};
+use crate::run::RunMode;
+use crate::ui::widgets::{spawn_screen_root, ButtonWidget, LabelWidget, PanelWidget};
+use crate::ui::{UiColors, UiState, UiStyle};
+use bevy::prelude::*;
+use iyes_loopless::prelude::{AppLooplessStateExt, ConditionSet, NextState};
+
+pub struct RecordsUiPlugin;
+
+/// This plugin is responsible for the records screen opened from the main menu, the screen is
+/// only drawn during the State `UiState::Records`
+impl Plugin for RecordsUiPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RecordsView>()
+            .add_enter_system(UiState::Records, setup_records_ui)
+            .add_exit_system(UiState::Records, cleanup_records_ui)
+            .add_system_set(
+                ConditionSet::new()
+                    .run_in_state(UiState::Records)
+                    .with_system(click_records_button)
+                    .with_system(update_records_text)
+                    .into(),
+            );
+    }
+}
+
+/// How many runs are listed at once
+const SHOWN_RECORDS: usize = 10;
+
+/// Which runs the records screen lists and how they are sorted
+#[derive(Resource, Clone, PartialEq, Debug, Default)]
+pub struct RecordsView {
+    /// None lists the runs of every mode
+    mode: Option<RunMode>,
+    sort: RecordStat,
+}
+
+impl RecordsView {
+    fn cycle_mode(&mut self, step: i32) {
+        // every mode and None for all of them
+        let mut modes = vec![None];
+        modes.extend(RunMode::ALL.iter().map(|mode| Some(*mode)));
+        let index = modes
+            .iter()
+            .position(|mode| *mode == self.mode)
+            .unwrap_or(0) as i32;
+        self.mode = modes[(index + step).rem_euclid(modes.len() as i32) as usize];
+    }
+
+    fn cycle_sort(&mut self, step: i32) {
+        let stats = RecordStat::ALL;
+        let index = stats
+            .iter()
+            .position(|stat| *stat == self.sort)
+            .unwrap_or(0) as i32;
+        self.sort = stats[(index + step).rem_euclid(stats.len() as i32) as usize];
+    }
+}
+
+#[derive(Component, Default, PartialEq, Clone)]
+pub struct RecordsUi;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RecordsPart {
+    Mode,
+    Sort,
+    List,
+}
+
+#[derive(Component, PartialEq, Clone)]
+pub struct RecordsText {
+    part: RecordsPart,
+}
+
+#[derive(Component, PartialEq, Clone)]
+pub struct RecordsButtonProps {
+    records_button_type: RecordsButtonType,
+}
+
+#[derive(PartialEq, Clone)]
+pub enum RecordsButtonType {
+    PreviousMode,
+    NextMode,
+    PreviousSort,
+    NextSort,
+    Back,
+}
+
+impl RecordsButtonProps {
+    pub fn get_button_text(&self, localization: &Localization) -> String {
+        return match self.records_button_type {
+            RecordsButtonType::PreviousMode | RecordsButtonType::PreviousSort => String::from("<"),
+            RecordsButtonType::NextMode | RecordsButtonType::NextSort => String::from(">"),
+            RecordsButtonType::Back => localization.get("ui-back"),
+        };
+    }
+}
+
+fn get_records_text(
+    part: RecordsPart,
+    view: &RecordsView,
+    records: &Records,
+    localization: &Localization,
+) -> String {
+    return match part {
+        RecordsPart::Mode => {
+            let mode = match view.mode {
+                Some(mode) => localization.get(mode.get_locale_key()),
+                None => localization.get("records-all-modes"),
+            };
+            localization.get_with("records-mode", &[("mode", mode.into())])
+        }
+        RecordsPart::Sort => localization.get_with(
+            "records-sort",
+            &[("stat", localization.get(view.sort.get_locale_key()).into())],
+        ),
+        RecordsPart::List => {
+            let runs = records.get_sorted(view.mode, view.sort);
+            if runs.is_empty() {
+                return localization.get("records-empty");
+            }
+            runs.iter()
+                .take(SHOWN_RECORDS)
+                .map(|run| {
+                    localization.get_with(
+                        "records-entry",
+                        &[
+                            ("mode", localization.get(run.mode.get_locale_key()).into()),
+                            ("nights", run.nights.into()),
+                            ("gold", run.gold.into()),
+                            ("reputation", run.reputation.into()),
+                            ("haggles", run.haggles_won.into()),
+                            ("seed", format!("{:016x}", run.seed).into()),
+                        ],
+                    )
+                })
+                .collect::<Vec<String>>()
+                .join("\n")
+        }
+    };
+}
+
+fn setup_records_ui(
+    mut commands: Commands,
+    ui_style: Res<UiStyle>,
+    colors: Res<UiColors>,
+    view: Res<RecordsView>,
+    records: Res<Records>,
+    localization: Res<Localization>,
+) {
+    spawn_screen_root(&mut commands)
+        .insert(RecordsUi)
+        .with_children(|parent| {
+            PanelWidget::new(Val::Percent(70.0), Val::Percent(80.0))
+                .absolute()
+                .with_justify(JustifyContent::FlexStart)
+                .with_padding(15.0)
+                .spawn(parent, &colors)
+                .with_children(|parent| {
+                    LabelWidget::title(localization.get("records-title"))
+                        .spawn(parent, &ui_style, &colors);
+
+                    for (part, previous, next) in [
+                        (
+                            RecordsPart::Mode,
+                            RecordsButtonType::PreviousMode,
+                            RecordsButtonType::NextMode,
+                        ),
+                        (
+                            RecordsPart::Sort,
+                            RecordsButtonType::PreviousSort,
+                            RecordsButtonType::NextSort,
+                        ),
+                    ] {
+                        PanelWidget::new(Val::Percent(100.0), Val::Auto)
+                            .row()
+                            .with_margin(UiRect::vertical(Val::Px(5.0)))
+                            .spawn(parent, &colors)
+                            .with_children(|parent| {
+                                spawn_records_button(
+                                    parent,
+                                    previous,
+                                    &ui_style,
+                                    &colors,
+                                    &localization,
+                                );
+                                LabelWidget::new(get_records_text(
+                                    part,
+                                    &view,
+                                    &records,
+                                    &localization,
+                                ))
+                                .spawn(parent, &ui_style, &colors)
+                                .insert(RecordsText { part });
+                                spawn_records_button(
+                                    parent,
+                                    next,
+                                    &ui_style,
+                                    &colors,
+                                    &localization,
+                                );
+                            });
+                    }
+
+                    PanelWidget::new(Val::Percent(100.0), Val::Percent(60.0))
+                        .with_justify(JustifyContent::FlexStart)
+                        .with_padding(15.0)
+                        .with_color(colors.panel_dark)
+                        .spawn(parent, &colors)
+                        .with_children(|parent| {
+                            LabelWidget::new(get_records_text(
+                                RecordsPart::List,
+                                &view,
+                                &records,
+                                &localization,
+                            ))
+                            .small()
+                            .with_color(colors.text_light)
+                            .spawn(parent, &ui_style, &colors)
+                            .insert(RecordsText {
+                                part: RecordsPart::List,
+                            });
+                        });
+
+                    let props = RecordsButtonProps {
+                        records_button_type: RecordsButtonType::Back,
+                    };
+                    ButtonWidget::new(props.get_button_text(&localization))
+                        .with_margin(UiRect {
+                            top: Val::Auto,
+                            ..default()
+                        })
+                        .spawn(parent, &ui_style, &colors)
+                        .insert(props);
+                });
+        });
+}
+
+fn spawn_records_button(
+    parent: &mut ChildBuilder,
+    records_button_type: RecordsButtonType,
+    ui_style: &Res<UiStyle>,
+    colors: &Res<UiColors>,
+    localization: &Res<Localization>,
+) -> Entity {
+    let props = RecordsButtonProps {
+        records_button_type,
+    };
+    ButtonWidget::new(props.get_button_text(localization))
+        .small()
+        .spawn(parent, ui_style, colors)
+        .insert(props)
+        .id()
+}
+
+fn update_records_text(
+    mut texts: Query<(&mut Text, &RecordsText)>,
+    view: Res<RecordsView>,
+    records: Res<Records>,
+    localization: Res<Localization>,
+) {
+    if !view.is_changed() {
+        return;
+    }
+    for (mut text, records_text) in texts.iter_mut() {
+        text.sections[0].value =
+            get_records_text(records_text.part, &view, &records, &localization);
+    }
+}
+
+fn click_records_button(
+    mut commands: Commands,
+    interaction_query: Query<
+        (&Interaction, &RecordsButtonProps),
+        (Changed<Interaction>, With<Button>),
+    >,
+    mut view: ResMut<RecordsView>,
+) {
+    for (interaction, props) in &interaction_query {
+        if let Interaction::Clicked = interaction {
+            match props.records_button_type {
+                RecordsButtonType::PreviousMode => view.cycle_mode(-1),
+                RecordsButtonType::NextMode => view.cycle_mode(1),
+                RecordsButtonType::PreviousSort => view.cycle_sort(-1),
+                RecordsButtonType::NextSort => view.cycle_sort(1),
+                RecordsButtonType::Back => {
+                    commands.insert_resource(NextState(UiState::Normal));
+                }
+            }
+        }
+    }
+}
+
+fn cleanup_records_ui(mut commands: Commands, ui: Query<Entity, With<RecordsUi>>) {
+    for ui in ui.iter() {
+        commands.entity(ui).despawn_recursive();
+    }
+}
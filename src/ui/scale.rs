@@ -0,0 +1,37 @@
+//! The interface is laid out for a 1920x1080 window. Sizes in pixels, like fonts and paddings, are
+//! scaled through bevy's [`UiScale`] so the layout keeps its proportions in smaller and larger
+//! windows. Window sizes are logical pixels, so high DPI screens are already accounted for by the
+//! scale factor of the window. The interface size from the settings is applied on top
+
+use crate::settings::Settings;
+use bevy::prelude::{App, Plugin, Res, ResMut, UiScale, Windows};
+
+pub struct UiScalePlugin;
+
+impl Plugin for UiScalePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(update_ui_scale);
+    }
+}
+
+/// The window size the interface was designed for
+const REFERENCE_WIDTH: f32 = 1920.0;
+const REFERENCE_HEIGHT: f32 = 1080.0;
+
+/// How much the interface is scaled to fit a window of this size. Uses the smaller of the two
+/// ratios so nothing ends up outside the window
+pub fn get_window_scale(width: f32, height: f32) -> f32 {
+    (width / REFERENCE_WIDTH).min(height / REFERENCE_HEIGHT)
+}
+
+fn update_ui_scale(windows: Res<Windows>, settings: Res<Settings>, mut ui_scale: ResMut<UiScale>) {
+    let window = match windows.get_primary() {
+        Some(window) => window,
+        None => return,
+    };
+    let scale = (get_window_scale(window.width(), window.height()) * settings.ui_scale) as f64;
+    // only write when it changed, every text and node is laid out again when the scale changes
+    if (ui_scale.scale - scale).abs() > f64::EPSILON {
+        ui_scale.scale = scale;
+    }
+}
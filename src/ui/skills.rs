@@ -0,0 +1,219 @@
+use crate::localization::Localization;
+use crate::skills::{Progression, Skill};
+use crate::ui::widgets::{spawn_screen_root, ButtonWidget, LabelWidget, PanelWidget};
+use crate::ui::{UiColors, UiState, UiStyle};
+use bevy::prelude::*;
+use iyes_loopless::prelude::{AppLooplessStateExt, ConditionSet, NextState};
+
+pub struct SkillsUiPlugin;
+
+/// This plugin is responsible for the skill tree opened from the main menu, the screen is only
+/// drawn during the State `UiState::Skills`
+impl Plugin for SkillsUiPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_enter_system(UiState::Skills, setup_skills_ui)
+            .add_exit_system(UiState::Skills, cleanup_skills_ui)
+            .add_system_set(
+                ConditionSet::new()
+                    .run_in_state(UiState::Skills)
+                    .with_system(click_skills_button)
+                    .into(),
+            );
+    }
+}
+
+#[derive(Component, Default, PartialEq, Clone)]
+pub struct SkillsUi;
+
+#[derive(Component, PartialEq, Clone)]
+pub struct SkillsButtonProps {
+    skills_button_type: SkillsButtonType,
+}
+
+#[derive(PartialEq, Clone)]
+pub enum SkillsButtonType {
+    Learn(Skill),
+    Back,
+}
+
+impl SkillsButtonProps {
+    pub fn get_button_text(&self, localization: &Localization) -> String {
+        return match self.skills_button_type {
+            SkillsButtonType::Learn(skill) => {
+                localization.get_with("skills-learn", &[("cost", skill.get_cost().into())])
+            }
+            SkillsButtonType::Back => localization.get("ui-back"),
+        };
+    }
+}
+
+/// Why a skill that can't be learned right now can't be learned
+fn get_skill_status(
+    skill: Skill,
+    progression: &Progression,
+    localization: &Localization,
+) -> String {
+    if progression.has_skill(skill) {
+        return localization.get("skills-learned");
+    }
+    if !progression.has_requirements(skill) {
+        let requirements = skill
+            .get_requirements()
+            .iter()
+            .map(|requirement| localization.get(requirement.get_locale_key()))
+            .collect::<Vec<String>>()
+            .join(", ");
+        return localization.get_with("skills-requires", &[("skills", requirements.into())]);
+    }
+    localization.get_with("skills-cost", &[("cost", skill.get_cost().into())])
+}
+
+fn setup_skills_ui(
+    mut commands: Commands,
+    ui_style: Res<UiStyle>,
+    colors: Res<UiColors>,
+    progression: Res<Progression>,
+    localization: Res<Localization>,
+) {
+    spawn_screen_root(&mut commands)
+        .insert(SkillsUi)
+        .with_children(|parent| {
+            PanelWidget::new(Val::Percent(70.0), Val::Percent(90.0))
+                .absolute()
+                .with_justify(JustifyContent::FlexStart)
+                .with_padding(15.0)
+                .spawn(parent, &colors)
+                .with_children(|parent| {
+                    LabelWidget::title(localization.get("skills-title"))
+                        .spawn(parent, &ui_style, &colors);
+                    LabelWidget::new(localization.get_with(
+                        "skills-level",
+                        &[
+                            ("level", progression.level.into()),
+                            ("experience", progression.experience.into()),
+                            ("next", progression.get_experience_to_next_level().into()),
+                        ],
+                    ))
+                    .small()
+                    .spawn(parent, &ui_style, &colors);
+                    LabelWidget::new(
+                        localization
+                            .get_with("skills-points", &[("points", progression.points.into())]),
+                    )
+                    .small()
+                    .spawn(parent, &ui_style, &colors);
+
+                    PanelWidget::new(Val::Percent(100.0), Val::Auto)
+                        .with_justify(JustifyContent::FlexStart)
+                        .with_padding(10.0)
+                        .with_margin(UiRect::vertical(Val::Px(10.0)))
+                        .with_color(colors.panel_dark)
+                        .spawn(parent, &colors)
+                        .with_children(|parent| {
+                            for skill in Skill::ALL {
+                                spawn_skill_row(
+                                    parent,
+                                    skill,
+                                    &progression,
+                                    &ui_style,
+                                    &colors,
+                                    &localization,
+                                );
+                            }
+                        });
+
+                    let props = SkillsButtonProps {
+                        skills_button_type: SkillsButtonType::Back,
+                    };
+                    ButtonWidget::new(props.get_button_text(&localization))
+                        .with_margin(UiRect {
+                            top: Val::Auto,
+                            ..default()
+                        })
+                        .spawn(parent, &ui_style, &colors)
+                        .insert(props);
+                });
+        });
+}
+
+fn spawn_skill_row(
+    parent: &mut ChildBuilder,
+    skill: Skill,
+    progression: &Progression,
+    ui_style: &Res<UiStyle>,
+    colors: &Res<UiColors>,
+    localization: &Res<Localization>,
+) {
+    PanelWidget::new(Val::Percent(100.0), Val::Auto)
+        .row()
+        .with_margin(UiRect::vertical(Val::Px(3.0)))
+        .with_color(colors.panel_dark)
+        .spawn(parent, colors)
+        .with_children(|parent| {
+            let color = if progression.has_skill(skill) {
+                colors.success
+            } else {
+                colors.text_light
+            };
+            PanelWidget::new(Val::Percent(70.0), Val::Auto)
+                .with_color(colors.panel_dark)
+                .spawn(parent, colors)
+                .with_children(|parent| {
+                    LabelWidget::new(localization.get(skill.get_locale_key()))
+                        .with_color(color)
+                        .spawn(parent, ui_style, colors);
+                    LabelWidget::new(
+                        localization.get(&format!("{}-description", skill.get_locale_key())),
+                    )
+                    .small()
+                    .with_color(colors.text_light)
+                    .spawn(parent, ui_style, colors);
+                });
+
+            if progression.can_learn(skill) {
+                let props = SkillsButtonProps {
+                    skills_button_type: SkillsButtonType::Learn(skill),
+                };
+                ButtonWidget::new(props.get_button_text(localization))
+                    .small()
+                    .spawn(parent, ui_style, colors)
+                    .insert(props);
+            } else {
+                LabelWidget::new(get_skill_status(skill, progression, localization))
+                    .small()
+                    .with_color(color)
+                    .spawn(parent, ui_style, colors);
+            }
+        });
+}
+
+fn click_skills_button(
+    mut commands: Commands,
+    interaction_query: Query<
+        (&Interaction, &SkillsButtonProps),
+        (Changed<Interaction>, With<Button>),
+    >,
+    mut progression: ResMut<Progression>,
+) {
+    for (interaction, props) in &interaction_query {
+        if let Interaction::Clicked = interaction {
+            match props.skills_button_type {
+                SkillsButtonType::Learn(skill) => {
+                    // built again to show what the learned skill unlocked
+                    if progression.learn(skill) {
+                        commands.insert_resource(NextState(UiState::Skills));
+                    }
+                }
+                SkillsButtonType::Back => {
+                    commands.insert_resource(NextState(UiState::Normal));
+                }
+            }
+        }
+    }
+}
+
+fn cleanup_skills_ui(mut commands: Commands, ui: Query<Entity, With<SkillsUi>>) {
+    for ui in ui.iter() {
+        commands.entity(ui).despawn_recursive();
+    }
+}
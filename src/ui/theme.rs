@@ -0,0 +1,128 @@
+//! The look of the interface comes from the theme files in `assets/themes`. A theme sets the
+//! [`UiColors`] and the [`UiStyle`] every widget reads, and the player picks one in the options.
+//! The accessibility [`Palette`] picked in the options is laid over the colors of the theme.
+//! Other screens that are already open keep their look until they are opened again, only the
+//! options screen is rebuilt right away so the player sees the theme they picked.
+//! Builds with the `dev` feature watch the asset folder, so saving a theme file applies it
+//! without restarting the game
+
+use crate::loading::ThemeAssets;
+use crate::persistence::{RonAsset, RonAssetLoader};
+use crate::settings::Settings;
+use crate::ui::palette::Palette;
+use crate::ui::{UiColors, UiStyle};
+use crate::GameState;
+use bevy::asset::{AssetPath, LoadContext};
+use bevy::prelude::{
+    AddAsset, App, AssetEvent, Assets, Color, EventReader, EventWriter, Plugin, Res, ResMut,
+};
+use bevy::reflect::TypeUuid;
+use iyes_loopless::prelude::{AppLooplessStateExt, ConditionSet};
+use serde::{Deserialize, Deserializer};
+
+pub struct ThemePlugin;
+
+impl Plugin for ThemePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_asset::<UiTheme>()
+            .init_asset_loader::<RonAssetLoader<UiTheme>>()
+            .add_event::<ThemeChanged>()
+            .add_enter_system(GameState::Setup, apply_theme)
+            .add_system_set(
+                ConditionSet::new()
+                    .run_if_resource_exists::<ThemeAssets>()
+                    .with_system(apply_theme)
+                    .into(),
+            );
+    }
+}
+
+/// Sent whenever [`UiColors`] and [`UiStyle`] were replaced by another theme, a reloaded one or
+/// after the palette changed
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct ThemeChanged;
+
+#[derive(Clone, PartialEq, Debug, Deserialize, TypeUuid)]
+#[uuid = "0d8e6a2c-5f47-4c1b-b3e9-7a91c4d2e6f3"]
+pub struct UiTheme {
+    /// Shown in the options and stored in the settings to remember the choice
+    pub name: String,
+    pub colors: UiColors,
+    pub style: UiStyle,
+}
+
+/// Themes are `.theme.ron` files. The font of the theme is loaded along with it
+impl RonAsset for UiTheme {
+    const EXTENSIONS: &'static [&'static str] = &["theme.ron"];
+
+    fn load_dependencies(&mut self, load_context: &mut LoadContext) -> Vec<AssetPath<'static>> {
+        let font_path = AssetPath::from(self.style.font_path.as_str()).to_owned();
+        self.style.font = load_context.get_handle(font_path.clone());
+        vec![font_path]
+    }
+}
+
+/// Reads a color written as a hex code, with or without the leading `#`
+pub fn deserialize_hex_color<'de, D>(deserializer: D) -> Result<Color, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let hex = String::deserialize(deserializer)?;
+    Color::hex(hex.trim_start_matches('#')).map_err(serde::de::Error::custom)
+}
+
+/// The names of every loaded theme, in the order they are listed in [`ThemeAssets`]
+pub fn get_theme_names(theme_assets: &ThemeAssets, themes: &Assets<UiTheme>) -> Vec<String> {
+    theme_assets
+        .themes
+        .iter()
+        .filter_map(|handle| themes.get(handle))
+        .map(|theme| theme.name.clone())
+        .collect()
+}
+
+/// The theme picked in the settings. Falls back to the first theme if it doesn't exist anymore
+pub fn get_selected_theme<'a>(
+    settings: &Settings,
+    theme_assets: &ThemeAssets,
+    themes: &'a Assets<UiTheme>,
+) -> Option<&'a UiTheme> {
+    let loaded: Vec<&UiTheme> = theme_assets
+        .themes
+        .iter()
+        .filter_map(|handle| themes.get(handle))
+        .collect();
+    loaded
+        .iter()
+        .find(|theme| theme.name == settings.theme)
+        .or(loaded.first())
+        .copied()
+}
+
+fn apply_theme(
+    mut theme_events: EventReader<AssetEvent<UiTheme>>,
+    theme_assets: Res<ThemeAssets>,
+    themes: Res<Assets<UiTheme>>,
+    settings: Res<Settings>,
+    mut colors: ResMut<UiColors>,
+    mut ui_style: ResMut<UiStyle>,
+    mut theme_changed: EventWriter<ThemeChanged>,
+) {
+    // a theme file was (re)loaded, the selected theme is looked up again below anyway
+    let reloaded = theme_events.iter().count() > 0;
+    if !reloaded && !settings.is_changed() {
+        return;
+    }
+    let theme = match get_selected_theme(&settings, &theme_assets, &themes) {
+        Some(theme) => theme,
+        None => return,
+    };
+    let mut theme_colors = theme.colors.clone();
+    settings.palette.apply(&mut theme_colors);
+    if *colors == theme_colors && *ui_style == theme.style {
+        return;
+    }
+    *colors = theme_colors;
+    *ui_style = theme.style.clone();
+    theme_changed.send_default();
+}
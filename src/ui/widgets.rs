@@ -0,0 +1,474 @@
+//! Shared building blocks for the interface. Screens put their layouts together out of these
+//! widgets instead of spawning the bundles by hand. Every widget takes its colors from
+//! [`UiColors`] and its font, text sizes and borders from [`UiStyle`], both set by the current
+//! theme. Buttons are recolored on hover by a single system, so the screens only have to react to
+//! the clicks themselves
+
+use crate::ui::{UiColors, UiStyle};
+use bevy::ecs::system::EntityCommands;
+use bevy::prelude::*;
+
+pub struct WidgetsPlugin;
+
+impl Plugin for WidgetsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(update_button_colors)
+            .add_system(update_progress_bars);
+    }
+}
+
+/// Marks the buttons spawned by a [`ButtonWidget`] so they are recolored on hover
+#[derive(Component, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct WidgetButton;
+
+/// A full screen node that centers its children, used as the root of every screen
+pub fn spawn_screen_root<'w, 's, 'a>(
+    commands: &'a mut Commands<'w, 's>,
+) -> EntityCommands<'w, 's, 'a> {
+    commands.spawn(NodeBundle {
+        style: Style {
+            size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            position_type: PositionType::Absolute,
+            ..default()
+        },
+        ..default()
+    })
+}
+
+/// A button with a single line of text
+pub struct ButtonWidget {
+    text: String,
+    small: bool,
+    width: Val,
+    margin: UiRect,
+}
+
+impl ButtonWidget {
+    pub fn new(text: impl Into<String>) -> ButtonWidget {
+        ButtonWidget {
+            text: text.into(),
+            small: false,
+            width: Val::Auto,
+            margin: UiRect::default(),
+        }
+    }
+
+    /// A smaller button for rows and secondary actions
+    pub fn small(mut self) -> ButtonWidget {
+        self.small = true;
+        self
+    }
+
+    pub fn with_width(mut self, width: Val) -> ButtonWidget {
+        self.width = width;
+        self
+    }
+
+    pub fn with_margin(mut self, margin: UiRect) -> ButtonWidget {
+        self.margin = margin;
+        self
+    }
+
+    pub fn spawn<'w, 's, 'a>(
+        self,
+        parent: &'a mut ChildBuilder<'w, 's, '_>,
+        ui_style: &UiStyle,
+        colors: &UiColors,
+    ) -> EntityCommands<'w, 's, 'a> {
+        let (label, padding) = if self.small {
+            (
+                LabelWidget::new(self.text),
+                ui_style.button_padding * 2.0 / 3.0,
+            )
+        } else {
+            (LabelWidget::title(self.text), ui_style.button_padding)
+        };
+        let mut button = parent.spawn(ButtonBundle {
+            style: Style {
+                size: Size::new(self.width, Val::Auto),
+                padding: UiRect::all(Val::Px(padding)),
+                margin: self.margin,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            background_color: colors.button_standard.into(),
+            ..Default::default()
+        });
+        button.insert(WidgetButton).with_children(|parent| {
+            label
+                .with_color(colors.text_light)
+                .spawn(parent, ui_style, colors);
+        });
+        button
+    }
+}
+
+/// Which of the text sizes of the [`UiStyle`] a label uses
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+enum TextSize {
+    Title,
+    #[default]
+    Text,
+    Small,
+}
+
+/// A block of text
+pub struct LabelWidget {
+    text: String,
+    size: TextSize,
+    color: Option<Color>,
+    style: Style,
+}
+
+impl LabelWidget {
+    pub fn new(text: impl Into<String>) -> LabelWidget {
+        LabelWidget {
+            text: text.into(),
+            size: TextSize::Text,
+            color: None,
+            style: Style::default(),
+        }
+    }
+
+    /// A label for the top of a panel
+    pub fn title(text: impl Into<String>) -> LabelWidget {
+        let mut label = LabelWidget::new(text);
+        label.size = TextSize::Title;
+        label
+    }
+
+    /// Smaller text for captions and dense screens
+    pub fn small(mut self) -> LabelWidget {
+        self.size = TextSize::Small;
+        self
+    }
+
+    /// Defaults to the dark text color, meant for the standard panels
+    pub fn with_color(mut self, color: Color) -> LabelWidget {
+        self.color = Some(color);
+        self
+    }
+
+    pub fn with_style(mut self, style: Style) -> LabelWidget {
+        self.style = style;
+        self
+    }
+
+    pub fn spawn<'w, 's, 'a>(
+        self,
+        parent: &'a mut ChildBuilder<'w, 's, '_>,
+        ui_style: &UiStyle,
+        colors: &UiColors,
+    ) -> EntityCommands<'w, 's, 'a> {
+        let font_size = match self.size {
+            TextSize::Title => ui_style.title_size,
+            TextSize::Text => ui_style.text_size,
+            TextSize::Small => ui_style.small_size,
+        };
+        parent.spawn(TextBundle {
+            style: self.style,
+            text: Text {
+                sections: vec![TextSection {
+                    value: self.text,
+                    style: TextStyle {
+                        font: ui_style.font.clone(),
+                        font_size,
+                        color: self.color.unwrap_or(colors.text_dark),
+                    },
+                }],
+                alignment: Default::default(),
+            },
+            ..Default::default()
+        })
+    }
+}
+
+/// A node holding other widgets. Panels lay their children out in a column by default
+pub struct PanelWidget {
+    style: Style,
+    color: Option<Color>,
+}
+
+impl PanelWidget {
+    pub fn new(width: Val, height: Val) -> PanelWidget {
+        PanelWidget {
+            style: Style {
+                size: Size::new(width, height),
+                justify_content: JustifyContent::SpaceBetween,
+                flex_direction: FlexDirection::Column,
+                position_type: PositionType::Relative,
+                ..default()
+            },
+            color: None,
+        }
+    }
+
+    /// Lays the children out in a row instead of a column
+    pub fn row(mut self) -> PanelWidget {
+        self.style.flex_direction = FlexDirection::Row;
+        self.style.align_items = AlignItems::Center;
+        self
+    }
+
+    /// Takes the panel out of the layout of its parent, used for the main panel of a screen
+    pub fn absolute(mut self) -> PanelWidget {
+        self.style.position_type = PositionType::Absolute;
+        self
+    }
+
+    pub fn with_justify(mut self, justify_content: JustifyContent) -> PanelWidget {
+        self.style.justify_content = justify_content;
+        self
+    }
+
+    pub fn with_padding(mut self, padding: f32) -> PanelWidget {
+        self.style.padding = UiRect::all(Val::Px(padding));
+        self
+    }
+
+    pub fn with_margin(mut self, margin: UiRect) -> PanelWidget {
+        self.style.margin = margin;
+        self
+    }
+
+    /// Defaults to the standard panel color
+    pub fn with_color(mut self, color: Color) -> PanelWidget {
+        self.color = Some(color);
+        self
+    }
+
+    pub fn spawn<'w, 's, 'a>(
+        self,
+        parent: &'a mut ChildBuilder<'w, 's, '_>,
+        colors: &UiColors,
+    ) -> EntityCommands<'w, 's, 'a> {
+        parent.spawn(NodeBundle {
+            style: self.style,
+            background_color: self.color.unwrap_or(colors.panel).into(),
+            ..default()
+        })
+    }
+}
+
+/// A bordered card with a colored title band above a line of text
+pub struct CardWidget {
+    title: String,
+    body: String,
+    accent: Color,
+    icon: Option<Handle<Image>>,
+}
+
+impl CardWidget {
+    pub fn new(title: impl Into<String>, body: impl Into<String>, accent: Color) -> CardWidget {
+        CardWidget {
+            title: title.into(),
+            body: body.into(),
+            accent,
+            icon: None,
+        }
+    }
+
+    /// Shows an icon in front of the title, so the card can be told apart without its color
+    pub fn with_icon(mut self, icon: Handle<Image>) -> CardWidget {
+        self.icon = Some(icon);
+        self
+    }
+
+    pub fn spawn<'w, 's, 'a>(
+        self,
+        parent: &'a mut ChildBuilder<'w, 's, '_>,
+        ui_style: &UiStyle,
+        colors: &UiColors,
+    ) -> EntityCommands<'w, 's, 'a> {
+        // spawn in two parts so that its split up and we have a border
+        let mut card = parent.spawn(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(65.0), Val::Percent(100.0)),
+                padding: UiRect::all(Val::Px(ui_style.border_width)),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                position_type: PositionType::Relative,
+                ..default()
+            },
+            background_color: self.accent.into(),
+            ..default()
+        });
+        card.with_children(|parent| {
+            // actual interior that we want to use
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::Center,
+                        flex_direction: FlexDirection::Column,
+                        position_type: PositionType::Relative,
+                        ..default()
+                    },
+                    background_color: colors.card_background.into(),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent
+                        .spawn(NodeBundle {
+                            style: Style {
+                                size: Size::new(Val::Percent(100.0), Val::Auto),
+                                padding: UiRect {
+                                    left: Val::Px(20.0),
+                                    right: Val::Px(20.0),
+                                    top: Val::Px(10.0),
+                                    bottom: Val::Px(20.0),
+                                },
+                                justify_content: JustifyContent::FlexStart,
+                                align_items: AlignItems::Center,
+                                position_type: PositionType::Relative,
+                                ..default()
+                            },
+                            background_color: self.accent.into(),
+                            ..default()
+                        })
+                        .with_children(|parent| {
+                            if let Some(icon) = self.icon {
+                                parent.spawn(ImageBundle {
+                                    style: Style {
+                                        size: Size::new(
+                                            Val::Px(ui_style.title_size),
+                                            Val::Px(ui_style.title_size),
+                                        ),
+                                        margin: UiRect::right(Val::Px(10.0)),
+                                        ..default()
+                                    },
+                                    image: icon.into(),
+                                    background_color: colors.card_background.into(),
+                                    ..default()
+                                });
+                            }
+                            LabelWidget::title(self.title)
+                                .with_color(colors.card_background)
+                                .spawn(parent, ui_style, colors);
+                        });
+
+                    parent
+                        .spawn(NodeBundle {
+                            style: Style {
+                                size: Size::new(Val::Percent(100.0), Val::Auto),
+                                padding: UiRect::all(Val::Px(20.0)),
+                                align_items: AlignItems::Center,
+                                justify_content: JustifyContent::FlexStart,
+                                position_type: PositionType::Relative,
+                                ..default()
+                            },
+                            background_color: colors.card_background.into(),
+                            ..default()
+                        })
+                        .with_children(|parent| {
+                            LabelWidget::title(self.body).spawn(parent, ui_style, colors);
+                        });
+                });
+        });
+        card
+    }
+}
+
+/// A horizontal bar that fills up to `value`, from 0.0 to 1.0. Below `warning_below` the bar is
+/// drawn in the failure color
+#[derive(Component, Clone, Copy, PartialEq, Debug)]
+pub struct ProgressBar {
+    pub value: f32,
+    pub warning_below: f32,
+}
+
+#[derive(Component, Clone, Copy, PartialEq, Eq, Debug, Default)]
+struct ProgressBarFill;
+
+pub struct ProgressBarWidget {
+    bar: ProgressBar,
+    height: f32,
+}
+
+impl ProgressBarWidget {
+    pub fn new(value: f32) -> ProgressBarWidget {
+        ProgressBarWidget {
+            bar: ProgressBar {
+                value,
+                warning_below: 0.0,
+            },
+            height: 20.0,
+        }
+    }
+
+    pub fn with_warning_below(mut self, warning_below: f32) -> ProgressBarWidget {
+        self.bar.warning_below = warning_below;
+        self
+    }
+
+    pub fn spawn<'w, 's, 'a>(
+        self,
+        parent: &'a mut ChildBuilder<'w, 's, '_>,
+        colors: &UiColors,
+    ) -> EntityCommands<'w, 's, 'a> {
+        let mut bar = parent.spawn(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Px(self.height)),
+                position_type: PositionType::Relative,
+                ..default()
+            },
+            background_color: colors.panel_dark.into(),
+            ..default()
+        });
+        bar.insert(self.bar).with_children(|parent| {
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        size: Size::new(
+                            Val::Percent(self.bar.value.clamp(0.0, 1.0) * 100.0),
+                            Val::Percent(100.0),
+                        ),
+                        ..default()
+                    },
+                    background_color: colors.success.into(),
+                    ..default()
+                })
+                .insert(ProgressBarFill);
+        });
+        bar
+    }
+}
+
+fn update_button_colors(
+    colors: Res<UiColors>,
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor),
+        (Changed<Interaction>, With<Button>, With<WidgetButton>),
+    >,
+) {
+    for (interaction, mut color) in &mut interaction_query {
+        *color = match interaction {
+            Interaction::None => colors.button_standard,
+            _ => colors.button_hovered,
+        }
+        .into();
+    }
+}
+
+fn update_progress_bars(
+    colors: Res<UiColors>,
+    bars: Query<(&ProgressBar, &Children), Changed<ProgressBar>>,
+    mut fills: Query<(&mut Style, &mut BackgroundColor), With<ProgressBarFill>>,
+) {
+    for (bar, children) in bars.iter() {
+        for child in children.iter() {
+            if let Ok((mut style, mut color)) = fills.get_mut(*child) {
+                style.size.width = Val::Percent(bar.value.clamp(0.0, 1.0) * 100.0);
+                *color = if bar.value < bar.warning_below {
+                    colors.failure.into()
+                } else {
+                    colors.success.into()
+                };
+            }
+        }
+    }
+}
@@ -0,0 +1,149 @@
+//! Upgrades are bought for the shop with gold from the upgrades screen and stay for the rest of
+//! the run. A bigger counter lets more customers line up, display cases make items worth more to
+//! customers and show what they are after, candles and décor earn reputation every night and a back room makes room for more
+//! stock. Every level of an upgrade is drawn on the shop, see [`crate::scene`]
+
+use crate::night::NightEnded;
+use crate::player::Reputation;
+use crate::GameState;
+use bevy::prelude::{App, EventReader, Plugin, Res, ResMut, Resource};
+use bevy::utils::HashMap;
+use iyes_loopless::prelude::ConditionSet;
+use serde::{Deserialize, Serialize};
+
+pub struct UpgradesPlugin;
+
+impl Plugin for UpgradesPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ShopUpgrades>().add_system_set(
+            ConditionSet::new()
+                .run_in_state(GameState::Playing)
+                .run_on_event::<NightEnded>()
+                .with_system(gain_decor_reputation)
+                .into(),
+        );
+    }
+}
+
+/// No upgrade goes past this level
+pub const MAX_UPGRADE_LEVEL: u32 = 3;
+/// How much stock fits into the shop without a back room
+const BASE_STOCK_CAPACITY: u32 = 20;
+/// How much more stock fits into the shop with every level of the back room
+const BACK_ROOM_CAPACITY: u32 = 10;
+/// How much more customers pay for items with every level of the display cases, in percent
+const DISPLAY_CASE_VALUE: u32 = 10;
+/// Reputation earned every night with every level of décor
+const DECOR_REPUTATION: i32 = 1;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+pub enum ShopUpgrade {
+    Counter,
+    DisplayCases,
+    Decor,
+    BackRoom,
+}
+
+impl ShopUpgrade {
+    pub const ALL: [ShopUpgrade; 4] = [
+        ShopUpgrade::Counter,
+        ShopUpgrade::DisplayCases,
+        ShopUpgrade::Decor,
+        ShopUpgrade::BackRoom,
+    ];
+
+    /// Id of the name of the upgrade in the translations, the description is the same id
+    /// followed by `-description`
+    pub fn get_locale_key(&self) -> &'static str {
+        return match self {
+            ShopUpgrade::Counter => "upgrade-counter",
+            ShopUpgrade::DisplayCases => "upgrade-display-cases",
+            ShopUpgrade::Decor => "upgrade-decor",
+            ShopUpgrade::BackRoom => "upgrade-back-room",
+        };
+    }
+
+    /// What the first level costs, every level after costs that much more
+    fn get_base_cost(&self) -> u32 {
+        return match self {
+            ShopUpgrade::Counter => 250,
+            ShopUpgrade::DisplayCases => 300,
+            ShopUpgrade::Decor => 150,
+            ShopUpgrade::BackRoom => 200,
+        };
+    }
+
+    /// The tag in `shop_bg.aseprite` with the frames of the upgrade at the level
+    pub fn get_tag(&self, level: u32) -> String {
+        let name = match self {
+            ShopUpgrade::Counter => "Counter",
+            ShopUpgrade::DisplayCases => "DisplayCases",
+            ShopUpgrade::Decor => "Decor",
+            ShopUpgrade::BackRoom => "BackRoom",
+        };
+        format!("{} {}", name, level)
+    }
+}
+
+/// The upgrades bought in the current run and their levels
+#[derive(Resource, Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ShopUpgrades {
+    levels: HashMap<ShopUpgrade, u32>,
+}
+
+impl ShopUpgrades {
+    pub fn get_level(&self, upgrade: ShopUpgrade) -> u32 {
+        self.levels.get(&upgrade).copied().unwrap_or(0)
+    }
+
+    /// What the next level of the upgrade costs, None once it's fully upgraded
+    pub fn get_next_cost(&self, upgrade: ShopUpgrade) -> Option<u32> {
+        let level = self.get_level(upgrade);
+        if level >= MAX_UPGRADE_LEVEL {
+            return None;
+        }
+        Some(upgrade.get_base_cost() * (level + 1))
+    }
+
+    /// Goes up a level, the gold has to be paid by the caller
+    pub fn upgrade(&mut self, upgrade: ShopUpgrade) {
+        let level = self.levels.entry(upgrade).or_insert(0);
+        *level = (*level + 1).min(MAX_UPGRADE_LEVEL);
+    }
+
+    /// How many more customers fit into the line
+    pub fn get_extra_customers(&self) -> u32 {
+        self.get_level(ShopUpgrade::Counter)
+    }
+
+    /// What customers think the item is worth, from what it's worth on the market
+    pub fn get_item_value(&self, value: u32) -> u32 {
+        value * (100 + DISPLAY_CASE_VALUE * self.get_level(ShopUpgrade::DisplayCases)) / 100
+    }
+
+    /// How much sharper appraisals are, customers give themselves away looking into the display
+    /// cases
+    pub fn get_appraisal_precision(&self) -> u32 {
+        self.get_level(ShopUpgrade::DisplayCases)
+    }
+
+    pub fn get_nightly_reputation(&self) -> i32 {
+        DECOR_REPUTATION * self.get_level(ShopUpgrade::Decor) as i32
+    }
+
+    /// How many items fit into the shop
+    pub fn get_stock_capacity(&self) -> u32 {
+        BASE_STOCK_CAPACITY + BACK_ROOM_CAPACITY * self.get_level(ShopUpgrade::BackRoom)
+    }
+}
+
+fn gain_decor_reputation(
+    mut events: EventReader<NightEnded>,
+    upgrades: Res<ShopUpgrades>,
+    mut reputation: ResMut<Reputation>,
+) {
+    for _ in events.iter() {
+        reputation.change(upgrades.get_nightly_reputation());
+    }
+}